@@ -1,4 +1,4 @@
-use linalg_rs::Matrix;
+use linalg_rs::{MatMulAlgo, Matrix};
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
@@ -10,6 +10,10 @@ fn matmul_bench(c: &mut Criterion) {
     c.bench_function("MxN @ NxP dense matmul", |b| {
         b.iter(|| x.matmul(&y).unwrap())
     });
+
+    c.bench_function("MxN @ NxP dense naive matmul", |b| {
+        b.iter(|| x.matmul_with(&y, MatMulAlgo::Naive).unwrap())
+    });
 }
 
 criterion_group!(benches, matmul_bench);
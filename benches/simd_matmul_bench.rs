@@ -0,0 +1,23 @@
+use linalg_rs::{MatMulAlgo, Matrix};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+// Benchmark comparing the scalar matmul against the dispatched one,
+// which uses SIMD kernels when the CPU supports them
+fn simd_matmul_bench(c: &mut Criterion) {
+    let x = black_box(Matrix::<f64>::randomize((128, 128)));
+    let y = black_box(Matrix::<f64>::randomize((128, 128)));
+
+    let mut group = c.benchmark_group("NxN @ NxN f64 matmul");
+
+    group.bench_function("scalar", |b| {
+        b.iter(|| x.matmul_with(&y, MatMulAlgo::Naive).unwrap())
+    });
+
+    group.bench_function("dispatched", |b| b.iter(|| x.matmul(&y).unwrap()));
+
+    group.finish();
+}
+
+criterion_group!(benches, simd_matmul_bench);
+criterion_main!(benches);
@@ -10,7 +10,7 @@ fn main() {
     // Will not multiply if dimensions does not line up
     c.mul_self(&b);
 
-    let d = c.add_val(42f64).pow(3.0).dot(&b).unwrap().sub_val(EF64);
+    let d = c.add_val(42f64).powf(3.0).hadamard(&b).unwrap().sub_val(EF64);
 
     d.print(5);
 }
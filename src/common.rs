@@ -54,6 +54,49 @@ impl MatrixElement for i128 {}
 impl MatrixElement for f32 {}
 impl MatrixElement for f64 {}
 
+/// Trait BinaryElement represents the element types
+/// that can be encoded with `Matrix::to_bytes`
+pub trait BinaryElement: MatrixElement {
+    /// Tag identifying the type in the binary format of `Matrix::to_bytes`.
+    /// Has to be unique among the element types
+    const DTYPE_TAG: u8;
+
+    /// Appends the little-endian bytes of the value,
+    /// exactly `size_of::<Self>()` of them
+    fn write_le_bytes(self, out: &mut Vec<u8>);
+
+    /// Reads a value back from exactly `size_of::<Self>()` little-endian bytes
+    fn read_le_bytes(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_binary_element {
+    ($($t:ty => $tag:expr),* $(,)?) => {
+        $(
+            impl BinaryElement for $t {
+                const DTYPE_TAG: u8 = $tag;
+
+                fn write_le_bytes(self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_le_bytes());
+                }
+
+                fn read_le_bytes(bytes: &[u8]) -> Self {
+                    Self::from_le_bytes(bytes.try_into().unwrap())
+                }
+            }
+        )*
+    };
+}
+
+impl_binary_element!(
+    i8 => 0,
+    i16 => 1,
+    i32 => 2,
+    i64 => 3,
+    i128 => 4,
+    f32 => 5,
+    f64 => 6,
+);
+
 /// Some operations can only be done on floats,
 /// and these can be implemented both for Matrix,
 /// and sparse matrix
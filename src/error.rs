@@ -24,7 +24,19 @@ pub enum MatrixError {
     /// Divide by zero
     MatrixDivideByZeroError,
     /// File read error
-    MatrixFileReadError(&'static str),
+    MatrixFileReadError(String),
+    /// File write error
+    MatrixFileWriteError(String),
+    /// The operation is only defined for square matrices
+    NotSquareError,
+    /// The matrix is singular, so it has no inverse
+    /// and linear systems with it have no unique solution
+    SingularMatrixError,
+    /// An operation was given values outside of its mathematical domain,
+    /// like the square root of a negative number
+    DomainError(&'static str),
+    /// Encoding a matrix into a text format failed
+    MatrixSerializationError(String),
 }
 
 impl Display for MatrixError {
@@ -57,6 +69,21 @@ impl Display for MatrixError {
             MatrixError::MatrixFileReadError(path) => {
                 write!(f, "Could not read file from path: {}", path)
             }
+            MatrixError::MatrixFileWriteError(path) => {
+                write!(f, "Could not write file to path: {}", path)
+            }
+            MatrixError::NotSquareError => write!(f, "The matrix is not square"),
+            MatrixError::SingularMatrixError => write!(f, "The matrix is singular"),
+            MatrixError::DomainError(reason) => {
+                write!(
+                    f,
+                    "Value outside of the domain of the operation: {}",
+                    reason
+                )
+            }
+            MatrixError::MatrixSerializationError(reason) => {
+                write!(f, "Could not serialize matrix: {}", reason)
+            }
         }
     }
 }
@@ -1,8 +1,48 @@
-use std::{error::Error, ops::RangeInclusive, str::FromStr};
+use std::{error::Error, mem::size_of, ops::RangeInclusive, str::FromStr};
 
+use itertools::iproduct;
+use num_traits::{real::Real, Float};
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::optim;
+use crate::{at, Dimension, Matrix, MatrixElement, MatrixError};
+
+// Dimensions longer than this get truncated when printed
+const PRINT_THRESHOLD: usize = 10;
+// Number of leading and trailing rows/cols shown when truncating
+const PRINT_EDGE_ITEMS: usize = 3;
+
+// Upper bound on QR iterations per eigenvalue before giving up
+const EIGEN_MAX_ITERATIONS: usize = 1000;
+
+// Leading bytes of every binary encoded matrix
+pub(crate) const BINARY_MAGIC: [u8; 4] = *b"LNRS";
+// Magic, dtype tag, then nrows and ncols as little-endian u64
+pub(crate) const BINARY_HEADER_LEN: usize = BINARY_MAGIC.len() + 1 + 2 * size_of::<u64>();
+
+// Element-wise functions shared by LinAlgFloats and LinAlgReals.
+// Every Float is Real, so both trait impls go through apply_real
+pub(crate) enum RealOp<T> {
+    Log(T),
+    Ln,
+    Sqrt,
+    Sin,
+    Cos,
+    Tan,
+    Sinh,
+    Cosh,
+    Tanh,
+}
 
-use crate::{at, Matrix, MatrixElement};
+// JSON schema of a dense matrix, data is in row-major order.
+// Generic over the container so encoding can borrow the data
+#[derive(Serialize, Deserialize)]
+pub(crate) struct MatrixJson<D> {
+    pub(crate) nrows: usize,
+    pub(crate) ncols: usize,
+    pub(crate) data: D,
+}
 
 pub fn swap(lhs: &mut usize, rhs: &mut usize) {
     let temp = *lhs;
@@ -23,12 +63,24 @@ where
             1 => self.at(0, 0),
             2 => Self::det_2x2(self),
             3 => Self::det_3x3(self),
-            n => Self::det_nxn(self.data.clone(), n),
+            // LU divides, which truncates on integers,
+            // so those keep the exact cofactor expansion
+            n if Self::is_integral() => Self::det_nxn(self.data.clone(), n),
+            _ => Self::det_lu(self),
         }
     }
 
+    // Whether T is an integer type, found by checking if 1 / 2 truncates
+    #[inline(always)]
+    pub(crate) fn is_integral() -> bool {
+        T::one() / (T::one() + T::one()) == T::zero()
+    }
+
     // General helper function calling out to other matmuls based on target architecture
-    pub fn matmul_helper(&self, other: &Self) -> Self {
+    pub fn matmul_helper(&self, other: &Self) -> Self
+    where
+        T: 'static,
+    {
         match (self.shape(), other.shape()) {
             ((1, 2), (2, 1)) => return self.onetwo_by_twoone(other),
             ((2, 2), (2, 1)) => return self.twotwo_by_twoone(other),
@@ -39,9 +91,9 @@ where
 
         // Target Detection
 
-        // if let Some(result) = optim::get_optimized_matmul(self, other) {
-        //     return result;
-        // }
+        if let Some(result) = optim::get_optimized_matmul(self, other) {
+            return result;
+        }
 
         let blck_size = Self::get_block_size(self, other);
 
@@ -57,6 +109,151 @@ where
         Self::optimized_blocked_matmul(self, other, blck_size)
     }
 
+    // Reduces every column (Row) or every row (Col) down to a single value.
+    // Reducing over rows gives a 1 x ncols matrix,
+    // reducing over columns gives a nrows x 1 matrix
+    pub(crate) fn reduce_axis<F>(&self, dim: Dimension, f: F) -> Self
+    where
+        F: Fn(&[T]) -> T + Sync + Send,
+    {
+        match dim {
+            Dimension::Row => {
+                let data: Vec<T> = (0..self.ncols)
+                    .into_par_iter()
+                    .map(|j| f(&self.col(j).unwrap()))
+                    .collect();
+
+                Self::new(data, (1, self.ncols)).unwrap()
+            }
+            Dimension::Col => {
+                let data: Vec<T> = (0..self.nrows)
+                    .into_par_iter()
+                    .map(|i| f(self.row(i).unwrap()))
+                    .collect();
+
+                Self::new(data, (self.nrows, 1)).unwrap()
+            }
+        }
+    }
+
+    // Applies f to every element, keeping the shape
+    pub(crate) fn map_elements<F>(&self, f: F) -> Self
+    where
+        F: Fn(T) -> T + Sync + Send,
+    {
+        let data: Vec<T> = self.data.par_iter().map(|&e| f(e)).collect();
+
+        Self::new(data, self.shape()).unwrap()
+    }
+
+    // Formats the matrix numpy style, with right aligned columns.
+    // Dimensions above PRINT_THRESHOLD only show their first and last
+    // PRINT_EDGE_ITEMS entries, separated by "...".
+    // The last line holds the shape and dtype
+    pub(crate) fn format_with_decimals(&self, decimals: usize) -> String {
+        let summary = format!(
+            "shape={:?}, dtype={}",
+            self.shape(),
+            std::any::type_name::<T>()
+        );
+
+        if self.nrows == 0 || self.ncols == 0 {
+            return format!("[]\n{summary}");
+        }
+
+        let cols = visible_indices(self.ncols);
+
+        // None marks the row of ellipsis
+        let cells: Vec<Option<Vec<String>>> = visible_indices(self.nrows)
+            .into_iter()
+            .map(|i| {
+                i.map(|i| {
+                    cols.iter()
+                        .map(|j| match j {
+                            Some(j) => {
+                                format!("{:.*}", decimals, self.data[at!(i, *j, self.ncols)])
+                            }
+                            None => "...".to_string(),
+                        })
+                        .collect()
+                })
+            })
+            .collect();
+
+        let width = cells
+            .iter()
+            .flatten()
+            .flatten()
+            .map(|c| c.len())
+            .max()
+            .unwrap_or(0);
+
+        let rows: Vec<String> = cells
+            .iter()
+            .map(|row| match row {
+                Some(row) => {
+                    let row: Vec<String> = row.iter().map(|c| format!("{c:>width$}")).collect();
+                    format!("[{}]", row.join(" "))
+                }
+                None => "...".to_string(),
+            })
+            .collect();
+
+        format!("[{}]\n{summary}", rows.join("\n "))
+    }
+
+    // Applies f element-wise, where other is either the same shape as self,
+    // a 1 x ncols row repeated for every row
+    // or a nrows x 1 column repeated for every column
+    pub(crate) fn broadcast_op<F>(&self, other: &Self, f: F) -> Result<Self, MatrixError>
+    where
+        F: Fn(T, T) -> T + Sync + Send,
+    {
+        let ncols = self.ncols;
+
+        let other_idx: fn(usize, usize, usize) -> usize = match other.shape() {
+            shape if shape == self.shape() => |i, j, ncols| at!(i, j, ncols),
+            (1, n) if n == ncols => |_, j, _| j,
+            (m, 1) if m == self.nrows => |i, _, _| i,
+            _ => return Err(MatrixError::MatrixDimensionMismatchError),
+        };
+
+        let data: Vec<T> = self
+            .data
+            .par_iter()
+            .enumerate()
+            .map(|(idx, &x)| {
+                let (i, j) = (idx / ncols, idx % ncols);
+                f(x, other.data[other_idx(i, j, ncols)])
+            })
+            .collect();
+
+        Self::new(data, self.shape())
+    }
+
+    // Compares two matrices element-wise, giving 1 where pred holds and 0 elsewhere
+    pub(crate) fn compare_mask<F>(
+        &self,
+        other: &Self,
+        pred: F,
+    ) -> Result<Matrix<'a, i8>, MatrixError>
+    where
+        F: Fn(&T, &T) -> bool + Sync + Send,
+    {
+        if !self.same_shape(other) {
+            return Err(MatrixError::MatrixDimensionMismatchError);
+        }
+
+        let data: Vec<i8> = self
+            .data
+            .par_iter()
+            .zip(other.data.par_iter())
+            .map(|(x, y)| pred(x, y) as i8)
+            .collect();
+
+        Matrix::<i8>::new(data, self.shape())
+    }
+
     // Calculate efficient blocksize
     #[inline(always)]
     pub fn get_block_size(&self, other: &Self) -> usize {
@@ -123,7 +320,74 @@ where
         det
     }
 
-    fn submatrix(matrix: Vec<T>, n: usize, row_to_remove: usize, col_to_remove: usize) -> Vec<T> {
+    // Product of U's diagonal times the sign of the permutation
+    fn det_lu(&self) -> T {
+        let n = self.nrows;
+        let (lu, _, sign) = self.lu_helper();
+
+        (0..n).fold(sign, |det, i| det * lu[at!(i, i, n)])
+    }
+
+    // ===================================================
+    //           LU Decomposition
+    // ===================================================
+
+    // Doolittle LU decomposition with partial pivoting, done in place.
+    //
+    // Returns the combined LU buffer, where L is stored below the diagonal
+    // with an implicit unit diagonal and U is stored on and above it,
+    // together with the row permutation and the sign of that permutation.
+    //
+    // Zero pivots are skipped, leaving a zero on U's diagonal
+    pub(crate) fn lu_helper(&self) -> (Vec<T>, Vec<usize>, T) {
+        let n = self.nrows;
+
+        let mut lu = self.data.clone();
+        let mut perm: Vec<usize> = (0..n).collect();
+        let mut sign = T::one();
+
+        for col in 0..n {
+            let pivot_row = (col..n)
+                .max_by(|&a, &b| {
+                    lu[at!(a, col, n)]
+                        .abs()
+                        .partial_cmp(&lu[at!(b, col, n)].abs())
+                        .unwrap()
+                })
+                .unwrap();
+
+            if pivot_row != col {
+                swap_rows(&mut lu, n, pivot_row, col);
+                perm.swap(pivot_row, col);
+                sign = -sign;
+            }
+
+            let pivot = lu[at!(col, col, n)];
+
+            if pivot == T::zero() {
+                continue;
+            }
+
+            for row in (col + 1)..n {
+                let factor = lu[at!(row, col, n)] / pivot;
+                lu[at!(row, col, n)] = factor;
+
+                for j in (col + 1)..n {
+                    let u = lu[at!(col, j, n)];
+                    lu[at!(row, j, n)] -= factor * u;
+                }
+            }
+        }
+
+        (lu, perm, sign)
+    }
+
+    pub(crate) fn submatrix(
+        matrix: Vec<T>,
+        n: usize,
+        row_to_remove: usize,
+        col_to_remove: usize,
+    ) -> Vec<T> {
         matrix
             .par_iter()
             .enumerate()
@@ -165,7 +429,7 @@ where
     #[inline(always)]
     fn onetwo_by_twotwo(&self, other: &Self) -> Self {
         let a = self.at(0, 0) * other.at(0, 0) + self.at(0, 1) * other.at(1, 0);
-        let b = self.at(0, 0) * other.at(1, 0) + self.at(0, 1) * other.at(1, 1);
+        let b = self.at(0, 0) * other.at(0, 1) + self.at(0, 1) * other.at(1, 1);
 
         Self::new(vec![a, b], (1, 2)).unwrap()
     }
@@ -173,10 +437,10 @@ where
     // 2x2 @ 2x2 matrix
     #[inline(always)]
     fn twotwo_by_twotwo(&self, other: &Self) -> Self {
-        let a = self.at(0, 0) * other.at(0, 0) + self.at(1, 0) * other.at(1, 0);
+        let a = self.at(0, 0) * other.at(0, 0) + self.at(0, 1) * other.at(1, 0);
         let b = self.at(0, 0) * other.at(0, 1) + self.at(0, 1) * other.at(1, 1);
         let c = self.at(1, 0) * other.at(0, 0) + self.at(1, 1) * other.at(1, 0);
-        let d = self.at(1, 0) * other.at(1, 0) + self.at(1, 1) * other.at(1, 1);
+        let d = self.at(1, 0) * other.at(0, 1) + self.at(1, 1) * other.at(1, 1);
 
         Self::new(vec![a, b, c, d], (2, 2)).unwrap()
     }
@@ -189,7 +453,8 @@ where
 
     /// Naive matmul if you don't have any SIMD intrinsincts
     ///
-    /// Also blocked, but doing different than just N
+    /// Also blocked, but doing different than just N.
+    /// Every block of output rows is computed on its own thread
     fn optimized_blocked_matmul(&self, other: &Self, block_size: usize) -> Self {
         let M = self.nrows;
         let N = self.ncols;
@@ -197,30 +462,35 @@ where
 
         let mut data = vec![T::zero(); M * P];
 
-        //let t_other = other.transpose_copy();
+        // Transposed so the inner loop reads contiguous memory
+        let t_other = other.transpose_copy();
+
+        data.par_chunks_mut((block_size * P).max(1))
+            .enumerate()
+            .for_each(|(block, rows)| {
+                let ii = block * block_size;
+                let block_end_i = (ii + block_size).min(M);
 
-        for kk in (0..N).step_by(block_size) {
-            for jj in (0..P).step_by(block_size) {
-                for ii in (0..M).step_by(block_size) {
-                    let block_end_i = (ii + block_size).min(M);
-                    let block_end_j = (jj + block_size).min(P);
+                for kk in (0..N).step_by(block_size) {
                     let block_end_k = (kk + block_size).min(N);
 
-                    // Blocking for L0 memory
-                    for i in ii..block_end_i {
-                        for j in jj..block_end_j {
-                            // for k in kk..block_end_k {
-                            //     data[at!(i, j, P)] += self.at(i, k) * other.at(k, j);
-                            // }
-                            data[at!(i, j, P)] = (kk..block_end_k)
-                                .into_par_iter()
-                                .map(|k| self.at(i, k) * other.at(k, j))
-                                .sum();
+                    for jj in (0..P).step_by(block_size) {
+                        let block_end_j = (jj + block_size).min(P);
+
+                        // Blocking for L0 memory
+                        for i in ii..block_end_i {
+                            let lhs = &self.data[at!(i, kk, N)..at!(i, block_end_k, N)];
+
+                            for j in jj..block_end_j {
+                                let rhs = &t_other.data[at!(j, kk, N)..at!(j, block_end_k, N)];
+
+                                rows[at!(i - ii, j, P)] += dot(lhs, rhs);
+                            }
                         }
                     }
                 }
-            }
-        }
+            });
+
         Self::new(data, (M, P)).unwrap()
     }
 
@@ -231,22 +501,27 @@ where
     }
 
     // The magnum opus of matrix multiply, also known as naive matmul
-    // Only optimization is a parallelized innermost summation
-    fn naive(&self, other: &Self) -> Self {
+    // Only optimizations are parallelizing over output rows
+    // and a transposed rhs for contiguous reads
+    pub(crate) fn naive(&self, other: &Self) -> Self {
         let M = self.nrows;
         let N = self.ncols;
         let P = other.ncols;
 
         let mut data = vec![T::zero(); M * P];
 
-        for i in 0..M {
-            for j in 0..P {
-                data[at!(i, j, P)] = (0..N)
-                    .into_par_iter()
-                    .map(|k| self.at(i, k) * other.at(k, j))
-                    .sum();
-            }
-        }
+        // Transposed so both operands are read row by row
+        let t_other = other.transpose_copy();
+
+        data.par_chunks_mut(P.max(1))
+            .enumerate()
+            .for_each(|(i, row)| {
+                let lhs = &self.data[at!(i, 0, N)..at!(i + 1, 0, N)];
+
+                for (j, elem) in row.iter_mut().enumerate() {
+                    *elem = dot(lhs, &t_other.data[at!(j, 0, N)..at!(j + 1, 0, N)]);
+                }
+            });
 
         Self::new(data, (M, P)).unwrap()
     }
@@ -255,30 +530,339 @@ where
     // https://csapp.cs.cmu.edu/public/waside/waside-blocking.pdf
     //
     // Modification involves transposing the B matrix, at the cost
-    // of increased space complexity, but better cache hit rate
+    // of increased space complexity, but better cache hit rate.
     //
-    // NOTE: Only works for M N @ N M matrices for now
-    fn blocked_matmul(&self, other: &Self, block_size: usize) -> Self {
-        let n = self.nrows;
-
-        let en = block_size * (n / block_size);
+    // Block boundaries are clamped, so any M N @ N P works,
+    // also when block_size does not divide the dimensions
+    pub(crate) fn blocked_matmul(&self, other: &Self, block_size: usize) -> Self {
+        let m = self.nrows;
+        let n = self.ncols;
+        let p = other.ncols;
 
-        let mut data = vec![T::zero(); n * n];
+        let mut data = vec![T::zero(); m * p];
 
         let t_other = other.transpose_copy();
 
-        for kk in (0..n).step_by(en) {
-            for jj in (0..n).step_by(en) {
-                for i in 0..n {
-                    for j in jj..jj + block_size {
-                        data[at!(i, j, n)] = (kk..kk + block_size)
-                            .into_par_iter()
-                            .map(|k| self.at(i, k) * t_other.at(j, k))
-                            .sum();
+        data.par_chunks_mut(p.max(1))
+            .enumerate()
+            .for_each(|(i, row)| {
+                for kk in (0..n).step_by(block_size) {
+                    let block_end_k = (kk + block_size).min(n);
+                    let lhs = &self.data[at!(i, kk, n)..at!(i, block_end_k, n)];
+
+                    for jj in (0..p).step_by(block_size) {
+                        let block_end_j = (jj + block_size).min(p);
+
+                        for (j, elem) in row.iter_mut().enumerate().take(block_end_j).skip(jj) {
+                            *elem += dot(lhs, &t_other.data[at!(j, kk, n)..at!(j, block_end_k, n)]);
+                        }
                     }
                 }
+            });
+
+        Self::new(data, (m, p)).unwrap()
+    }
+}
+
+// Helpers only valid for floating point matrices
+impl<'a, T> Matrix<'a, T>
+where
+    T: MatrixElement + Float + 'a,
+    <T as FromStr>::Err: Error + 'static,
+    Vec<T>: IntoParallelIterator,
+    Vec<&'a T>: IntoParallelRefIterator<'a>,
+{
+    // ===================================================
+    //           Inverse
+    // ===================================================
+
+    // Gauss-Jordan elimination with partial pivoting.
+    // The n x k rhs is carried along, and ends up as the solution X
+    // of AX = rhs once the lhs has been reduced to the identity.
+    // Passing the identity as rhs gives the inverse.
+    //
+    // Returns None if a pivot is zero within tolerance, i.e. singular
+    pub(crate) fn gauss_jordan_solve(&self, mut rhs: Vec<T>, k: usize) -> Option<Vec<T>> {
+        let n = self.nrows;
+
+        let mut lhs = self.data.clone();
+
+        let tol = Self::pivot_tolerance(&lhs, n);
+
+        for col in 0..n {
+            let pivot_row = (col..n)
+                .max_by(|&a, &b| {
+                    lhs[at!(a, col, n)]
+                        .abs()
+                        .partial_cmp(&lhs[at!(b, col, n)].abs())
+                        .unwrap()
+                })
+                .unwrap();
+
+            if lhs[at!(pivot_row, col, n)].abs() <= tol {
+                return None;
+            }
+
+            if pivot_row != col {
+                swap_rows(&mut lhs, n, pivot_row, col);
+                swap_rows(&mut rhs, k, pivot_row, col);
+            }
+
+            let pivot = lhs[at!(col, col, n)];
+
+            for j in 0..n {
+                lhs[at!(col, j, n)] /= pivot;
+            }
+
+            for j in 0..k {
+                rhs[at!(col, j, k)] /= pivot;
+            }
+
+            for row in 0..n {
+                let factor = lhs[at!(row, col, n)];
+
+                if row == col || factor == T::zero() {
+                    continue;
+                }
+
+                for j in 0..n {
+                    let l = lhs[at!(col, j, n)];
+                    lhs[at!(row, j, n)] -= factor * l;
+                }
+
+                for j in 0..k {
+                    let r = rhs[at!(col, j, k)];
+                    rhs[at!(row, j, k)] -= factor * r;
+                }
+            }
+        }
+
+        Some(rhs)
+    }
+
+    // Anything at or below this is treated as a zero pivot.
+    // Scaled by the largest element so it works regardless of magnitude
+    pub(crate) fn pivot_tolerance(data: &[T], n: usize) -> T {
+        let largest = data
+            .iter()
+            .fold(T::zero(), |acc, &e| if e.abs() > acc { e.abs() } else { acc });
+
+        T::epsilon() * largest * T::from(n).unwrap()
+    }
+}
+
+// Helpers valid for any real matrix
+impl<'a, T> Matrix<'a, T>
+where
+    T: MatrixElement + Real + 'a,
+    <T as FromStr>::Err: Error + 'static,
+    Vec<T>: IntoParallelIterator,
+    Vec<&'a T>: IntoParallelRefIterator<'a>,
+{
+    // The single implementation behind the element-wise
+    // methods of both LinAlgFloats and LinAlgReals
+    pub(crate) fn apply_real(&self, op: RealOp<T>) -> Self {
+        match op {
+            RealOp::Log(base) => self.map_elements(|e| e.log(base)),
+            RealOp::Ln => self.map_elements(|e| e.ln()),
+            RealOp::Sqrt => self.map_elements(|e| e.sqrt()),
+            RealOp::Sin => self.map_elements(|e| e.sin()),
+            RealOp::Cos => self.map_elements(|e| e.cos()),
+            RealOp::Tan => self.map_elements(|e| e.tan()),
+            RealOp::Sinh => self.map_elements(|e| e.sinh()),
+            RealOp::Cosh => self.map_elements(|e| e.cosh()),
+            RealOp::Tanh => self.map_elements(|e| e.tanh()),
+        }
+    }
+
+    // ===================================================
+    //           QR
+    // ===================================================
+
+    // Householder QR of an m x n matrix with m >= n.
+    // Returns the full m x m orthogonal Q and the m x n upper triangular R
+    pub(crate) fn householder_qr(&self) -> (Self, Self) {
+        let (m, n) = self.shape();
+
+        let mut r = self.data.clone();
+        let mut q = Self::eye(m).data;
+
+        for k in 0..n.min(m.saturating_sub(1)) {
+            let norm = (k..m)
+                .map(|i| r[at!(i, k, n)] * r[at!(i, k, n)])
+                .sum::<T>()
+                .sqrt();
+
+            if norm == T::zero() {
+                continue;
+            }
+
+            // Reflect onto -sign(x0) * |x| e0 to avoid cancellation
+            let x0 = r[at!(k, k, n)];
+            let alpha = if x0 > T::zero() { -norm } else { norm };
+
+            let mut v: Vec<T> = (k..m).map(|i| r[at!(i, k, n)]).collect();
+            v[0] -= alpha;
+
+            let v_norm = v.iter().map(|&e| e * e).sum::<T>().sqrt();
+
+            if v_norm == T::zero() {
+                continue;
+            }
+
+            v.iter_mut().for_each(|e| *e /= v_norm);
+
+            let two = T::one() + T::one();
+
+            // R = H R, only rows k.. are affected
+            for j in 0..n {
+                let dot = (k..m).map(|i| v[i - k] * r[at!(i, j, n)]).sum::<T>();
+
+                for i in k..m {
+                    r[at!(i, j, n)] -= two * v[i - k] * dot;
+                }
+            }
+
+            // Q = Q H, only cols k.. are affected
+            for i in 0..m {
+                let dot = (k..m).map(|j| q[at!(i, j, m)] * v[j - k]).sum::<T>();
+
+                for j in k..m {
+                    q[at!(i, j, m)] -= two * dot * v[j - k];
+                }
+            }
+
+            for i in k + 1..m {
+                r[at!(i, k, n)] = T::zero();
+            }
+        }
+
+        (Self::new(q, (m, m)).unwrap(), Self::new(r, (m, n)).unwrap())
+    }
+
+    // ===================================================
+    //           Eigenvalues
+    // ===================================================
+
+    // QR iteration with Wilkinson shifts on a square matrix.
+    // The active block shrinks by one every time its last
+    // subdiagonal element becomes negligible, either compared to
+    // its neighbouring diagonal elements or to the whole matrix.
+    // None if the matrix is not square, or if an eigenvalue
+    // needs more than EIGEN_MAX_ITERATIONS.
+    // Backs get_eigenvalues of both LinAlgFloats and LinAlgReals
+    pub(crate) fn qr_eigenvalues(&self) -> Option<Vec<T>> {
+        if self.nrows != self.ncols {
+            return None;
+        }
+
+        let n = self.nrows;
+
+        let mut a = self.data.clone();
+        let norm = self.data.iter().map(|&e| e * e).sum::<T>().sqrt();
+        let mut active = n;
+        let mut iterations = 0;
+
+        while active > 1 {
+            let (p, q) = (active - 2, active - 1);
+
+            let sub = a[at!(q, p, n)].abs();
+            let scale = a[at!(p, p, n)].abs() + a[at!(q, q, n)].abs();
+
+            if sub <= T::epsilon() * scale || sub <= T::epsilon() * norm {
+                active -= 1;
+                iterations = 0;
+                continue;
+            }
+
+            if iterations == EIGEN_MAX_ITERATIONS {
+                return None;
+            }
+            iterations += 1;
+
+            let shift = Self::wilkinson_shift(a[at!(p, p, n)], a[at!(q, p, n)], a[at!(q, q, n)]);
+
+            let mut block: Vec<T> = iproduct!(0..active, 0..active)
+                .map(|(i, j)| a[at!(i, j, n)])
+                .collect();
+
+            for i in 0..active {
+                block[at!(i, i, active)] -= shift;
+            }
+
+            let (q_mat, r_mat) = Self::new(block, (active, active)).unwrap().householder_qr();
+
+            let next = r_mat.naive(&q_mat);
+
+            for (i, j) in iproduct!(0..active, 0..active) {
+                a[at!(i, j, n)] = next.at(i, j);
+            }
+
+            for i in 0..active {
+                a[at!(i, i, n)] += shift;
             }
         }
-        Self::new(data, (n, n)).unwrap()
+
+        Some((0..n).map(|i| a[at!(i, i, n)]).collect())
+    }
+
+    // Eigenvalue of the trailing 2x2 block [a b; b c] closest to c
+    fn wilkinson_shift(a: T, b: T, c: T) -> T {
+        let two = T::one() + T::one();
+        let d = (a - c) / two;
+        let sign = if d >= T::zero() { T::one() } else { -T::one() };
+
+        c - sign * b * b / (d.abs() + (d * d + b * b).sqrt())
+    }
+}
+
+// Variance of data, dividing the squared deviations by len - ddof.
+// NaN if there are no degrees of freedom left
+pub(crate) fn variance_of<T>(data: &[T], ddof: usize) -> T
+where
+    T: MatrixElement + Float,
+{
+    let n = data.len();
+
+    if n <= ddof {
+        return T::nan();
     }
+
+    let mean = data.iter().copied().sum::<T>() / T::from(n).unwrap();
+
+    data.iter().map(|&e| (e - mean) * (e - mean)).sum::<T>() / T::from(n - ddof).unwrap()
+}
+
+// Indices to print along a dimension of length n,
+// where None is where the ellipsis goes
+fn visible_indices(n: usize) -> Vec<Option<usize>> {
+    if n <= PRINT_THRESHOLD {
+        return (0..n).map(Some).collect();
+    }
+
+    (0..PRINT_EDGE_ITEMS)
+        .map(Some)
+        .chain(std::iter::once(None))
+        .chain((n - PRINT_EDGE_ITEMS..n).map(Some))
+        .collect()
+}
+
+// Swaps two rows of a row major n-column buffer
+pub(crate) fn swap_rows<T: Copy>(data: &mut [T], ncols: usize, a: usize, b: usize) {
+    for j in 0..ncols {
+        data.swap(at!(a, j, ncols), at!(b, j, ncols));
+    }
+}
+
+/// Largest value of a non-empty slice, ignoring NaNs
+pub(crate) fn max_of<T: MatrixElement + Float>(data: &[T]) -> T {
+    data.iter().copied().fold(T::neg_infinity(), T::max)
+}
+
+/// Sequential dot product of two equally long slices
+#[inline(always)]
+fn dot<T: MatrixElement>(lhs: &[T], rhs: &[T]) -> T {
+    lhs.iter()
+        .zip(rhs)
+        .fold(T::zero(), |acc, (&a, &b)| acc + a * b)
 }
@@ -5,27 +5,33 @@
 //! This file is sub 1500 lines and acts as the core file
 
 mod helper;
+mod ops;
 mod optim;
 
 use helper::*;
 
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::{
     error::Error,
     fmt::{Debug, Display},
     fs,
     marker::PhantomData,
+    mem::size_of,
     ops::Div,
+    path::Path,
     str::FromStr,
 };
 
+use anyhow::{anyhow, bail};
 use itertools::{iproduct, Itertools};
-use num_traits::{pow, real::Real, sign::abs, Float};
-use rand::Rng;
+use num_traits::{pow, real::Real, sign::abs, AsPrimitive, Float};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use rayon::prelude::*;
 use std::iter::Sum;
 
-use crate::{at, LinAlgFloats, MatrixElement, MatrixError, SparseMatrix};
+use crate::{
+    at, BinaryElement, LinAlgFloats, LinAlgReals, MatrixElement, MatrixError, SparseMatrix,
+};
 
 /// Shape represents the dimension size
 /// of the matrix as a tuple of usize
@@ -86,25 +92,61 @@ where
     Vec<&'a T>: IntoParallelRefIterator<'a>,
 {
     type Err = anyhow::Error;
+
+    /// Parses whitespace separated values, one row per line.
+    ///
+    /// Blank lines before the first and after the last row are ignored,
+    /// and empty input gives an empty 0 x 0 matrix. A token that fails to parse
+    /// gives an error with its 1-based line and column, where the column is
+    /// the position of the token in the line. Rows of different length
+    /// give an error with the first offending line
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        // Parse the input string and construct the matrix dynamically
-        let v: Vec<T> = s
-            .trim()
-            .lines()
-            .map(|l| {
-                l.split_whitespace()
-                    .map(|num| num.parse::<T>().unwrap())
-                    .collect::<Vec<T>>()
-            })
-            .collect::<Vec<Vec<T>>>()
-            .into_iter()
-            .flatten()
-            .collect();
+        let mut data: Vec<T> = Vec::new();
+        let mut rows = 0;
+        let mut cols = 0;
+
+        let lines: Vec<&str> = s.lines().collect();
+        let last = lines.iter().rposition(|line| !line.trim().is_empty());
+
+        for (i, line) in lines.iter().enumerate() {
+            // Blank lines around the rows are skipped here instead of
+            // trimmed away, so i + 1 stays the real line number
+            if line.trim().is_empty() && (rows == 0 || Some(i) > last) {
+                continue;
+            }
 
-        let rows = s.trim().lines().count();
-        let cols = s.trim().lines().nth(0).unwrap().split_whitespace().count();
+            let mut width = 0;
+
+            for (j, token) in line.split_whitespace().enumerate() {
+                match token.parse::<T>() {
+                    Ok(val) => data.push(val),
+                    Err(e) => bail!(
+                        "Invalid value '{}' at line {}, column {}: {}",
+                        token,
+                        i + 1,
+                        j + 1,
+                        e
+                    ),
+                }
+
+                width += 1;
+            }
+
+            if rows == 0 {
+                cols = width;
+            } else if width != cols {
+                bail!(
+                    "Row at line {} has {} values, expected {}",
+                    i + 1,
+                    width,
+                    cols
+                );
+            }
+
+            rows += 1;
+        }
 
-        Ok(Self::new(v, (rows, cols)).unwrap())
+        Self::new(data, (rows, cols)).map_err(|e| anyhow!("{}", e))
     }
 }
 
@@ -116,28 +158,7 @@ where
     Vec<&'a T>: IntoParallelRefIterator<'a>,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "[");
-
-        // Large matrices
-        if self.nrows > 10 || self.ncols > 10 {
-            write!(f, "...");
-        }
-
-        for i in 0..self.nrows {
-            for j in 0..self.ncols {
-                if i == 0 {
-                    write!(f, "{:.4} ", self.get(i, j).unwrap());
-                } else {
-                    write!(f, " {:.4}", self.get(i, j).unwrap());
-                }
-            }
-            // Print ] on same line if youre at the end
-            if i == self.nrows - 1 {
-                break;
-            }
-            writeln!(f);
-        }
-        writeln!(f, "], dtype={}", std::any::type_name::<T>())
+        writeln!(f, "{}", self.format_with_decimals(4))
     }
 }
 
@@ -165,6 +186,118 @@ where
     }
 }
 
+impl<'a, T> TryFrom<Vec<Vec<T>>> for Matrix<'a, T>
+where
+    T: MatrixElement,
+    <T as FromStr>::Err: Error + 'static,
+    Vec<T>: IntoParallelIterator,
+    Vec<&'a T>: IntoParallelRefIterator<'a>,
+{
+    type Error = MatrixError;
+
+    /// Creates a matrix from a vector of rows.
+    ///
+    /// Returns MatrixCreationError if the rows are not all the same length
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::try_from(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+    ///
+    /// assert_eq!(matrix.shape(), (2,3));
+    /// assert_eq!(matrix.get_vec(), vec![1, 2, 3, 4, 5, 6]);
+    /// ```
+    fn try_from(rows: Vec<Vec<T>>) -> Result<Self, Self::Error> {
+        let nrows = rows.len();
+        let ncols = rows.first().map_or(0, |row| row.len());
+
+        if rows.iter().any(|row| row.len() != ncols) {
+            return Err(MatrixError::MatrixCreationError);
+        }
+
+        Self::new(rows.into_iter().flatten().collect(), (nrows, ncols))
+    }
+}
+
+impl<'a, T> From<(Vec<T>, Shape)> for Matrix<'a, T>
+where
+    T: MatrixElement,
+    <T as FromStr>::Err: Error + 'static,
+    Vec<T>: IntoParallelIterator,
+    Vec<&'a T>: IntoParallelRefIterator<'a>,
+{
+    /// Creates a matrix from flat row-major data and a shape.
+    ///
+    /// Panics if the shape does not match the length of the data,
+    /// use `Matrix::new` to handle that case without panicking
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::from((vec![1.0, 2.0, 3.0, 4.0], (2,2)));
+    ///
+    /// assert_eq!(matrix.at(1,0), 3.0);
+    /// ```
+    fn from((data, shape): (Vec<T>, Shape)) -> Self {
+        Self::new(data, shape).expect("data length must match nrows * ncols")
+    }
+}
+
+impl<'a, T> From<SparseMatrix<'a, T>> for Matrix<'a, T>
+where
+    T: MatrixElement,
+    <T as FromStr>::Err: Error + 'static,
+    Vec<T>: IntoParallelIterator,
+    Vec<&'a T>: IntoParallelRefIterator<'a>,
+{
+    /// Creates a dense matrix from a sparse one, same as `Matrix::from_sparse`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::{Matrix, SparseMatrix};
+    ///
+    /// let matrix: Matrix<i32> = SparseMatrix::eye(2).into();
+    ///
+    /// assert_eq!(matrix.get_vec(), vec![1, 0, 0, 1]);
+    /// ```
+    fn from(sparse: SparseMatrix<'a, T>) -> Self {
+        Self::from_sparse(sparse)
+    }
+}
+
+impl<'a, 'b, T> IntoIterator for &'b Matrix<'a, T>
+where
+    T: MatrixElement,
+    <T as FromStr>::Err: Error + 'static,
+    Vec<T>: IntoParallelIterator,
+    Vec<&'a T>: IntoParallelRefIterator<'a>,
+{
+    type Item = &'b T;
+    type IntoIter = std::slice::Iter<'b, T>;
+
+    /// Iterates over all elements in row-major order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::new(vec![1, 2, 3, 4], (2,2)).unwrap();
+    ///
+    /// let sum: i32 = (&matrix).into_iter().sum();
+    ///
+    /// assert_eq!(sum, 10);
+    /// ```
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter()
+    }
+}
+
 /// Printer functions for the matrix
 impl<'a, T> Matrix<'a, T>
 where
@@ -185,36 +318,97 @@ where
     ///
     /// ```
     pub fn print(&self, decimals: usize) {
-        print!("[");
+        println!("{}", self.format_with_decimals(decimals));
+    }
 
-        // Large matrices
-        if self.nrows > 10 || self.ncols > 10 {
-            print!("...");
-        }
+    /// Writes the matrix to a comma separated file,
+    /// one row per line and without a header
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix: Matrix<i32> = Matrix::eye(3);
+    ///
+    /// // matrix.to_csv("eye.csv").unwrap();
+    /// ```
+    pub fn to_csv<P: AsRef<Path>>(&self, path: P) -> Result<(), MatrixError> {
+        let content: String = self
+            .row_iter()
+            .map(|row| row.iter().join(",") + "\n")
+            .collect();
 
-        for i in 0..self.nrows {
-            for j in 0..self.ncols {
-                if i == 0 {
-                    print!(
-                        "{val:.dec$} ",
-                        dec = decimals,
-                        val = self.get(i, j).unwrap()
-                    );
-                } else {
-                    print!(
-                        " {val:.dec$}",
-                        dec = decimals,
-                        val = self.get(i, j).unwrap()
-                    );
-                }
-            }
-            // Print ] on same line if youre at the end
-            if i == self.nrows - 1 {
-                break;
-            }
-            println!();
+        let path = path.as_ref();
+
+        fs::write(path, content)
+            .map_err(|_| MatrixError::MatrixFileWriteError(path.display().to_string()))
+    }
+
+    /// Encodes the matrix into a compact binary format.
+    ///
+    /// The layout is a 4 byte magic, a dtype tag byte, nrows and ncols
+    /// as little-endian u64, followed by the raw little-endian elements.
+    /// Unlike the text formats, floats round-trip exactly
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix: Matrix<f64> = Matrix::init(0.1, (2,3));
+    ///
+    /// let bytes = matrix.to_bytes();
+    ///
+    /// assert_eq!(bytes.len(), 21 + 6 * 8);
+    /// assert_eq!(Matrix::<f64>::from_bytes(&bytes).unwrap(), matrix);
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8>
+    where
+        T: BinaryElement,
+    {
+        let mut bytes = Vec::with_capacity(BINARY_HEADER_LEN + self.size() * size_of::<T>());
+
+        bytes.extend_from_slice(&BINARY_MAGIC);
+        bytes.push(T::DTYPE_TAG);
+        bytes.extend_from_slice(&(self.nrows as u64).to_le_bytes());
+        bytes.extend_from_slice(&(self.ncols as u64).to_le_bytes());
+
+        for &val in self.data.iter() {
+            val.write_le_bytes(&mut bytes);
         }
-        println!("], dtype={}", std::any::type_name::<T>());
+
+        bytes
+    }
+
+    /// Encodes the matrix as JSON with the schema
+    /// `{ "nrows": .., "ncols": .., "data": [..] }`,
+    /// where data is in row-major order.
+    ///
+    /// Note that JSON has no NaN or infinity, so those
+    /// are written as null and will not load again
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::new(vec![1, 2, 3, 4, 5, 6], (2,3)).unwrap();
+    ///
+    /// assert_eq!(matrix.to_json().unwrap(), r#"{"nrows":2,"ncols":3,"data":[1,2,3,4,5,6]}"#);
+    /// ```
+    pub fn to_json(&self) -> Result<String, MatrixError>
+    where
+        T: Serialize,
+    {
+        let json = MatrixJson {
+            nrows: self.nrows,
+            ncols: self.ncols,
+            data: &self.data[..],
+        };
+
+        serde_json::to_string(&json)
+            .map_err(|e| MatrixError::MatrixSerializationError(e.to_string()))
     }
 
     /// Calculates sparsity of a given Matrix
@@ -233,6 +427,38 @@ where
         self.count_where(|&e| e == T::zero()) as f64 / self.size() as f64
     }
 
+    /// Counts the number of non-zero elements in the matrix
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let mat: Matrix<f32> = Matrix::eye(4);
+    ///
+    /// assert_eq!(mat.nnz(), 4);
+    /// ```
+    pub fn nnz(&self) -> usize {
+        self.data.par_iter().filter(|&&e| e != T::zero()).count()
+    }
+
+    /// Calculates the density of a matrix, which is the
+    /// fraction of elements that are non-zero.
+    /// This is the same as 1 - sparsity
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let mat: Matrix<f32> = Matrix::eye(4);
+    ///
+    /// assert_eq!(mat.density(), 0.25);
+    /// ```
+    pub fn density(&self) -> f64 {
+        self.nnz() as f64 / self.size() as f64
+    }
+
     /// Returns the shape of a matrix represented as  
     /// (usize, usize)
     ///
@@ -301,6 +527,33 @@ where
         Self::from_shape(value, shape)
     }
 
+    /// Creates a matrix of the given shape where every element
+    /// is f(i, j) for its row i and column j
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix: Matrix<i32> = Matrix::from_fn((2,3), |i, j| (i + j) as i32);
+    ///
+    /// assert_eq!(matrix.get_vec(), vec![0, 1, 2, 1, 2, 3]);
+    /// assert_eq!(matrix.shape(), (2,3));
+    /// ```
+    pub fn from_fn<F>(shape: Shape, f: F) -> Self
+    where
+        F: Fn(usize, usize) -> T + Sync,
+    {
+        let (nrows, ncols) = shape;
+
+        let data: Vec<T> = (0..nrows * ncols)
+            .into_par_iter()
+            .map(|idx| f(idx / ncols, idx % ncols))
+            .collect();
+
+        Self::new(data, shape).unwrap()
+    }
+
     /// Returns an eye matrix which for now is the same as the
     /// identity matrix
     ///
@@ -352,82 +605,236 @@ where
         Self::eye(size)
     }
 
-    /// Tries to create a matrix from a slize and shape
+    /// Creates a square matrix with the given values on the
+    /// diagonal and zeros everywhere else
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let s = vec![1f32, 2f32, 3f32, 4f32];
-    /// let matrix = Matrix::from_slice(&s, (4,1)).unwrap();
+    /// let matrix: Matrix<i32> = Matrix::from_diagonal(&[1, 2]);
     ///
-    /// assert_eq!(matrix.shape(), (4,1));
+    /// assert_eq!(matrix.get_vec(), vec![1, 0, 0, 2]);
     /// ```
-    pub fn from_slice(arr: &[T], shape: Shape) -> Result<Self, MatrixError> {
-        if shape.0 * shape.1 != arr.len() {
-            return Err(MatrixError::MatrixCreationError.into());
-        }
+    pub fn from_diagonal(values: &[T]) -> Self {
+        let size = values.len();
+        let mut data: Vec<T> = vec![T::zero(); size * size];
 
-        Ok(Self::new(arr.to_owned(), shape).unwrap())
+        values
+            .iter()
+            .enumerate()
+            .for_each(|(i, &val)| data[at!(i, i, size)] = val);
+
+        Self::new(data, (size, size)).unwrap()
     }
 
-    /// Creates a matrix where all values are 0.
-    /// All sizes are based on a shape
+    /// Creates an n x n tridiagonal matrix with constant values
+    /// on the sub, main and super diagonals
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let matrix: Matrix<f64> = Matrix::zeros((4,1));
+    /// let matrix: Matrix<i32> = Matrix::tridiagonal(-1, 2, -1, 3);
     ///
-    /// assert_eq!(matrix.shape(), (4,1));
-    /// assert_eq!(matrix.get(0,0).unwrap(), 0f64);
-    /// assert_eq!(matrix.size(), 4);
+    /// assert_eq!(matrix.get_vec(), vec![2, -1, 0, -1, 2, -1, 0, -1, 2]);
     /// ```
-    pub fn zeros(shape: Shape) -> Self {
-        Self::from_shape(T::zero(), shape)
+    pub fn tridiagonal(sub: T, diag: T, super_: T, n: usize) -> Self {
+        let mut data: Vec<T> = vec![T::zero(); n * n];
+
+        for i in 0..n {
+            data[at!(i, i, n)] = diag;
+
+            if i > 0 {
+                data[at!(i, i - 1, n)] = sub;
+            }
+            if i + 1 < n {
+                data[at!(i, i + 1, n)] = super_;
+            }
+        }
+
+        Self::new(data, (n, n)).unwrap()
     }
 
-    /// Creates a matrix where all values are 1.
-    /// All sizes are based on a shape
+    /// Creates a banded matrix from a list of diagonals.
+    ///
+    /// Every diagonal is given by its offset, where a positive offset
+    /// is above the main diagonal and a negative one below it, as in
+    /// `diagonal_offset`. Every cell not on a given diagonal is 0,
+    /// and later diagonals overwrite earlier ones with the same offset.
+    ///
+    /// Returns MatrixCreationError if the length of a diagonal does
+    /// not match the length of that diagonal in the given shape
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let matrix: Matrix<f64> = Matrix::ones((4,1));
+    /// let matrix = Matrix::banded(&[(0, vec![1, 2, 3]), (2, vec![4])], (3,3)).unwrap();
     ///
-    /// assert_eq!(matrix.shape(), (4,1));
-    /// assert_eq!(matrix.get(0,0).unwrap(), 1f64);
-    /// assert_eq!(matrix.size(), 4);
+    /// assert_eq!(matrix.get_vec(), vec![1, 0, 4, 0, 2, 0, 0, 0, 3]);
     /// ```
-    pub fn ones(shape: Shape) -> Self {
-        Self::from_shape(T::one(), shape)
+    pub fn banded(diagonals: &[(isize, Vec<T>)], shape: Shape) -> Result<Self, MatrixError> {
+        let (nrows, ncols) = shape;
+        let mut data: Vec<T> = vec![T::zero(); nrows * ncols];
+
+        for (k, values) in diagonals {
+            let offset = k.unsigned_abs();
+
+            let (row_start, col_start) = if *k >= 0 { (0, offset) } else { (offset, 0) };
+
+            let len = nrows
+                .saturating_sub(row_start)
+                .min(ncols.saturating_sub(col_start));
+
+            if values.len() != len {
+                return Err(MatrixError::MatrixCreationError);
+            }
+
+            for (i, &val) in values.iter().enumerate() {
+                data[at!(row_start + i, col_start + i, ncols)] = val;
+            }
+        }
+
+        Self::new(data, shape)
     }
 
-    /// Creates a matrix where all values are 0.
-    /// All sizes are based on an already existent matrix
+    /// Creates a Vandermonde matrix with increasing powers.
+    ///
+    /// Row i is `[1, x_i, x_i^2, ..., x_i^degree]`, so the
+    /// shape is `x.len() x (degree + 1)`
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let matrix1: Matrix<i8> = Matrix::default();
-    /// let matrix2 = Matrix::zeros_like(&matrix1);
+    /// let matrix = Matrix::vandermonde(&[2, 3], 3);
     ///
-    /// assert_eq!(matrix2.shape(), matrix1.shape());
-    /// assert_eq!(matrix2.get(0,0).unwrap(), 0i8);
+    /// assert_eq!(matrix.shape(), (2,4));
+    /// assert_eq!(matrix.get_vec(), vec![1, 2, 4, 8, 1, 3, 9, 27]);
     /// ```
-    pub fn zeros_like(other: &Self) -> Self {
-        Self::from_shape(T::zero(), other.shape())
+    pub fn vandermonde(x: &[T], degree: usize) -> Self {
+        let ncols = degree + 1;
+
+        let data: Vec<T> = x
+            .iter()
+            .flat_map(|&xi| {
+                // Only multiply when another column needs the power,
+                // so integers don't overflow past x_i^degree
+                (0..ncols).scan(T::one(), move |power, k| {
+                    if k > 0 {
+                        *power *= xi;
+                    }
+                    Some(*power)
+                })
+            })
+            .collect();
+
+        Self::new(data, (x.len(), ncols)).unwrap()
     }
 
-    /// Creates a matrix where all values are 1.
-    /// All sizes are based on an already existent matrix
+    /// Tries to create a matrix from a slize and shape
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let s = vec![1f32, 2f32, 3f32, 4f32];
+    /// let matrix = Matrix::from_slice(&s, (4,1)).unwrap();
+    ///
+    /// assert_eq!(matrix.shape(), (4,1));
+    /// ```
+    pub fn from_slice(arr: &[T], shape: Shape) -> Result<Self, MatrixError> {
+        if shape.0 * shape.1 != arr.len() {
+            return Err(MatrixError::MatrixCreationError.into());
+        }
+
+        Ok(Self::new(arr.to_owned(), shape).unwrap())
+    }
+
+    /// Collects an iterator into a matrix of the given shape,
+    /// filling it in row-major order.
+    ///
+    /// Returns MatrixCreationError if the iterator does not
+    /// yield exactly nrows * ncols elements
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::from_iter_shape((0..6).map(|i| i * 2), (2,3)).unwrap();
+    ///
+    /// assert_eq!(matrix.get_vec(), vec![0, 2, 4, 6, 8, 10]);
+    /// ```
+    pub fn from_iter_shape<I: IntoIterator<Item = T>>(
+        iter: I,
+        shape: Shape,
+    ) -> Result<Self, MatrixError> {
+        Self::new(iter.into_iter().collect(), shape)
+    }
+
+    /// Creates a matrix where all values are 0.
+    /// All sizes are based on a shape
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix: Matrix<f64> = Matrix::zeros((4,1));
+    ///
+    /// assert_eq!(matrix.shape(), (4,1));
+    /// assert_eq!(matrix.get(0,0).unwrap(), 0f64);
+    /// assert_eq!(matrix.size(), 4);
+    /// ```
+    pub fn zeros(shape: Shape) -> Self {
+        Self::from_shape(T::zero(), shape)
+    }
+
+    /// Creates a matrix where all values are 1.
+    /// All sizes are based on a shape
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix: Matrix<f64> = Matrix::ones((4,1));
+    ///
+    /// assert_eq!(matrix.shape(), (4,1));
+    /// assert_eq!(matrix.get(0,0).unwrap(), 1f64);
+    /// assert_eq!(matrix.size(), 4);
+    /// ```
+    pub fn ones(shape: Shape) -> Self {
+        Self::from_shape(T::one(), shape)
+    }
+
+    /// Creates a matrix where all values are 0.
+    /// All sizes are based on an already existent matrix
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix1: Matrix<i8> = Matrix::default();
+    /// let matrix2 = Matrix::zeros_like(&matrix1);
+    ///
+    /// assert_eq!(matrix2.shape(), matrix1.shape());
+    /// assert_eq!(matrix2.get(0,0).unwrap(), 0i8);
+    /// ```
+    pub fn zeros_like(other: &Self) -> Self {
+        Self::from_shape(T::zero(), other.shape())
+    }
+
+    /// Creates a matrix where all values are 1.
+    /// All sizes are based on an already existent matrix
     ///
     /// # Examples
     ///
@@ -478,8 +885,29 @@ where
     /// //assert!(elem >= 1f32 && 2f32 <= elem);
     /// ```
     pub fn randomize_range(start: T, end: T, shape: Shape) -> Self {
-        let mut rng = rand::thread_rng();
+        Self::randomize_range_with(start, end, shape, &mut rand::thread_rng())
+    }
+
+    /// Same as `randomize_range`, but the values are drawn from
+    /// a generator seeded with `seed`, so the same seed always
+    /// gives the same matrix
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let a = Matrix::randomize_range_seeded(1f32, 2f32, (2,3), 42);
+    /// let b = Matrix::randomize_range_seeded(1f32, 2f32, (2,3), 42);
+    ///
+    /// assert_eq!(a, b);
+    /// ```
+    pub fn randomize_range_seeded(start: T, end: T, shape: Shape, seed: u64) -> Self {
+        Self::randomize_range_with(start, end, shape, &mut StdRng::seed_from_u64(seed))
+    }
 
+    // Shared by the seeded and unseeded constructors
+    fn randomize_range_with<R: Rng>(start: T, end: T, shape: Shape, rng: &mut R) -> Self {
         let (rows, cols) = shape;
 
         let len: usize = rows * cols;
@@ -517,14 +945,146 @@ where
     ///
     /// // m.print(4);
     /// ```
-    pub fn from_file(path: &'static str) -> Result<Self, MatrixError> {
-        let data =
-            fs::read_to_string(path).map_err(|_| MatrixError::MatrixFileReadError(path).into())?;
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, MatrixError> {
+        let path = path.as_ref();
+        let data = fs::read_to_string(path)
+            .map_err(|_| MatrixError::MatrixFileReadError(path.display().to_string()))?;
 
         data.parse::<Self>()
             .map_err(|_| MatrixError::MatrixParseError.into())
     }
 
+    /// Decodes a matrix written by `to_bytes`.
+    ///
+    /// Returns MatrixParseError if the header is truncated, the magic
+    /// or dtype does not match, or the payload has the wrong length
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::{Matrix, MatrixError};
+    ///
+    /// let matrix: Matrix<i32> = Matrix::eye(3);
+    ///
+    /// let bytes = matrix.to_bytes();
+    ///
+    /// assert_eq!(Matrix::<i32>::from_bytes(&bytes).unwrap(), matrix);
+    /// assert_eq!(Matrix::<f32>::from_bytes(&bytes), Err(MatrixError::MatrixParseError));
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, MatrixError>
+    where
+        T: BinaryElement,
+    {
+        if bytes.len() < BINARY_HEADER_LEN
+            || bytes[..BINARY_MAGIC.len()] != BINARY_MAGIC
+            || bytes[BINARY_MAGIC.len()] != T::DTYPE_TAG
+        {
+            return Err(MatrixError::MatrixParseError);
+        }
+
+        let read_u64 = |offset: usize| -> Result<usize, MatrixError> {
+            let mut buf = [0u8; 8];
+            buf.copy_from_slice(&bytes[offset..offset + 8]);
+            usize::try_from(u64::from_le_bytes(buf)).map_err(|_| MatrixError::MatrixParseError)
+        };
+
+        let nrows = read_u64(BINARY_MAGIC.len() + 1)?;
+        let ncols = read_u64(BINARY_MAGIC.len() + 9)?;
+
+        let payload = &bytes[BINARY_HEADER_LEN..];
+
+        let expected = nrows
+            .checked_mul(ncols)
+            .and_then(|n| n.checked_mul(size_of::<T>()));
+
+        if expected != Some(payload.len()) {
+            return Err(MatrixError::MatrixParseError);
+        }
+
+        let data: Vec<T> = payload
+            .chunks_exact(size_of::<T>())
+            .map(T::read_le_bytes)
+            .collect();
+
+        Self::new(data, (nrows, ncols)).map_err(|_| MatrixError::MatrixParseError)
+    }
+
+    /// Decodes a matrix from JSON written by `to_json`.
+    ///
+    /// Returns MatrixParseError if the JSON does not follow the
+    /// schema, or if nrows * ncols does not match the length of data
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::{Matrix, MatrixError};
+    ///
+    /// let matrix: Matrix<i32> = Matrix::from_json(r#"{"nrows":2,"ncols":1,"data":[4,5]}"#).unwrap();
+    ///
+    /// assert_eq!(matrix.get_vec(), vec![4, 5]);
+    /// assert_eq!(
+    ///     Matrix::<i32>::from_json(r#"{"nrows":2,"ncols":2,"data":[4,5]}"#),
+    ///     Err(MatrixError::MatrixParseError)
+    /// );
+    /// ```
+    pub fn from_json(json: &str) -> Result<Self, MatrixError>
+    where
+        T: DeserializeOwned,
+    {
+        let parsed: MatrixJson<Vec<T>> =
+            serde_json::from_str(json).map_err(|_| MatrixError::MatrixParseError)?;
+
+        if parsed.nrows.checked_mul(parsed.ncols) != Some(parsed.data.len()) {
+            return Err(MatrixError::MatrixParseError);
+        }
+
+        Self::new(parsed.data, (parsed.nrows, parsed.ncols))
+    }
+
+    /// Reads a matrix from a comma separated file.
+    ///
+    /// Every line is a row, and the shape is inferred from the file.
+    /// If `has_header` is true, the first line is skipped.
+    /// Rows of different lengths give a MatrixParseError
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// // let m: Matrix<f64> = Matrix::from_csv("data.csv", true).unwrap();
+    ///
+    /// // m.print(4);
+    /// ```
+    pub fn from_csv<P: AsRef<Path>>(path: P, has_header: bool) -> Result<Self, MatrixError> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .map_err(|_| MatrixError::MatrixFileReadError(path.display().to_string()))?;
+
+        let rows: Vec<Vec<T>> = content
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .skip(has_header as usize)
+            .map(|l| {
+                l.split(',')
+                    .map(|num| num.trim().parse::<T>())
+                    .collect::<Result<Vec<T>, _>>()
+            })
+            .collect::<Result<_, _>>()
+            .map_err(|_| MatrixError::MatrixParseError)?;
+
+        let ncols = match rows.first() {
+            Some(row) => row.len(),
+            None => return Err(MatrixError::MatrixParseError),
+        };
+
+        if rows.iter().any(|row| row.len() != ncols) {
+            return Err(MatrixError::MatrixParseError);
+        }
+
+        Self::new(rows.concat(), (rows.len(), ncols))
+    }
+
     /// Constructs a new dense matrix from a sparse one.
     ///
     /// This transfesrs ownership as well!
@@ -571,6 +1131,28 @@ pub enum Dimension {
     Col = 1,
 }
 
+/// Enum for choosing which algorithm `matmul_with` uses
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MatMulAlgo {
+    /// Triple loop over every output element
+    Naive,
+    /// Blocked matmul over the transposed rhs
+    Blocked,
+    /// Same as `matmul`, picking the algorithm and block size from the shapes
+    AutoBlocked,
+}
+
+/// Enum for choosing the output size of `convolve2d`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConvMode {
+    /// Only positions where the kernel fits fully inside the matrix
+    Valid,
+    /// Same shape as the input, centered on the full output
+    Same,
+    /// Every position where the kernel overlaps the matrix
+    Full,
+}
+
 /// Regular matrix methods that are not operating math on them
 impl<'a, T> Matrix<'a, T>
 where
@@ -585,6 +1167,7 @@ where
     /// # Examples
     ///
     /// ```
+    /// #![allow(deprecated)]
     /// use sukker::Matrix;
     ///
     /// let mut matrix = Matrix::init(10.5, (2,3));
@@ -592,14 +1175,43 @@ where
     ///
     /// assert_eq!(matrix.shape(), (3,2));
     /// ```
+    #[deprecated(
+        since = "1.1.3",
+        note = "reshape silently keeps the old shape on failure, use `try_reshape`"
+    )]
     pub fn reshape(&mut self, nrows: usize, ncols: usize) {
-        if nrows * ncols != self.size() {
+        if self.try_reshape(nrows, ncols).is_err() {
             eprintln!("Err: Can not reshape.. Keeping old dimensions for now");
-            return;
+        }
+    }
+
+    /// Reshapes a matrix if possible.
+    ///
+    /// Returns a MatrixCreationError and keeps the old shape
+    /// if the new shape holds a different number of elements
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let mut matrix = Matrix::init(10.5, (2,3));
+    ///
+    /// assert!(matrix.try_reshape(3,2).is_ok());
+    /// assert_eq!(matrix.shape(), (3,2));
+    ///
+    /// assert!(matrix.try_reshape(4,2).is_err());
+    /// assert_eq!(matrix.shape(), (3,2));
+    /// ```
+    pub fn try_reshape(&mut self, nrows: usize, ncols: usize) -> Result<(), MatrixError> {
+        if nrows * ncols != self.size() {
+            return Err(MatrixError::MatrixCreationError);
         }
 
         self.nrows = nrows;
         self.ncols = ncols;
+
+        Ok(())
     }
 
     /// Get the total size of the matrix
@@ -638,6 +1250,32 @@ where
         Some(self.at(i, j))
     }
 
+    ///  Gets a mutable reference to the element based on is and js.
+    ///
+    ///  Returns None if the indexes are out of bounds
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let mut matrix = Matrix::init(10.5f32, (2,3));
+    ///
+    /// if let Some(x) = matrix.get_mut(0, 1) {
+    ///     *x *= 2.0;
+    /// }
+    ///
+    /// assert_eq!(matrix.get(0,1).unwrap(), 21f32);
+    /// assert!(matrix.get_mut(2, 0).is_none());
+    /// ```
+    pub fn get_mut(&mut self, i: usize, j: usize) -> Option<&mut T> {
+        if i >= self.nrows || j >= self.ncols {
+            return None;
+        }
+
+        Some(&mut self.data[at!(i, j, self.ncols)])
+    }
+
     ///  Gets element based on is and js, but will
     ///  panic if indexes are out of range.
     ///
@@ -697,1389 +1335,4141 @@ where
         self.data.clone()
     }
 
-    ///  Gets a piece of the matrix out as a matrix
+    /// Gets a row of the matrix as a slice, without copying.
     ///
-    ///  If some indeces are out of bounds, unlike `get_vec_slice`
-    ///  this function will return an IndexOutOfBoundsError
-    ///  and will not return data
+    /// Returns None if the row is out of bounds
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let matrix = Matrix::init(10.5, (4,4));
-    /// let sub_matrix = matrix.get_sub_matrix((1,1), (2,2)).unwrap();
+    /// let matrix = Matrix::new(vec![1, 2, 3, 4, 5, 6], (2,3)).unwrap();
     ///
-    /// assert_eq!(sub_matrix.get_vec(), vec![10.5,10.5,10.5,10.5]);
+    /// assert_eq!(matrix.row(1), Some(&[4, 5, 6][..]));
+    /// assert_eq!(matrix.row(2), None);
     /// ```
-    pub fn get_sub_matrix(&self, start_idx: Shape, size: Shape) -> Result<Self, MatrixError> {
-        let (start_row, start_col) = start_idx;
-        let (dx, dy) = size;
-
-        let data = iproduct!(start_row..start_row + dy, start_col..start_col + dx)
-            .filter_map(|(i, j)| self.get(i, j))
-            .collect();
+    pub fn row(&self, i: usize) -> Option<&[T]> {
+        if i >= self.nrows {
+            return None;
+        }
 
-        return match Self::new(data, size) {
-            Ok(a) => Ok(a),
-            Err(_) => Err(MatrixError::MatrixIndexOutOfBoundsError.into()),
-        };
+        Some(&self.data[i * self.ncols..(i + 1) * self.ncols])
     }
 
-    /// Concat two mtrices on a dimension
+    /// Same as `row`. Rows are contiguous in memory,
+    /// so the slice borrows straight from the matrix
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
-    /// use sukker::Dimension;
-    ///
-    /// let matrix = Matrix::init(10.5, (4,4));
-    /// let matrix2 = Matrix::init(10.5, (1,4));
     ///
-    /// let res = matrix.concat(&matrix2, Dimension::Row).unwrap();
+    /// let matrix = Matrix::new(vec![1, 2, 3, 4, 5, 6], (3,2)).unwrap();
     ///
-    /// assert_eq!(res.shape(), (5,4));
+    /// assert_eq!(matrix.row_slice(2), Some(&[5, 6][..]));
+    /// assert_eq!(matrix.row_slice(0).unwrap().iter().sum::<i32>(), 3);
+    /// assert_eq!(matrix.row_slice(3), None);
     /// ```
-    pub fn concat(&self, other: &Self, dim: Dimension) -> Result<Self, MatrixError> {
-        match dim {
-            Dimension::Row => {
-                if self.ncols != other.ncols {
-                    return Err(MatrixError::MatrixConcatinationError.into());
-                }
-
-                let mut new_data = self.data.clone();
-
-                new_data.extend(other.data.iter());
-
-                let nrows = self.nrows + other.nrows;
-                let shape = (nrows, self.ncols);
-
-                return Ok(Self::new(new_data, shape).unwrap());
-            }
-
-            Dimension::Col => {
-                if self.nrows != other.nrows {
-                    return Err(MatrixError::MatrixConcatinationError.into());
-                }
-
-                let mut new_data: Vec<T> = Vec::new();
-
-                let take_self = self.ncols;
-                let take_other = other.ncols;
-
-                for (idx, _) in self.data.iter().step_by(take_self).enumerate() {
-                    // Add from self, then other
-                    let row = (idx / take_self) * take_self;
-                    new_data.extend(self.data.iter().skip(row).take(take_self));
-                    new_data.extend(other.data.iter().skip(row).take(take_other));
-                }
-
-                let ncols = self.ncols + other.ncols;
-                let shape = (self.nrows, ncols);
-
-                return Ok(Self::new(new_data, shape).unwrap());
-            }
-        };
+    pub fn row_slice(&self, i: usize) -> Option<&[T]> {
+        self.row(i)
     }
 
-    // TODO: Add option to transpose to be able to extend
-    // Doens't change anything if dimension mismatch
-
-    /// Extend a matrix with another on a dimension
+    /// Gets a row of the matrix as a mutable slice, without copying.
+    ///
+    /// Returns None if the row is out of bounds
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
-    /// use sukker::Dimension;
     ///
-    /// let mut matrix = Matrix::init(10.5, (4,4));
-    /// let matrix2 = Matrix::init(10.5, (4,1));
+    /// let mut matrix = Matrix::new(vec![1, 2, 3, 4, 5, 6], (2,3)).unwrap();
     ///
-    /// matrix.extend(&matrix2, Dimension::Col);
+    /// matrix.row_slice_mut(0).unwrap()[2] = 10;
     ///
-    /// assert_eq!(matrix.shape(), (4,5));
+    /// assert_eq!(matrix.at(0, 2), 10);
+    /// assert!(matrix.row_slice_mut(2).is_none());
     /// ```
-    pub fn extend(&mut self, other: &Self, dim: Dimension) {
-        match dim {
-            Dimension::Row => {
-                if self.ncols != other.ncols {
-                    eprintln!("Error: Dimension mismatch");
-                    return;
-                }
-
-                self.data.extend(other.data.iter());
+    pub fn row_slice_mut(&mut self, i: usize) -> Option<&mut [T]> {
+        if i >= self.nrows {
+            return None;
+        }
 
-                self.nrows += other.nrows;
-            }
+        Some(&mut self.data[i * self.ncols..(i + 1) * self.ncols])
+    }
 
-            Dimension::Col => {
-                if self.nrows != other.nrows {
-                    eprintln!("Error: Dimension mismatch");
-                    return;
-                }
+    /// Gets a column of the matrix as a vector.
+    ///
+    /// Columns are strided in memory, so this has to copy.
+    /// Returns None if the column is out of bounds
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::new(vec![1, 2, 3, 4, 5, 6], (2,3)).unwrap();
+    ///
+    /// assert_eq!(matrix.col(1), Some(vec![2, 5]));
+    /// assert_eq!(matrix.col(3), None);
+    /// ```
+    pub fn col(&self, j: usize) -> Option<Vec<T>> {
+        if j >= self.ncols {
+            return None;
+        }
 
-                let mut new_data: Vec<T> = Vec::new();
+        Some(self.data.iter().skip(j).step_by(self.ncols).copied().collect())
+    }
 
-                let take_self = self.ncols;
-                let take_other = other.ncols;
+    /// Returns a new matrix with row i removed.
+    ///
+    /// Returns MatrixIndexOutOfBoundsError if the row does not exist
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::new(vec![1, 2, 3, 4, 5, 6], (3,2)).unwrap();
+    ///
+    /// let res = matrix.remove_row(1).unwrap();
+    ///
+    /// assert_eq!(res.shape(), (2,2));
+    /// assert_eq!(res.get_vec(), vec![1, 2, 5, 6]);
+    /// ```
+    pub fn remove_row(&self, i: usize) -> Result<Self, MatrixError> {
+        if i >= self.nrows {
+            return Err(MatrixError::MatrixIndexOutOfBoundsError);
+        }
 
-                for (idx, _) in self.data.iter().step_by(take_self).enumerate() {
-                    // Add from self, then other
-                    let row = (idx / take_self) * take_self;
-                    new_data.extend(self.data.iter().skip(row).take(take_self));
-                    new_data.extend(other.data.iter().skip(row).take(take_other));
-                }
+        let data: Vec<T> = self
+            .row_iter()
+            .enumerate()
+            .filter(|&(row, _)| row != i)
+            .flat_map(|(_, row)| row.iter().copied())
+            .collect();
 
-                self.ncols += other.ncols;
-            }
-        };
+        Self::new(data, (self.nrows - 1, self.ncols))
     }
 
-    ///  Sets element based on is and js
+    /// Returns a new matrix with column j removed.
     ///
-    ///  Sets nothing if you;re out of bounds
+    /// Returns MatrixIndexOutOfBoundsError if the column does not exist
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let mut matrix = Matrix::init(10.5, (2,3));
-    /// matrix.set(11.5, (1, 2));
+    /// let matrix = Matrix::new(vec![1, 2, 3, 4, 5, 6], (2,3)).unwrap();
     ///
-    /// assert_eq!(matrix.get(1,2).unwrap(), 11.5);
+    /// let res = matrix.remove_col(0).unwrap();
+    ///
+    /// assert_eq!(res.shape(), (2,2));
+    /// assert_eq!(res.get_vec(), vec![2, 3, 5, 6]);
     /// ```
-    pub fn set(&mut self, value: T, idx: Shape) {
-        let idx = at!(idx.0, idx.1, self.ncols);
-
-        if idx >= self.size() {
-            eprintln!("Error: Index out of bounds. Not setting value.");
-            return;
+    pub fn remove_col(&self, j: usize) -> Result<Self, MatrixError> {
+        if j >= self.ncols {
+            return Err(MatrixError::MatrixIndexOutOfBoundsError);
         }
 
-        self.data[idx] = value;
+        let data: Vec<T> = self
+            .data
+            .iter()
+            .enumerate()
+            .filter(|&(idx, _)| idx % self.ncols != j)
+            .map(|(_, &e)| e)
+            .collect();
+
+        Self::new(data, (self.nrows, self.ncols - 1))
     }
 
-    ///  Sets many elements based on vector of indeces
+    /// Returns a new matrix with row inserted before row i.
+    /// Passing i = nrows appends the row at the end.
     ///
-    ///  For indexes out of bounds, nothing is set
+    /// Returns MatrixIndexOutOfBoundsError if i > nrows,
+    /// and MatrixDimensionMismatchError if the row is not ncols long
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let mut matrix = Matrix::init(10.5, (2,3));
-    /// matrix.set_many(vec![(1,2), (1,1)], 11.5);
+    /// let matrix = Matrix::new(vec![1, 2, 5, 6], (2,2)).unwrap();
     ///
-    /// assert_eq!(matrix.get(1,2).unwrap(), 11.5);
-    /// assert_eq!(matrix.get(1,1).unwrap(), 11.5);
-    /// assert_eq!(matrix.get(0,1).unwrap(), 10.5);
+    /// let res = matrix.insert_row(1, &[3, 4]).unwrap();
+    ///
+    /// assert_eq!(res.shape(), (3,2));
+    /// assert_eq!(res.get_vec(), vec![1, 2, 3, 4, 5, 6]);
     /// ```
-    pub fn set_many(&mut self, idx_list: Vec<Shape>, value: T) {
-        idx_list.iter().for_each(|&idx| self.set(value, idx));
+    pub fn insert_row(&self, i: usize, row: &[T]) -> Result<Self, MatrixError> {
+        if i > self.nrows {
+            return Err(MatrixError::MatrixIndexOutOfBoundsError);
+        }
+
+        if row.len() != self.ncols {
+            return Err(MatrixError::MatrixDimensionMismatchError);
+        }
+
+        let split = i * self.ncols;
+
+        let data: Vec<T> = self.data[..split]
+            .iter()
+            .chain(row.iter())
+            .chain(self.data[split..].iter())
+            .copied()
+            .collect();
+
+        Self::new(data, (self.nrows + 1, self.ncols))
     }
 
-    /// Sets all elements of a matrix in a 1d range.
+    /// Returns a new matrix with col inserted before column j.
+    /// Passing j = ncols appends the column at the end.
     ///
-    /// The range is inclusive to stop, and will panic
-    /// if any indexes are out of range
+    /// Returns MatrixIndexOutOfBoundsError if j > ncols,
+    /// and MatrixDimensionMismatchError if the column is not nrows long
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let mut matrix = Matrix::init(10.5, (2,3));
-    /// matrix.set_range(0, 3, 11.5);
+    /// let matrix = Matrix::new(vec![1, 3, 4, 6], (2,2)).unwrap();
     ///
-    /// assert_eq!(matrix.get(0,2).unwrap(), 11.5);
-    /// assert_eq!(matrix.get(0,1).unwrap(), 11.5);
-    /// assert_eq!(matrix.get(1,1).unwrap(), 10.5);
+    /// let res = matrix.insert_col(1, &[2, 5]).unwrap();
+    ///
+    /// assert_eq!(res.shape(), (2,3));
+    /// assert_eq!(res.get_vec(), vec![1, 2, 3, 4, 5, 6]);
     /// ```
-    pub fn set_range(&mut self, start: usize, stop: usize, value: T) {
-        (start..=stop).for_each(|i| self.data[i] = value);
+    pub fn insert_col(&self, j: usize, col: &[T]) -> Result<Self, MatrixError> {
+        if j > self.ncols {
+            return Err(MatrixError::MatrixIndexOutOfBoundsError);
+        }
+
+        if col.len() != self.nrows {
+            return Err(MatrixError::MatrixDimensionMismatchError);
+        }
+
+        // Indexed by row instead of row_iter, which yields
+        // no rows at all for an n x 0 matrix
+        let data: Vec<T> = (0..self.nrows)
+            .zip(col.iter())
+            .flat_map(|(i, &val)| {
+                let row = self.row(i).unwrap();
+
+                row[..j]
+                    .iter()
+                    .copied()
+                    .chain(std::iter::once(val))
+                    .chain(row[j..].iter().copied())
+            })
+            .collect();
+
+        Self::new(data, (self.nrows, self.ncols + 1))
     }
 
-    /// Calculates the (row, col) for a matrix by a single index
+    /// Repeats the matrix rep_rows times vertically and rep_cols times
+    /// horizontally, like numpy's `tile`.
+    ///
+    /// The result has shape (nrows * rep_rows, ncols * rep_cols),
+    /// so zero repetitions in either direction gives an empty matrix
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let matrix = Matrix::init(10.5, (2,2));
-    /// let inv = matrix.one_to_2d_idx(1);
+    /// let matrix = Matrix::new(vec![1, 2], (1,2)).unwrap();
     ///
-    /// assert_eq!(inv, (0,1));
+    /// let res = matrix.tile(2, 2);
+    ///
+    /// assert_eq!(res.shape(), (2,4));
+    /// assert_eq!(res.get_vec(), vec![1, 2, 1, 2, 1, 2, 1, 2]);
     /// ```
-    pub fn one_to_2d_idx(&self, idx: usize) -> Shape {
-        let row = idx / self.ncols;
-        let col = idx % self.ncols;
+    pub fn tile(&self, rep_rows: usize, rep_cols: usize) -> Self {
+        let data: Vec<T> = (0..rep_rows)
+            .flat_map(|_| self.row_iter())
+            .flat_map(|row| (0..rep_cols).flat_map(move |_| row.iter().copied()))
+            .collect();
 
-        (row, col)
+        Self::new(data, (self.nrows * rep_rows, self.ncols * rep_cols)).unwrap()
     }
 
-    /// Finds maximum element in the matrix
+    /// Mirrors the matrix left to right, reversing every row
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let matrix = Matrix::init(10.5, (2,3));
+    /// let matrix = Matrix::new(vec![1, 2, 3, 4, 5, 6], (2,3)).unwrap();
     ///
-    /// assert_eq!(matrix.max(), 10.5);
+    /// assert_eq!(matrix.flip_horizontal().get_vec(), vec![3, 2, 1, 6, 5, 4]);
     /// ```
-    pub fn max(&self) -> T {
-        // Matrix must have at least one element, thus we can unwrap
-        *self
-            .data
-            .par_iter()
-            .max_by(|a, b| a.partial_cmp(b).unwrap())
-            .unwrap()
+    pub fn flip_horizontal(&self) -> Self {
+        let data: Vec<T> = self
+            .row_iter()
+            .flat_map(|row| row.iter().rev().copied())
+            .collect();
+
+        Self::new(data, self.shape()).unwrap()
     }
 
-    /// Finds minimum element in the matrix
+    /// Mirrors the matrix top to bottom, reversing the order of the rows
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let mut matrix = Matrix::init(10.5, (2,3));
-    /// matrix.set(1.0, (0,2));
+    /// let matrix = Matrix::new(vec![1, 2, 3, 4, 5, 6], (2,3)).unwrap();
     ///
-    /// assert_eq!(matrix.min(), 1.0);
+    /// assert_eq!(matrix.flip_vertical().get_vec(), vec![4, 5, 6, 1, 2, 3]);
     /// ```
-    pub fn min(&self) -> T {
-        // Matrix must have at least one element, thus we can unwrap
-        *self
+    pub fn flip_vertical(&self) -> Self {
+        let data: Vec<T> = self
             .data
-            .par_iter()
-            .min_by(|a, b| a.partial_cmp(b).unwrap())
-            .unwrap()
+            .chunks(self.ncols.max(1))
+            .rev()
+            .flat_map(|row| row.iter().copied())
+            .collect();
+
+        Self::new(data, self.shape()).unwrap()
     }
 
-    /// Finds position in matrix where value is highest.
-    /// Restricted to find this across a row or column
-    /// in the matrix.
+    /// Rotates the matrix 90 degrees counter-clockwise k times.
+    ///
+    /// Only k % 4 matters. For odd k the shape is swapped
     ///
     /// # Examples
     ///
     /// ```
-    /// use sukker::{Matrix, Dimension};
+    /// use sukker::Matrix;
     ///
-    /// let mut matrix = Matrix::init(1.0, (3,3));
-    /// matrix.set(15.0, (0,2));
+    /// let matrix = Matrix::new(vec![1, 2, 3, 4, 5, 6], (2,3)).unwrap();
     ///
+    /// let res = matrix.rot90(1);
+    ///
+    /// assert_eq!(res.shape(), (3,2));
+    /// assert_eq!(res.get_vec(), vec![3, 6, 2, 5, 1, 4]);
     /// ```
-    fn argmax(&self, rowcol: usize, dimension: Dimension) -> Option<Shape> {
-        match dimension {
-            Dimension::Row => {
-                if rowcol >= self.nrows - 1 {
-                    return None;
-                }
+    pub fn rot90(&self, k: usize) -> Self {
+        let (m, n) = self.shape();
 
-                let mut highest: T = T::one();
-                let mut i = 0;
-
-                for (idx, elem) in self
-                    .data
-                    .iter()
-                    .enumerate()
-                    .skip(rowcol * self.ncols)
-                    .take(self.ncols)
-                {
-                    if *elem >= highest {
-                        i = idx;
-                    }
-                }
+        match k % 4 {
+            0 => self.clone(),
+            1 => {
+                let data: Vec<T> = iproduct!(0..n, 0..m)
+                    .map(|(i, j)| self.data[at!(j, n - 1 - i, n)])
+                    .collect();
 
-                Some(self.one_to_2d_idx(i))
+                Self::new(data, (n, m)).unwrap()
             }
+            2 => {
+                let data: Vec<T> = self.data.iter().rev().copied().collect();
 
-            Dimension::Col => {
-                if rowcol >= self.ncols - 1 {
-                    return None;
-                }
-
-                let mut highest: T = T::one();
-
-                let mut i = 0;
-
-                for (idx, elem) in self
-                    .data
-                    .iter()
-                    .enumerate()
-                    .skip(rowcol)
-                    .step_by(self.ncols)
-                {
-                    if *elem >= highest {
-                        i = idx;
-                    }
-                }
+                Self::new(data, (m, n)).unwrap()
+            }
+            _ => {
+                let data: Vec<T> = iproduct!(0..n, 0..m)
+                    .map(|(i, j)| self.data[at!(m - 1 - j, i, n)])
+                    .collect();
 
-                Some(self.one_to_2d_idx(i))
+                Self::new(data, (n, m)).unwrap()
             }
         }
     }
 
-    /// Finds position in matrix where value is lowest.
-    /// Restricted to find this across a row or column
-    /// in the matrix.
+    /// Converts every element to another element type.
     ///
+    /// Conversion follows the semantics of `as`: integers to floats
+    /// round to the nearest representable value, floats to integers
+    /// truncate towards zero and saturate at the bounds of the target
+    /// type, with NaN becoming 0. Narrowing integer casts wrap around
     ///
     /// # Examples
     ///
     /// ```
-    /// use sukker::{Matrix, Dimension};
+    /// use sukker::Matrix;
     ///
-    /// let mut matrix = Matrix::init(10.5, (3,3));
-    /// matrix.set(1.0, (0,1));
+    /// let matrix = Matrix::new(vec![1.7, -2.9, 3.0, 0.2], (2,2)).unwrap();
+    ///
+    /// let res: Matrix<i32> = matrix.cast();
     ///
-    /// // assert_eq!(matrix.argmin(1, Dimension::Col), Some(1));
+    /// assert_eq!(res.shape(), (2,2));
+    /// assert_eq!(res.get_vec(), vec![1, -2, 3, 0]);
     /// ```
-    fn argmin(&self, rowcol: usize, dimension: Dimension) -> Option<Shape> {
-        match dimension {
-            Dimension::Row => {
-                if rowcol >= self.nrows - 1 {
-                    return None;
-                }
-
-                let mut lowest: T = T::zero();
-
-                let mut i = 0;
-
-                for (idx, elem) in self
-                    .data
-                    .iter()
-                    .enumerate()
-                    .skip(rowcol * self.ncols)
-                    .take(self.ncols)
-                {
-                    if *elem < lowest {
-                        i = idx;
-                    }
-                }
-
-                Some(self.one_to_2d_idx(i))
-            }
-
-            Dimension::Col => {
-                if rowcol >= self.ncols - 1 {
-                    return None;
-                }
-
-                let mut lowest: T = T::zero();
-
-                let mut i = 0;
-
-                for (idx, elem) in self
-                    .data
-                    .iter()
-                    .enumerate()
-                    .skip(rowcol)
-                    .step_by(self.ncols)
-                {
-                    if *elem <= lowest {
-                        i = idx;
-                    }
-                }
+    pub fn cast<U>(&self) -> Matrix<'a, U>
+    where
+        U: MatrixElement + 'static,
+        <U as FromStr>::Err: Error + 'static,
+        Vec<U>: IntoParallelIterator,
+        Vec<&'a U>: IntoParallelRefIterator<'a>,
+        T: AsPrimitive<U>,
+    {
+        let data: Vec<U> = self.data.iter().map(|&x| x.as_()).collect();
 
-                Some(self.one_to_2d_idx(i))
-            }
-        }
+        Matrix::<U>::new(data, self.shape()).unwrap()
     }
 
-    /// Finds total sum of matrix
+    /// Iterates over the rows of the matrix as slices
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let matrix = Matrix::init(10f32, (2,2));
+    /// let matrix = Matrix::new(vec![1, 2, 3, 4, 5, 6], (2,3)).unwrap();
     ///
-    /// assert_eq!(matrix.cumsum(), 40.0);
+    /// let sums: Vec<i32> = matrix.row_iter().map(|row| row.iter().sum()).collect();
+    ///
+    /// assert_eq!(sums, vec![6, 15]);
     /// ```
-    pub fn cumsum(&self) -> T {
-        if self.size() == 0 {
-            return T::zero();
-        }
-
-        self.data.par_iter().copied().sum()
+    pub fn row_iter(&self) -> impl Iterator<Item = &[T]> {
+        // Empty matrices have no data, so any non-zero chunk size gives no rows
+        self.data.chunks_exact(self.ncols.max(1))
     }
 
-    /// Multiplies  all elements in matrix
+    /// Iterates over the columns of the matrix.
+    ///
+    /// Columns are strided in memory, so each one is collected into a vector
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let matrix = Matrix::init(10f32, (2,2));
+    /// let matrix = Matrix::new(vec![1, 2, 3, 4, 5, 6], (2,3)).unwrap();
     ///
-    /// assert_eq!(matrix.cumprod(), 10000.0);
+    /// let sums: Vec<i32> = matrix.col_iter().map(|col| col.iter().sum()).collect();
+    ///
+    /// assert_eq!(sums, vec![5, 7, 9]);
     /// ```
-    pub fn cumprod(&self) -> T {
-        if self.size() == 0 {
-            return T::zero();
-        }
+    pub fn col_iter(&self) -> impl Iterator<Item = Vec<T>> + '_ {
+        let data = &self.data;
+        let ncols = self.ncols;
 
-        self.data.par_iter().copied().product()
+        (0..ncols).map(move |j| data.iter().skip(j).step_by(ncols).copied().collect())
     }
 
-    /// Gets the average of the matrix
+    /// Gets the main diagonal of the matrix.
+    ///
+    /// For non-square matrices, the diagonal has
+    /// as many elements as the shortest dimension
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let matrix = Matrix::init(10f32, (2,2));
+    /// let matrix = Matrix::new(vec![1, 2, 3, 4, 5, 6], (2,3)).unwrap();
     ///
-    /// assert_eq!(matrix.avg(), 10.0);
+    /// assert_eq!(matrix.diagonal(), vec![1, 5]);
     /// ```
-    pub fn avg(&self) -> T {
-        self.data.par_iter().copied().sum::<T>() / self.size().to_string().parse::<T>().unwrap()
+    pub fn diagonal(&self) -> Vec<T> {
+        self.diagonal_offset(0)
     }
 
-    /// Gets the mean of the matrix
+    /// Gets a diagonal offset from the main one.
+    ///
+    /// A positive `k` gives the k-th diagonal above the main one,
+    /// and a negative `k` the k-th diagonal below it.
+    /// Diagonals outside the matrix are empty
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let matrix = Matrix::init(10f32, (2,2));
+    /// let matrix = Matrix::new(vec![1, 2, 3, 4, 5, 6, 7, 8, 9], (3,3)).unwrap();
     ///
-    /// assert_eq!(matrix.mean(), 10.0);
+    /// assert_eq!(matrix.diagonal_offset(1), vec![2, 6]);
+    /// assert_eq!(matrix.diagonal_offset(-2), vec![7]);
+    /// assert_eq!(matrix.diagonal_offset(3), Vec::<i32>::new());
     /// ```
-    pub fn mean(&self) -> T {
-        self.avg()
+    pub fn diagonal_offset(&self, k: isize) -> Vec<T> {
+        let offset = k.unsigned_abs();
+
+        let (row_start, col_start) = if k >= 0 { (0, offset) } else { (offset, 0) };
+
+        if row_start >= self.nrows || col_start >= self.ncols {
+            return Vec::new();
+        }
+
+        let len = (self.nrows - row_start).min(self.ncols - col_start);
+
+        (0..len)
+            .map(|i| self.data[at!(row_start + i, col_start + i, self.ncols)])
+            .collect()
     }
 
-    /// Gets the median of the matrix
+    ///  Gets a piece of the matrix out as a matrix
+    ///
+    ///  If some indeces are out of bounds, unlike `get_vec_slice`
+    ///  this function will return an IndexOutOfBoundsError
+    ///  and will not return data
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let matrix = Matrix::new(vec![1.0, 4.0, 6.0, 5.0], (2,2)).unwrap();
+    /// let matrix = Matrix::init(10.5, (4,4));
+    /// let sub_matrix = matrix.get_sub_matrix((1,1), (2,2)).unwrap();
     ///
-    /// assert!(matrix.median() >= 4.45 && matrix.median() <= 4.55);
+    /// assert_eq!(sub_matrix.get_vec(), vec![10.5,10.5,10.5,10.5]);
     /// ```
-    pub fn median(&self) -> T {
-        if self.size() == 1 {
-            return self.at(0, 0);
-        }
+    pub fn get_sub_matrix(&self, start_idx: Shape, size: Shape) -> Result<Self, MatrixError> {
+        let (start_row, start_col) = start_idx;
+        let (dx, dy) = size;
 
-        match self.data.len() % 2 {
-            0 => {
-                let half: usize = self.data.len() / 2;
+        let data = iproduct!(start_row..start_row + dy, start_col..start_col + dx)
+            .filter_map(|(i, j)| self.get(i, j))
+            .collect();
 
-                self.data
-                    .iter()
-                    .sorted_by(|a, b| a.partial_cmp(&b).unwrap())
-                    .skip(half - 1)
-                    .take(2)
-                    .copied()
-                    .sum::<T>()
-                    / (T::one() + T::one())
+        return match Self::new(data, size) {
+            Ok(a) => Ok(a),
+            Err(_) => Err(MatrixError::MatrixIndexOutOfBoundsError.into()),
+        };
+    }
+
+    /// Concat two mtrices on a dimension
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    /// use sukker::Dimension;
+    ///
+    /// let matrix = Matrix::init(10.5, (4,4));
+    /// let matrix2 = Matrix::init(10.5, (1,4));
+    ///
+    /// let res = matrix.concat(&matrix2, Dimension::Row).unwrap();
+    ///
+    /// assert_eq!(res.shape(), (5,4));
+    /// ```
+    pub fn concat(&self, other: &Self, dim: Dimension) -> Result<Self, MatrixError> {
+        if !self.can_concat(other.shape(), &dim) {
+            return Err(MatrixError::MatrixConcatinationError);
+        }
+
+        match dim {
+            Dimension::Row => {
+                let mut new_data = self.data.clone();
+
+                new_data.extend(other.data.iter());
+
+                let nrows = self.nrows + other.nrows;
+                let shape = (nrows, self.ncols);
+
+                Self::new(new_data, shape)
             }
-            1 => {
-                let half: usize = self.data.len() / 2;
 
-                self.data
-                    .iter()
-                    .sorted_by(|a, b| a.partial_cmp(&b).unwrap())
-                    .nth(half)
-                    .copied()
-                    .unwrap()
+            Dimension::Col => {
+                let mut new_data: Vec<T> = Vec::with_capacity(self.size() + other.size());
+
+                // Add a row from self, then the same row from other
+                for i in 0..self.nrows {
+                    new_data.extend(self.row(i).unwrap());
+                    new_data.extend(other.row(i).unwrap());
+                }
+
+                let ncols = self.ncols + other.ncols;
+                let shape = (self.nrows, ncols);
+
+                Self::new(new_data, shape)
             }
-            _ => unreachable!(),
         }
     }
 
-    /// Sums up elements over given axis and dimension.
-    /// Will return 0 if you're out of bounds
+    /// Stacks matrices on top of each other, in order.
+    ///
+    /// All matrices must have the same number of columns.
+    /// Returns MatrixConcatinationError if they do not, or if no matrices are given
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let a = Matrix::new(vec![1, 2], (1,2)).unwrap();
+    /// let b = Matrix::new(vec![3, 4, 5, 6], (2,2)).unwrap();
+    ///
+    /// let res = Matrix::vstack(&[&a, &b]).unwrap();
+    ///
+    /// assert_eq!(res.shape(), (3,2));
+    /// assert_eq!(res.get_vec(), vec![1, 2, 3, 4, 5, 6]);
+    /// ```
+    pub fn vstack(mats: &[&Self]) -> Result<Self, MatrixError> {
+        let ncols = mats
+            .first()
+            .ok_or(MatrixError::MatrixConcatinationError)?
+            .ncols;
+
+        if mats.iter().any(|m| m.ncols != ncols) {
+            return Err(MatrixError::MatrixConcatinationError);
+        }
+
+        let nrows = mats.iter().map(|m| m.nrows).sum();
+        let data: Vec<T> = mats.iter().flat_map(|m| m.data.iter().copied()).collect();
+
+        Self::new(data, (nrows, ncols))
+    }
+
+    /// Stacks matrices next to each other, in order.
+    ///
+    /// All matrices must have the same number of rows.
+    /// Returns MatrixConcatinationError if they do not, or if no matrices are given
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let a = Matrix::new(vec![1, 4], (2,1)).unwrap();
+    /// let b = Matrix::new(vec![2, 3, 5, 6], (2,2)).unwrap();
+    ///
+    /// let res = Matrix::hstack(&[&a, &b]).unwrap();
+    ///
+    /// assert_eq!(res.shape(), (2,3));
+    /// assert_eq!(res.get_vec(), vec![1, 2, 3, 4, 5, 6]);
+    /// ```
+    pub fn hstack(mats: &[&Self]) -> Result<Self, MatrixError> {
+        let nrows = mats
+            .first()
+            .ok_or(MatrixError::MatrixConcatinationError)?
+            .nrows;
+
+        if mats.iter().any(|m| m.nrows != nrows) {
+            return Err(MatrixError::MatrixConcatinationError);
+        }
+
+        let ncols = mats.iter().map(|m| m.ncols).sum();
+        let data: Vec<T> = (0..nrows)
+            .flat_map(|i| {
+                mats.iter()
+                    .flat_map(move |m| m.row(i).unwrap().iter().copied())
+            })
+            .collect();
+
+        Self::new(data, (nrows, ncols))
+    }
+
+    /// Concat two matrices on a dimension, transposing `other` first
+    /// if that is the only way the shapes line up.
+    ///
+    /// `self` is never transposed. If the shapes already fit,
+    /// this is the same as `concat`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    /// use sukker::Dimension;
+    ///
+    /// let matrix = Matrix::init(1, (4,1));
+    /// let row = Matrix::init(2, (1,4));
+    ///
+    /// let res = matrix.concat_auto(&row, Dimension::Col).unwrap();
+    ///
+    /// assert_eq!(res.shape(), (4,2));
+    /// assert_eq!(res.get_vec(), vec![1, 2, 1, 2, 1, 2, 1, 2]);
+    /// ```
+    pub fn concat_auto(&self, other: &Self, dim: Dimension) -> Result<Self, MatrixError> {
+        let (rows, cols) = other.shape();
+
+        if !self.can_concat((rows, cols), &dim) && self.can_concat((cols, rows), &dim) {
+            return self.concat(&other.transpose_copy(), dim);
+        }
+
+        self.concat(other, dim)
+    }
+
+    // Doens't change anything if dimension mismatch
+
+    /// Extend a matrix with another on a dimension
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    /// use sukker::Dimension;
+    ///
+    /// let mut matrix = Matrix::init(10.5, (4,4));
+    /// let matrix2 = Matrix::init(10.5, (4,1));
+    ///
+    /// matrix.extend(&matrix2, Dimension::Col);
+    ///
+    /// assert_eq!(matrix.shape(), (4,5));
+    /// ```
+    pub fn extend(&mut self, other: &Self, dim: Dimension) {
+        match self.concat(other, dim) {
+            Ok(res) => *self = res,
+            Err(_) => eprintln!("Error: Dimension mismatch"),
+        }
+    }
+
+    /// Extend a matrix with another on a dimension, transposing `other`
+    /// first if that is the only way the shapes line up.
+    ///
+    /// `self` is never transposed. Returns a MatrixConcatinationError
+    /// and keeps the old matrix if the shapes do not fit either way
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    /// use sukker::Dimension;
+    ///
+    /// let mut matrix = Matrix::init(10.5, (4,4));
+    /// let matrix2 = Matrix::init(10.5, (1,4));
+    ///
+    /// matrix.extend_auto(&matrix2, Dimension::Col).unwrap();
+    ///
+    /// assert_eq!(matrix.shape(), (4,5));
+    /// ```
+    pub fn extend_auto(&mut self, other: &Self, dim: Dimension) -> Result<(), MatrixError> {
+        *self = self.concat_auto(other, dim)?;
+
+        Ok(())
+    }
+
+    // Rows are stacked when the column counts match,
+    // and columns are placed side by side when the row counts match
+    fn can_concat(&self, other_shape: Shape, dim: &Dimension) -> bool {
+        match dim {
+            Dimension::Row => self.ncols == other_shape.1,
+            Dimension::Col => self.nrows == other_shape.0,
+        }
+    }
+
+    ///  Sets element based on is and js
+    ///
+    ///  Sets nothing if you;re out of bounds
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let mut matrix = Matrix::init(10.5, (2,3));
+    /// matrix.set(11.5, (1, 2));
+    ///
+    /// assert_eq!(matrix.get(1,2).unwrap(), 11.5);
+    /// ```
+    pub fn set(&mut self, value: T, idx: Shape) {
+        let idx = at!(idx.0, idx.1, self.ncols);
+
+        if idx >= self.size() {
+            eprintln!("Error: Index out of bounds. Not setting value.");
+            return;
+        }
+
+        self.data[idx] = value;
+    }
+
+    ///  Sets many elements based on vector of indeces
+    ///
+    ///  For indexes out of bounds, nothing is set
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let mut matrix = Matrix::init(10.5, (2,3));
+    /// matrix.set_many(vec![(1,2), (1,1)], 11.5);
+    ///
+    /// assert_eq!(matrix.get(1,2).unwrap(), 11.5);
+    /// assert_eq!(matrix.get(1,1).unwrap(), 11.5);
+    /// assert_eq!(matrix.get(0,1).unwrap(), 10.5);
+    /// ```
+    pub fn set_many(&mut self, idx_list: Vec<Shape>, value: T) {
+        idx_list.iter().for_each(|&idx| self.set(value, idx));
+    }
+
+    /// Sets all elements of a matrix in a 1d range.
+    ///
+    /// The indexes are flat indexes into the row major data,
+    /// so a range can wrap over several rows. For a 2d region, see `set_rect`.
+    ///
+    /// The range is inclusive to stop, and indexes
+    /// past the end of the matrix are skipped
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let mut matrix = Matrix::init(10.5, (2,3));
+    /// matrix.set_range(0, 3, 11.5);
+    ///
+    /// assert_eq!(matrix.get(0,2).unwrap(), 11.5);
+    /// assert_eq!(matrix.get(0,1).unwrap(), 11.5);
+    /// assert_eq!(matrix.get(1,1).unwrap(), 10.5);
+    /// ```
+    pub fn set_range(&mut self, start: usize, stop: usize, value: T) {
+        self.data
+            .iter_mut()
+            .skip(start)
+            .take(stop.saturating_add(1).saturating_sub(start))
+            .for_each(|e| *e = value);
+    }
+
+    /// Sets all elements in the rectangle spanned by
+    /// `top_left` and `bottom_right`, both inclusive.
+    ///
+    /// Returns a MatrixIndexOutOfBoundsError and sets nothing if the
+    /// rectangle does not fit in the matrix, or the corners are swapped
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let mut matrix = Matrix::init(0, (3,3));
+    /// matrix.set_rect((0,1), (1,2), 1).unwrap();
+    ///
+    /// assert_eq!(matrix.get_vec(), vec![0, 1, 1, 0, 1, 1, 0, 0, 0]);
+    /// ```
+    pub fn set_rect(
+        &mut self,
+        top_left: Shape,
+        bottom_right: Shape,
+        value: T,
+    ) -> Result<(), MatrixError> {
+        let (top, left) = top_left;
+        let (bottom, right) = bottom_right;
+
+        if top > bottom || left > right || bottom >= self.nrows || right >= self.ncols {
+            return Err(MatrixError::MatrixIndexOutOfBoundsError);
+        }
+
+        let ncols = self.ncols;
+
+        self.data
+            .par_chunks_mut(ncols)
+            .skip(top)
+            .take(bottom - top + 1)
+            .for_each(|row| row[left..=right].iter_mut().for_each(|e| *e = value));
+
+        Ok(())
+    }
+
+    /// Gets the elements at a list of (row, col) positions,
+    /// in the same order as the positions.
+    ///
+    /// Positions out of bounds are skipped, so the result
+    /// can be shorter than the list of positions
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::new(vec![1, 2, 3, 4, 5, 6], (2,3)).unwrap();
+    ///
+    /// assert_eq!(matrix.gather(&[(1,2), (0,0), (2,0), (0,1)]), vec![6, 1, 2]);
+    /// ```
+    pub fn gather(&self, indices: &[Shape]) -> Vec<T> {
+        indices
+            .iter()
+            .filter(|&&(i, j)| i < self.nrows && j < self.ncols)
+            .map(|&(i, j)| self.data[at!(i, j, self.ncols)])
+            .collect()
+    }
+
+    /// Sets the elements at a list of (row, col) positions
+    /// to the matching values, in order.
+    ///
+    /// Returns MatrixDimensionMismatchError and sets nothing if
+    /// there is not exactly one value per position. Like `gather`,
+    /// positions out of bounds are skipped. If a position is repeated,
+    /// the last value wins
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let mut matrix = Matrix::init(0, (2,3));
+    /// matrix.scatter(&[(1,2), (0,0)], &[7, 8]).unwrap();
+    ///
+    /// assert_eq!(matrix.get_vec(), vec![8, 0, 0, 0, 0, 7]);
+    /// ```
+    pub fn scatter(&mut self, indices: &[Shape], values: &[T]) -> Result<(), MatrixError> {
+        if indices.len() != values.len() {
+            return Err(MatrixError::MatrixDimensionMismatchError);
+        }
+
+        for (&(i, j), &val) in indices.iter().zip(values) {
+            if i < self.nrows && j < self.ncols {
+                self.data[at!(i, j, self.ncols)] = val;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Swaps two rows of the matrix in-place.
+    ///
+    /// Returns MatrixIndexOutOfBoundsError if either row does not exist
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let mut matrix = Matrix::new(vec![1, 2, 3, 4, 5, 6], (3,2)).unwrap();
+    ///
+    /// matrix.swap_rows(0, 2).unwrap();
+    ///
+    /// assert_eq!(matrix.get_vec(), vec![5, 6, 3, 4, 1, 2]);
+    /// ```
+    pub fn swap_rows(&mut self, a: usize, b: usize) -> Result<(), MatrixError> {
+        if a >= self.nrows || b >= self.nrows {
+            return Err(MatrixError::MatrixIndexOutOfBoundsError);
+        }
+
+        swap_rows(&mut self.data, self.ncols, a, b);
+
+        Ok(())
+    }
+
+    /// Multiplies every element of a row by factor in-place.
+    ///
+    /// Returns MatrixIndexOutOfBoundsError if the row does not exist
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let mut matrix = Matrix::new(vec![1, 2, 3, 4, 5, 6], (3,2)).unwrap();
+    ///
+    /// matrix.scale_row(1, 10).unwrap();
+    ///
+    /// assert_eq!(matrix.get_vec(), vec![1, 2, 30, 40, 5, 6]);
+    /// ```
+    pub fn scale_row(&mut self, row: usize, factor: T) -> Result<(), MatrixError> {
+        if row >= self.nrows {
+            return Err(MatrixError::MatrixIndexOutOfBoundsError);
+        }
+
+        let ncols = self.ncols;
+
+        self.data[row * ncols..(row + 1) * ncols]
+            .iter_mut()
+            .for_each(|e| *e *= factor);
+
+        Ok(())
+    }
+
+    /// Adds factor times row src onto row dst in-place,
+    /// so that dst += factor * src.
+    ///
+    /// Returns MatrixIndexOutOfBoundsError if either row does not exist
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let mut matrix = Matrix::new(vec![1, 2, 3, 4, 5, 6], (3,2)).unwrap();
+    ///
+    /// matrix.add_scaled_row(2, 0, -5).unwrap();
+    ///
+    /// assert_eq!(matrix.get_vec(), vec![1, 2, 3, 4, 0, -4]);
+    /// ```
+    pub fn add_scaled_row(&mut self, dst: usize, src: usize, factor: T) -> Result<(), MatrixError> {
+        if dst >= self.nrows || src >= self.nrows {
+            return Err(MatrixError::MatrixIndexOutOfBoundsError);
+        }
+
+        let ncols = self.ncols;
+
+        for j in 0..ncols {
+            let val = self.data[at!(src, j, ncols)];
+            self.data[at!(dst, j, ncols)] += factor * val;
+        }
+
+        Ok(())
+    }
+
+    /// Swaps two columns of the matrix in-place.
+    ///
+    /// Returns MatrixIndexOutOfBoundsError if either column does not exist
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let mut matrix = Matrix::new(vec![1, 2, 3, 4, 5, 6], (3,2)).unwrap();
+    ///
+    /// matrix.swap_cols(0, 1).unwrap();
+    ///
+    /// assert_eq!(matrix.get_vec(), vec![2, 1, 4, 3, 6, 5]);
+    /// ```
+    pub fn swap_cols(&mut self, a: usize, b: usize) -> Result<(), MatrixError> {
+        if a >= self.ncols || b >= self.ncols {
+            return Err(MatrixError::MatrixIndexOutOfBoundsError);
+        }
+
+        let ncols = self.ncols;
+
+        for i in 0..self.nrows {
+            self.data.swap(at!(i, a, ncols), at!(i, b, ncols));
+        }
+
+        Ok(())
+    }
+
+    /// Multiplies every element of a column by factor in-place.
+    ///
+    /// Returns MatrixIndexOutOfBoundsError if the column does not exist
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let mut matrix = Matrix::new(vec![1, 2, 3, 4, 5, 6], (3,2)).unwrap();
+    ///
+    /// matrix.scale_col(1, -1).unwrap();
+    ///
+    /// assert_eq!(matrix.get_vec(), vec![1, -2, 3, -4, 5, -6]);
+    /// ```
+    pub fn scale_col(&mut self, col: usize, factor: T) -> Result<(), MatrixError> {
+        if col >= self.ncols {
+            return Err(MatrixError::MatrixIndexOutOfBoundsError);
+        }
+
+        let ncols = self.ncols;
+
+        for i in 0..self.nrows {
+            self.data[at!(i, col, ncols)] *= factor;
+        }
+
+        Ok(())
+    }
+
+    /// Adds factor times column src onto column dst in-place,
+    /// so that dst += factor * src.
+    ///
+    /// Returns MatrixIndexOutOfBoundsError if either column does not exist
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let mut matrix = Matrix::new(vec![1, 2, 3, 4, 5, 6], (3,2)).unwrap();
+    ///
+    /// matrix.add_scaled_col(1, 0, -2).unwrap();
+    ///
+    /// assert_eq!(matrix.get_vec(), vec![1, 0, 3, -2, 5, -4]);
+    /// ```
+    pub fn add_scaled_col(&mut self, dst: usize, src: usize, factor: T) -> Result<(), MatrixError> {
+        if dst >= self.ncols || src >= self.ncols {
+            return Err(MatrixError::MatrixIndexOutOfBoundsError);
+        }
+
+        let ncols = self.ncols;
+
+        for i in 0..self.nrows {
+            let val = self.data[at!(i, src, ncols)];
+            self.data[at!(i, dst, ncols)] += factor * val;
+        }
+
+        Ok(())
+    }
+
+    /// Calculates the (row, col) for a matrix by a single index
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::init(10.5, (2,2));
+    /// let inv = matrix.one_to_2d_idx(1);
+    ///
+    /// assert_eq!(inv, (0,1));
+    /// ```
+    pub fn one_to_2d_idx(&self, idx: usize) -> Shape {
+        let row = idx / self.ncols;
+        let col = idx % self.ncols;
+
+        (row, col)
+    }
+
+    /// Finds maximum element in the matrix
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::init(10.5, (2,3));
+    ///
+    /// assert_eq!(matrix.max(), 10.5);
+    /// ```
+    pub fn max(&self) -> T {
+        // Matrix must have at least one element, thus we can unwrap
+        *self
+            .data
+            .par_iter()
+            .max_by(|a, b| a.partial_cmp(b).unwrap())
+            .unwrap()
+    }
+
+    /// Finds minimum element in the matrix
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let mut matrix = Matrix::init(10.5, (2,3));
+    /// matrix.set(1.0, (0,2));
+    ///
+    /// assert_eq!(matrix.min(), 1.0);
+    /// ```
+    pub fn min(&self) -> T {
+        // Matrix must have at least one element, thus we can unwrap
+        *self
+            .data
+            .par_iter()
+            .min_by(|a, b| a.partial_cmp(b).unwrap())
+            .unwrap()
+    }
+
+    /// Finds position in matrix where value is highest.
+    /// Restricted to find this across a row or column
+    /// in the matrix.
+    ///
+    /// Returns None if the row or column is out of bounds.
+    /// If the highest value occurs several times, the first one is returned
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::{Matrix, Dimension};
+    ///
+    /// let mut matrix = Matrix::init(1.0, (3,3));
+    /// matrix.set(15.0, (0,2));
+    ///
+    /// assert_eq!(matrix.argmax(0, Dimension::Row), Some((0,2)));
+    /// assert_eq!(matrix.argmax(2, Dimension::Col), Some((0,2)));
+    /// ```
+    pub fn argmax(&self, rowcol: usize, dimension: Dimension) -> Option<Shape> {
+        self.arg_extreme(rowcol, dimension, |elem, best| elem > best)
+    }
+
+    /// Finds position in matrix where value is lowest.
+    /// Restricted to find this across a row or column
+    /// in the matrix.
+    ///
+    /// Returns None if the row or column is out of bounds.
+    /// If the lowest value occurs several times, the first one is returned
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::{Matrix, Dimension};
+    ///
+    /// let mut matrix = Matrix::init(10.5, (3,3));
+    /// matrix.set(1.0, (0,1));
+    ///
+    /// assert_eq!(matrix.argmin(1, Dimension::Col), Some((0,1)));
+    /// assert_eq!(matrix.argmin(0, Dimension::Row), Some((0,1)));
+    /// ```
+    pub fn argmin(&self, rowcol: usize, dimension: Dimension) -> Option<Shape> {
+        self.arg_extreme(rowcol, dimension, |elem, best| elem < best)
+    }
+
+    // Scans a row or column, keeping the first index where
+    // `better` holds against every previous element
+    fn arg_extreme<F>(&self, rowcol: usize, dimension: Dimension, better: F) -> Option<Shape>
+    where
+        F: Fn(T, T) -> bool,
+    {
+        let (start, step, len) = match dimension {
+            Dimension::Row if rowcol < self.nrows => (rowcol * self.ncols, 1, self.ncols),
+            Dimension::Col if rowcol < self.ncols => (rowcol, self.ncols, self.nrows),
+            _ => return None,
+        };
+
+        let (idx, _) = self
+            .data
+            .iter()
+            .copied()
+            .enumerate()
+            .skip(start)
+            .step_by(step)
+            .take(len)
+            .reduce(|best, cur| if better(cur.1, best.1) { cur } else { best })?;
+
+        Some(self.one_to_2d_idx(idx))
+    }
+
+    /// Finds total sum of matrix
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::init(10f32, (2,2));
+    ///
+    /// assert_eq!(matrix.cumsum(), 40.0);
+    /// ```
+    pub fn cumsum(&self) -> T {
+        if self.size() == 0 {
+            return T::zero();
+        }
+
+        self.data.par_iter().copied().sum()
+    }
+
+    /// Multiplies  all elements in matrix
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::init(10f32, (2,2));
+    ///
+    /// assert_eq!(matrix.cumprod(), 10000.0);
+    /// ```
+    pub fn cumprod(&self) -> T {
+        if self.size() == 0 {
+            return T::zero();
+        }
+
+        self.data.par_iter().copied().product()
+    }
+
+    /// Gets the average of the matrix
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::init(10f32, (2,2));
+    ///
+    /// assert_eq!(matrix.avg(), 10.0);
+    /// ```
+    pub fn avg(&self) -> T {
+        self.data.par_iter().copied().sum::<T>() / self.size().to_string().parse::<T>().unwrap()
+    }
+
+    /// Gets the mean of the matrix
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::init(10f32, (2,2));
+    ///
+    /// assert_eq!(matrix.mean(), 10.0);
+    /// ```
+    pub fn mean(&self) -> T {
+        self.avg()
+    }
+
+    /// Gets the weighted mean of the matrix, which is the sum of
+    /// every element times its weight, divided by the sum of the weights.
+    ///
+    /// Returns MatrixDimensionMismatchError if the weights do not have
+    /// the same shape, and MatrixDivideByZeroError if they sum to 0
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::new(vec![1.0, 2.0, 3.0, 4.0], (2,2)).unwrap();
+    /// let weights = Matrix::new(vec![3.0, 1.0, 0.0, 0.0], (2,2)).unwrap();
+    ///
+    /// assert_eq!(matrix.weighted_mean(&weights).unwrap(), 1.25);
+    /// ```
+    pub fn weighted_mean(&self, weights: &Self) -> Result<T, MatrixError> {
+        if !self.same_shape(weights) {
+            return Err(MatrixError::MatrixDimensionMismatchError);
+        }
+
+        let total_weight = weights.cumsum();
+
+        if total_weight == T::zero() {
+            return Err(MatrixError::MatrixDivideByZeroError);
+        }
+
+        let weighted: T = self
+            .data
+            .par_iter()
+            .zip(weights.data.par_iter())
+            .map(|(&x, &w)| x * w)
+            .sum();
+
+        Ok(weighted / total_weight)
+    }
+
+    /// Gets the median of the matrix
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::new(vec![1.0, 4.0, 6.0, 5.0], (2,2)).unwrap();
+    ///
+    /// assert!(matrix.median() >= 4.45 && matrix.median() <= 4.55);
+    /// ```
+    pub fn median(&self) -> T {
+        if self.size() == 1 {
+            return self.at(0, 0);
+        }
+
+        match self.data.len() % 2 {
+            0 => {
+                let half: usize = self.data.len() / 2;
+
+                self.data
+                    .iter()
+                    .sorted_by(|a, b| a.partial_cmp(&b).unwrap())
+                    .skip(half - 1)
+                    .take(2)
+                    .copied()
+                    .sum::<T>()
+                    / (T::one() + T::one())
+            }
+            1 => {
+                let half: usize = self.data.len() / 2;
+
+                self.data
+                    .iter()
+                    .sorted_by(|a, b| a.partial_cmp(&b).unwrap())
+                    .nth(half)
+                    .copied()
+                    .unwrap()
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Sums up elements over given axis and dimension.
+    /// Will return 0 if you're out of bounds
+    ///
+    /// sum(2, Dimension::Col) means summig up these ones
+    ///
+    /// [ 10 10 (10) 10 10
+    ///   10 10 (10) 10 10
+    ///   10 10 (10) 10 10
+    ///   10 10 (10) 10 10
+    ///   10 10 (10) 10 10 ]
+    ///
+    ///   = 10 * 5 = 50
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    /// use sukker::Dimension;
+    ///
+    /// let matrix = Matrix::init(10f32, (5,5));
+    ///
+    /// assert_eq!(matrix.sum(0, Dimension::Row), 50.0);
+    /// assert_eq!(matrix.sum(3, Dimension::Col), 50.0);
+    /// ```
+    pub fn sum(&self, rowcol: usize, dimension: Dimension) -> T {
+        // TODO: Add out of bounds options
+        if self.size() == 1 {
+            return self.at(0, 0);
+        }
+
+        match dimension {
+            Dimension::Row => self
+                .data
+                .par_iter()
+                .skip(rowcol * self.ncols)
+                .take(self.ncols)
+                .copied()
+                .sum(),
+            Dimension::Col => self
+                .data
+                .par_iter()
+                .skip(rowcol)
+                .step_by(self.ncols)
+                .copied()
+                .sum(),
+        }
+    }
+
+    /// Prods up elements over given rowcol and dimension
+    /// Will return 1 if you're out of bounds.
+    ///
+    /// See `sum` for example on how this is calculated
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    /// use sukker::Dimension;
+    ///
+    /// let matrix = Matrix::init(10f32, (2,2));
+    ///
+    /// assert_eq!(matrix.prod(0, Dimension::Row), 100.0);
+    /// assert_eq!(matrix.prod(0, Dimension::Col), 100.0);
+    /// ```
+    pub fn prod(&self, rowcol: usize, dimension: Dimension) -> T {
+        match dimension {
+            Dimension::Row => self
+                .data
+                .par_iter()
+                .skip(rowcol * self.ncols)
+                .take(self.ncols)
+                .copied()
+                .product(),
+            Dimension::Col => self
+                .data
+                .par_iter()
+                .skip(rowcol)
+                .step_by(self.ncols)
+                .copied()
+                .product(),
+        }
+    }
+
+    /// Sums up every row or column of the matrix.
+    ///
+    /// Reducing over `Dimension::Row` sums each column and gives a 1 x ncols matrix,
+    /// while reducing over `Dimension::Col` sums each row and gives a nrows x 1 matrix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::{Matrix, Dimension};
+    ///
+    /// let matrix = Matrix::new(vec![1, 2, 3, 4, 5, 6], (2,3)).unwrap();
+    ///
+    /// assert_eq!(matrix.sum_axis(Dimension::Row).get_vec(), vec![5, 7, 9]);
+    /// assert_eq!(matrix.sum_axis(Dimension::Col).get_vec(), vec![6, 15]);
+    /// ```
+    pub fn sum_axis(&self, dim: Dimension) -> Self {
+        self.reduce_axis(dim, |v| v.iter().copied().sum())
+    }
+
+    /// Multiplies together every row or column of the matrix.
+    ///
+    /// See `sum_axis` for the shape of the result
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::{Matrix, Dimension};
+    ///
+    /// let matrix = Matrix::new(vec![1, 2, 3, 4, 5, 6], (2,3)).unwrap();
+    ///
+    /// assert_eq!(matrix.prod_axis(Dimension::Row).get_vec(), vec![4, 10, 18]);
+    /// assert_eq!(matrix.prod_axis(Dimension::Col).get_vec(), vec![6, 120]);
+    /// ```
+    pub fn prod_axis(&self, dim: Dimension) -> Self {
+        self.reduce_axis(dim, |v| v.iter().copied().product())
+    }
+
+    /// Gets the mean of every row or column of the matrix.
+    ///
+    /// See `sum_axis` for the shape of the result
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::{Matrix, Dimension};
+    ///
+    /// let matrix = Matrix::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], (2,3)).unwrap();
+    ///
+    /// assert_eq!(matrix.mean_axis(Dimension::Row).get_vec(), vec![2.5, 3.5, 4.5]);
+    /// assert_eq!(matrix.mean_axis(Dimension::Col).get_vec(), vec![2.0, 5.0]);
+    /// ```
+    pub fn mean_axis(&self, dim: Dimension) -> Self {
+        self.reduce_axis(dim, |v| {
+            v.iter().copied().sum::<T>() / v.len().to_string().parse::<T>().unwrap()
+        })
+    }
+
+    /// Finds the maximum of every row or column of the matrix.
+    ///
+    /// See `sum_axis` for the shape of the result
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::{Matrix, Dimension};
+    ///
+    /// let matrix = Matrix::new(vec![1, 8, 3, 4, 5, 6], (2,3)).unwrap();
+    ///
+    /// assert_eq!(matrix.max_axis(Dimension::Row).get_vec(), vec![4, 8, 6]);
+    /// assert_eq!(matrix.max_axis(Dimension::Col).get_vec(), vec![8, 6]);
+    /// ```
+    pub fn max_axis(&self, dim: Dimension) -> Self {
+        self.reduce_axis(dim, |v| {
+            v.iter()
+                .copied()
+                .reduce(|a, b| if b > a { b } else { a })
+                .unwrap_or_else(T::zero)
+        })
+    }
+
+    /// Finds the minimum of every row or column of the matrix.
+    ///
+    /// See `sum_axis` for the shape of the result
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::{Matrix, Dimension};
+    ///
+    /// let matrix = Matrix::new(vec![1, 8, 3, 4, 5, 2], (2,3)).unwrap();
+    ///
+    /// assert_eq!(matrix.min_axis(Dimension::Row).get_vec(), vec![1, 5, 2]);
+    /// assert_eq!(matrix.min_axis(Dimension::Col).get_vec(), vec![1, 2]);
+    /// ```
+    pub fn min_axis(&self, dim: Dimension) -> Self {
+        self.reduce_axis(dim, |v| {
+            v.iter()
+                .copied()
+                .reduce(|a, b| if b < a { b } else { a })
+                .unwrap_or_else(T::zero)
+        })
+    }
+
+    /// Finds the index of the maximum of every row or column of the matrix.
+    ///
+    /// Reducing over `Dimension::Row` gives the row index of the maximum
+    /// of each column, while reducing over `Dimension::Col` gives the column
+    /// index of the maximum of each row. Ties resolve to the first index,
+    /// like `argmax`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::{Matrix, Dimension};
+    ///
+    /// let matrix = Matrix::new(vec![1, 8, 3, 4, 5, 6], (2,3)).unwrap();
+    ///
+    /// assert_eq!(matrix.argmax_axis(Dimension::Row), vec![1, 0, 1]);
+    /// assert_eq!(matrix.argmax_axis(Dimension::Col), vec![1, 2]);
+    /// ```
+    pub fn argmax_axis(&self, dim: Dimension) -> Vec<usize> {
+        self.arg_extreme_axis(dim, |elem, best| elem > best)
+    }
+
+    /// Finds the index of the minimum of every row or column of the matrix.
+    ///
+    /// See `argmax_axis` for the layout of the result
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::{Matrix, Dimension};
+    ///
+    /// let matrix = Matrix::new(vec![1, 8, 3, 4, 5, 2], (2,3)).unwrap();
+    ///
+    /// assert_eq!(matrix.argmin_axis(Dimension::Row), vec![0, 1, 1]);
+    /// assert_eq!(matrix.argmin_axis(Dimension::Col), vec![0, 2]);
+    /// ```
+    pub fn argmin_axis(&self, dim: Dimension) -> Vec<usize> {
+        self.arg_extreme_axis(dim, |elem, best| elem < best)
+    }
+
+    // Runs arg_extreme over every column (Row) or every row (Col),
+    // keeping only the index along the reduced dimension
+    fn arg_extreme_axis<F>(&self, dim: Dimension, better: F) -> Vec<usize>
+    where
+        F: Fn(T, T) -> bool + Copy,
+    {
+        match dim {
+            Dimension::Row => (0..self.ncols)
+                .filter_map(|j| self.arg_extreme(j, Dimension::Col, better))
+                .map(|(i, _)| i)
+                .collect(),
+            Dimension::Col => (0..self.nrows)
+                .filter_map(|i| self.arg_extreme(i, Dimension::Row, better))
+                .map(|(_, j)| j)
+                .collect(),
+        }
+    }
+
+    /// Calculates the 1-norm of the matrix,
+    /// which is the maximum absolute column sum.
+    ///
+    /// Only requires the elements to be signed, see
+    /// `norm_frobenius` for a norm over floats
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::new(vec![1, -2, 3, -4], (2,2)).unwrap();
+    ///
+    /// assert_eq!(matrix.norm_l1(), 6);
+    /// ```
+    pub fn norm_l1(&self) -> T {
+        if self.size() == 0 {
+            return T::zero();
+        }
+
+        self.abs().sum_axis(Dimension::Row).max()
+    }
+
+    /// Calculates the infinity-norm of the matrix,
+    /// which is the maximum absolute row sum.
+    ///
+    /// Only requires the elements to be signed, see
+    /// `norm_frobenius` for a norm over floats
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::new(vec![1, -2, 3, -4], (2,2)).unwrap();
+    ///
+    /// assert_eq!(matrix.norm_inf(), 7);
+    /// ```
+    pub fn norm_inf(&self) -> T {
+        if self.size() == 0 {
+            return T::zero();
+        }
+
+        self.abs().sum_axis(Dimension::Col).max()
+    }
+}
+
+/// Linalg on floats
+impl<'a, T> LinAlgFloats<'a, T> for Matrix<'a, T>
+where
+    T: MatrixElement + Float + 'a,
+    <T as FromStr>::Err: Error + 'static,
+    Vec<T>: IntoParallelIterator,
+    Vec<&'a T>: IntoParallelRefIterator<'a>,
+{
+    /// Takes the logarithm of each element
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::{Matrix, LinAlgFloats};
+    ///
+    /// let matrix = Matrix::init(10.0, (2,2));
+    /// let result = matrix.log(10.0);
+    ///
+    /// assert_eq!(result.all(|&e| e == 1.0), true);
+    ///
+    /// ```
+    fn log(&self, base: T) -> Self {
+        self.apply_real(RealOp::Log(base))
+    }
+
+    /// Takes the natural logarithm of each element in a matrix
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::{Matrix, LinAlgFloats};
+    /// use sukker::constants::EF64;
+    ///
+    /// let matrix: Matrix<f64> = Matrix::init(EF64, (2,2));
+    ///
+    /// let res = matrix.ln();
+    /// ```
+    fn ln(&self) -> Self {
+        self.apply_real(RealOp::Ln)
+    }
+
+    /// Takes the square root of each element in a matrix.
+    /// Like `f64::sqrt`, negative elements become NaN.
+    /// Use `try_sqrt` to get an error instead
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::{Matrix, LinAlgFloats};
+    ///
+    /// let matrix: Matrix<f64> = Matrix::init(9.0, (3,3));
+    ///
+    /// let res = matrix.sqrt();
+    ///
+    /// assert_eq!(res.all(|&e| e == 3.0), true);
+    /// ```
+    fn sqrt(&self) -> Self {
+        self.apply_real(RealOp::Sqrt)
+    }
+
+    /// Gets sin of every value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::{Matrix, LinAlgFloats};
+    ///
+    /// let matrix = Matrix::init(1.0, (2,2));
+    ///
+    /// let res = matrix.sin();
+    /// ```
+    fn sin(&self) -> Self {
+        self.apply_real(RealOp::Sin)
+    }
+
+    /// Gets cos of every value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::{Matrix, LinAlgFloats};
+    /// use sukker::constants::EF32;
+    ///
+    /// let matrix = Matrix::init(EF32, (2,2));
+    ///
+    /// let res = matrix.cos();
+    /// ```
+    fn cos(&self) -> Self {
+        self.apply_real(RealOp::Cos)
+    }
+
+    /// Gets tan of every value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::{Matrix, LinAlgFloats};
+    /// use sukker::constants::EF32;
+    ///
+    /// let matrix = Matrix::init(EF32, (2,2));
+    ///
+    /// let res = matrix.tan();
+    /// ```
+    fn tan(&self) -> Self {
+        self.apply_real(RealOp::Tan)
+    }
+
+    /// Gets sinh of every value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::{Matrix, LinAlgFloats};
+    /// use sukker::constants::EF32;
+    ///
+    /// let matrix = Matrix::init(EF32, (2,2));
+    ///
+    /// let res = matrix.sinh();
+    /// ```
+    fn sinh(&self) -> Self {
+        self.apply_real(RealOp::Sinh)
+    }
+
+    /// Gets cosh of every value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::{Matrix, LinAlgFloats};
+    /// use sukker::constants::EF32;
+    ///
+    /// let matrix = Matrix::init(EF32, (2,2));
+    ///
+    /// let res = matrix.cosh();
+    /// ```
+    fn cosh(&self) -> Self {
+        self.apply_real(RealOp::Cosh)
+    }
+
+    /// Gets tanh of every value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::{Matrix, LinAlgFloats};
+    /// use sukker::constants::EF32;
+    ///
+    /// let matrix = Matrix::init(EF32, (2,2));
+    ///
+    /// let res = matrix.tanh();
+    /// ```
+    fn tanh(&self) -> Self {
+        self.apply_real(RealOp::Tanh)
+    }
+
+    /// Find the eigenvalues of a real symmetric matrix.
+    ///
+    /// Uses QR iteration with Wilkinson shifts, deflating whenever the last
+    /// subdiagonal element of the active block drops below machine epsilon
+    /// relative to its neighbouring diagonal elements or the Frobenius norm.
+    /// Each eigenvalue gets at most 1000 iterations to converge.
+    ///
+    /// The eigenvalues are returned in the order they appear on the diagonal.
+    /// Returns None if the matrix is not square or does not converge
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::{Matrix, LinAlgFloats};
+    ///
+    /// let matrix: Matrix<f64> = Matrix::new(vec![2.0, 1.0, 1.0, 2.0], (2,2)).unwrap();
+    ///
+    /// let mut eigenvalues = matrix.get_eigenvalues().unwrap();
+    /// eigenvalues.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    ///
+    /// assert!((eigenvalues[0] - 1.0).abs() < 1e-10);
+    /// assert!((eigenvalues[1] - 3.0).abs() < 1e-10);
+    /// ```
+    fn get_eigenvalues(&self) -> Option<Vec<T>> {
+        self.qr_eigenvalues()
+    }
+
+    /// Find the eigenvectors.
+    ///
+    /// Not implemented yet, always returns None
+    fn get_eigenvectors(&self) -> Option<Vec<T>> {
+        None
+    }
+}
+
+/// Same surface as LinAlgFloats, but for any `Real` type.
+///
+/// Both implementations go through the same element-wise helper,
+/// `apply_real`, so they always agree for types that are both `Float` and `Real`.
+/// When both traits are in scope, disambiguate with fully qualified syntax
+impl<'a, T> LinAlgReals<'a, T> for Matrix<'a, T>
+where
+    T: MatrixElement + Real + 'a,
+    <T as FromStr>::Err: Error + 'static,
+    Vec<T>: IntoParallelIterator,
+    Vec<&'a T>: IntoParallelRefIterator<'a>,
+{
+    /// Takes the logarithm of each element
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::{Matrix, LinAlgReals};
+    ///
+    /// let matrix: Matrix<f64> = Matrix::init(100.0, (2,2));
+    ///
+    /// let logged = LinAlgReals::log(&matrix, 10.0);
+    /// let rooted = <Matrix<f64> as LinAlgReals<f64>>::sqrt(&matrix);
+    ///
+    /// assert_eq!(logged.all(|&e| e == 2.0), true);
+    /// assert_eq!(rooted.all(|&e| e == 10.0), true);
+    /// ```
+    fn log(&self, base: T) -> Self {
+        self.apply_real(RealOp::Log(base))
+    }
+
+    /// Takes the natural logarithm of each element in a matrix
+    fn ln(&self) -> Self {
+        self.apply_real(RealOp::Ln)
+    }
+
+    /// Takes the square root of each element in a matrix.
+    /// Like `f64::sqrt`, negative elements become NaN.
+    /// Use `try_sqrt` to get an error instead
+    fn sqrt(&self) -> Self {
+        self.apply_real(RealOp::Sqrt)
+    }
+
+    /// Gets sin of every value
+    fn sin(&self) -> Self {
+        self.apply_real(RealOp::Sin)
+    }
+
+    /// Gets cos of every value
+    fn cos(&self) -> Self {
+        self.apply_real(RealOp::Cos)
+    }
+
+    /// Gets tan of every value
+    fn tan(&self) -> Self {
+        self.apply_real(RealOp::Tan)
+    }
+
+    /// Gets sinh of every value
+    fn sinh(&self) -> Self {
+        self.apply_real(RealOp::Sinh)
+    }
+
+    /// Gets cosh of every value
+    fn cosh(&self) -> Self {
+        self.apply_real(RealOp::Cosh)
+    }
+
+    /// Gets tanh of every value
+    fn tanh(&self) -> Self {
+        self.apply_real(RealOp::Tanh)
+    }
+
+    /// Find the eigenvalues of a real symmetric matrix.
+    ///
+    /// Same QR iteration as `LinAlgFloats::get_eigenvalues`.
+    /// Returns None if the matrix is not square or does not converge
+    fn get_eigenvalues(&self) -> Option<Vec<T>> {
+        self.qr_eigenvalues()
+    }
+
+    /// Find the eigenvectors.
+    ///
+    /// Not implemented yet, always returns None
+    fn get_eigenvectors(&self) -> Option<Vec<T>> {
+        None
+    }
+}
+
+/// Linear algebra operations that only make sense on floats
+impl<'a, T> Matrix<'a, T>
+where
+    T: MatrixElement + Float + 'a,
+    <T as FromStr>::Err: Error + 'static,
+    Vec<T>: IntoParallelIterator,
+    Vec<&'a T>: IntoParallelRefIterator<'a>,
+{
+    /// Creates the n x n Hilbert matrix, where every entry is `1 / (i + j + 1)`.
+    ///
+    /// It is notoriously ill-conditioned, which makes it a good
+    /// stress test for solvers and decompositions
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::<f64>::hilbert(3);
+    ///
+    /// assert_eq!(matrix.at(0, 0), 1.0);
+    /// assert_eq!(matrix.at(1, 2), 0.25);
+    /// ```
+    pub fn hilbert(n: usize) -> Self {
+        let data: Vec<T> = iproduct!(0..n, 0..n)
+            .map(|(i, j)| T::one() / T::from(i + j + 1).unwrap())
+            .collect();
+
+        Self::new(data, (n, n)).unwrap()
+    }
+
+    /// Finds the inverse of a matrix if possible
+    ///
+    /// Definition: AA^-1 = A^-1A = I
+    ///
+    /// Uses Gauss-Jordan elimination with partial pivoting.
+    /// Returns NotSquareError if the matrix is not square,
+    /// and SingularMatrixError if it is singular
+    ///
+    /// Examples
+    ///
+    /// ```
+    /// use sukker::{Matrix, MatrixError};
+    ///
+    /// let matrix = Matrix::new(vec![2.0, 0.0, 0.0, 4.0], (2,2)).unwrap();
+    ///
+    /// let inverse = matrix.inverse().unwrap();
+    ///
+    /// assert_eq!(inverse.get_vec(), vec![0.5, 0.0, 0.0, 0.25]);
+    ///
+    /// let singular = Matrix::new(vec![1.0, 2.0, 2.0, 4.0], (2,2)).unwrap();
+    ///
+    /// assert_eq!(singular.inverse(), Err(MatrixError::SingularMatrixError));
+    /// ```
+    pub fn inverse(&self) -> Result<Self, MatrixError> {
+        if self.nrows != self.ncols {
+            return Err(MatrixError::NotSquareError);
+        }
+
+        if self.shape() != (2, 2) {
+            let n = self.nrows;
+
+            return self
+                .gauss_jordan_solve(Self::eye(n).data, n)
+                .map(|inv| Self::new(inv, (n, n)).unwrap())
+                .ok_or(MatrixError::SingularMatrixError);
+        }
+
+        let a = self.at(0, 0);
+        let b = self.at(0, 1);
+        let c = self.at(1, 0);
+        let d = self.at(1, 1);
+
+        let det = a * d - b * c;
+
+        if det == T::zero() {
+            return Err(MatrixError::SingularMatrixError);
+        }
+
+        let mut mat = Self::new(vec![d, -b, -c, a], self.shape()).unwrap();
+
+        mat.mul_val_self(T::one() / det);
+
+        Ok(mat)
+    }
+
+    /// Solves the linear system A * X = B for X, where A is square.
+    ///
+    /// B may have several columns, each of which is solved for.
+    /// Uses Gauss-Jordan elimination with partial pivoting.
+    ///
+    /// Returns NotSquareError if A is not square, MatrixDimensionMismatchError
+    /// if B does not have as many rows as A, and SingularMatrixError if A is singular
+    ///
+    /// Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let a: Matrix<f64> = Matrix::new(vec![2.0, 1.0, 1.0, 3.0], (2,2)).unwrap();
+    /// let b = Matrix::new(vec![3.0, 5.0], (2,1)).unwrap();
+    ///
+    /// let x = a.solve(&b).unwrap();
+    ///
+    /// assert!((x.at(0,0) - 0.8).abs() < 1e-12);
+    /// assert!((x.at(1,0) - 1.4).abs() < 1e-12);
+    /// ```
+    pub fn solve(&self, b: &Self) -> Result<Self, MatrixError> {
+        if self.nrows != self.ncols {
+            return Err(MatrixError::NotSquareError);
+        }
+
+        if b.nrows != self.nrows {
+            return Err(MatrixError::MatrixDimensionMismatchError);
+        }
+
+        self.gauss_jordan_solve(b.data.clone(), b.ncols)
+            .map(|x| Self::new(x, b.shape()).unwrap())
+            .ok_or(MatrixError::SingularMatrixError)
+    }
+
+    /// Finds the Moore-Penrose pseudo-inverse of a tall matrix
+    /// with full column rank, computed as (A^T * A)^-1 * A^T.
+    ///
+    /// Returns None if the matrix has more columns than rows,
+    /// or if A^T * A is singular
+    ///
+    /// Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix: Matrix<f64> = Matrix::new(vec![1.0, 0.0, 0.0, 2.0, 0.0, 0.0], (3,2)).unwrap();
+    ///
+    /// let pinv = matrix.pinv().unwrap();
+    ///
+    /// assert_eq!(pinv.shape(), (2,3));
+    /// assert_eq!(pinv.get_vec(), vec![1.0, 0.0, 0.0, 0.0, 0.5, 0.0]);
+    /// ```
+    pub fn pinv(&self) -> Option<Self>
+    where
+        T: 'static,
+    {
+        if self.nrows < self.ncols {
+            return None;
+        }
+
+        self.gram()
+            .inverse()
+            .ok()?
+            .matmul(&self.transpose_copy())
+            .ok()
+    }
+
+    /// Solves the least-squares problem A * x ≈ b for a tall matrix A.
+    ///
+    /// Uses the QR decomposition of A rather than the normal equations,
+    /// which avoids squaring the condition number. b may have several
+    /// columns, each of which is solved for independently.
+    ///
+    /// Returns MatrixDimensionMismatchError if b does not have as many rows
+    /// as A, or if A has more columns than rows. Returns SingularMatrixError
+    /// if A does not have full column rank
+    ///
+    /// Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let a: Matrix<f64> = Matrix::new(vec![1.0, 0.0, 1.0, 1.0, 1.0, 2.0], (3,2)).unwrap();
+    /// let b: Matrix<f64> = Matrix::new(vec![1.0, 3.0, 5.0], (3,1)).unwrap();
+    ///
+    /// let x = a.lstsq(&b).unwrap();
+    ///
+    /// assert!((x.at(0,0) - 1.0).abs() < 1e-12);
+    /// assert!((x.at(1,0) - 2.0).abs() < 1e-12);
+    /// ```
+    pub fn lstsq(&self, b: &Self) -> Result<Self, MatrixError> {
+        let (m, n) = self.shape();
+
+        if b.nrows != m || m < n {
+            return Err(MatrixError::MatrixDimensionMismatchError);
+        }
+
+        let k = b.ncols;
+        let (q, r) = self.householder_qr();
+
+        let max_diag = (0..n)
+            .map(|i| r.data[at!(i, i, n)].abs())
+            .fold(T::zero(), |acc, e| acc.max(e));
+
+        let tol = max_diag * T::epsilon() * T::from(m).unwrap();
+
+        if (0..n).any(|i| r.data[at!(i, i, n)].abs() <= tol) {
+            return Err(MatrixError::SingularMatrixError);
+        }
+
+        // Only the first n rows of Q^T * b are needed
+        let mut x = Self::zeros((n, k));
+
+        for (i, col) in iproduct!(0..n, 0..k) {
+            x.data[at!(i, col, k)] = (0..m)
+                .map(|row| q.data[at!(row, i, m)] * b.data[at!(row, col, k)])
+                .sum();
+        }
+
+        // Back substitution with the upper triangular R
+        for col in 0..k {
+            for i in (0..n).rev() {
+                let sum = (i + 1..n)
+                    .map(|j| r.data[at!(i, j, n)] * x.data[at!(j, col, k)])
+                    .sum::<T>();
+
+                x.data[at!(i, col, k)] = (x.data[at!(i, col, k)] - sum) / r.data[at!(i, i, n)];
+            }
+        }
+
+        Ok(x)
+    }
+
+    /// Calculates the Frobenius norm of the matrix,
+    /// which is the square root of the sum of all squared elements.
+    ///
+    /// Requires floats because of the square root, unlike
+    /// `norm_l1` and `norm_inf` which only need signed elements
+    ///
+    /// Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::new(vec![3.0, 0.0, 0.0, -4.0], (2,2)).unwrap();
+    ///
+    /// assert_eq!(matrix.norm_frobenius(), 5.0);
+    /// ```
+    pub fn norm_frobenius(&self) -> T {
+        self.data
+            .par_iter()
+            .map(|&e| e * e)
+            .sum::<T>()
+            .sqrt()
+    }
+
+    /// Raises every element to a floating point power.
+    ///
+    /// For integer powers on any element type, see `pow`
+    ///
+    /// Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::init(4.0, (2,2));
+    ///
+    /// assert_eq!(matrix.powf(1.5).get_vec(), vec![8.0, 8.0, 8.0, 8.0]);
+    /// ```
+    pub fn powf(&self, exp: T) -> Self {
+        self.map_elements(|e| e.powf(exp))
+    }
+
+    /// Rank of the matrix, found by counting the pivots left after
+    /// Gaussian elimination with partial pivoting.
+    ///
+    /// Pivots with an absolute value at or below `epsilon` count as zero
+    ///
+    /// Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::new(vec![1.0, 2.0, 2.0, 4.0], (2,2)).unwrap();
+    ///
+    /// assert_eq!(matrix.rank(1e-10), 1);
+    /// assert_eq!(Matrix::<f64>::eye(3).rank(1e-10), 3);
+    /// ```
+    pub fn rank(&self, epsilon: T) -> usize {
+        let (m, n) = self.shape();
+        let mut data = self.data.clone();
+
+        let mut rank = 0;
+
+        for col in 0..n {
+            if rank == m {
+                break;
+            }
+
+            let pivot_row = (rank..m)
+                .max_by(|&a, &b| {
+                    data[at!(a, col, n)]
+                        .abs()
+                        .partial_cmp(&data[at!(b, col, n)].abs())
+                        .unwrap()
+                })
+                .unwrap();
+
+            if data[at!(pivot_row, col, n)].abs() <= epsilon {
+                continue;
+            }
+
+            swap_rows(&mut data, n, pivot_row, rank);
+
+            let pivot = data[at!(rank, col, n)];
+
+            for row in rank + 1..m {
+                let factor = data[at!(row, col, n)] / pivot;
+
+                for j in col..n {
+                    let val = data[at!(rank, j, n)];
+                    data[at!(row, j, n)] -= factor * val;
+                }
+            }
+
+            rank += 1;
+        }
+
+        rank
+    }
+
+    /// Population variance of all elements in the matrix.
+    ///
+    /// A matrix with a single element has variance 0
+    ///
+    /// Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::new(vec![1.0, 2.0, 3.0, 4.0], (2,2)).unwrap();
+    ///
+    /// assert_eq!(matrix.variance(), 1.25);
+    /// ```
+    pub fn variance(&self) -> T {
+        self.variance_ddof(0)
+    }
+
+    /// Variance of all elements with `ddof` delta degrees of freedom,
+    /// meaning the sum of squared deviations is divided by N - ddof.
+    ///
+    /// ddof = 0 gives the population variance and ddof = 1 the sample variance.
+    /// Returns NaN if N <= ddof
+    ///
+    /// Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::new(vec![1.0, 2.0, 3.0, 4.0, 5.0], (1,5)).unwrap();
+    ///
+    /// assert_eq!(matrix.variance_ddof(1), 2.5);
+    /// ```
+    pub fn variance_ddof(&self, ddof: usize) -> T {
+        variance_of(&self.data, ddof)
+    }
+
+    /// Population standard deviation of all elements in the matrix
+    ///
+    /// Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::new(vec![2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0], (2,4)).unwrap();
+    ///
+    /// assert_eq!(matrix.std_dev(), 2.0);
+    /// ```
+    pub fn std_dev(&self) -> T {
+        self.variance().sqrt()
+    }
+
+    /// Population variance of every row or column of the matrix.
+    ///
+    /// See `sum_axis` for the shape of the result
+    ///
+    /// Examples
+    ///
+    /// ```
+    /// use sukker::{Matrix, Dimension};
+    ///
+    /// let matrix = Matrix::new(vec![1.0, 2.0, 3.0, 3.0, 6.0, 9.0], (2,3)).unwrap();
+    ///
+    /// assert_eq!(matrix.var_axis(Dimension::Row).get_vec(), vec![1.0, 4.0, 9.0]);
+    /// assert_eq!(matrix.var_axis(Dimension::Col).get_vec(), vec![2.0 / 3.0, 6.0]);
+    /// ```
+    pub fn var_axis(&self, dim: Dimension) -> Self {
+        self.reduce_axis(dim, |v| variance_of(v, 0))
+    }
+
+    /// Softmax along an axis, using the same convention as `sum_axis`.
+    ///
+    /// With `Dimension::Col` every row sums to 1,
+    /// with `Dimension::Row` every column sums to 1.
+    /// The max of each row or column is subtracted before exponentiating,
+    /// so large values do not overflow
+    ///
+    /// Examples
+    ///
+    /// ```
+    /// use sukker::{Matrix, Dimension};
+    ///
+    /// let matrix = Matrix::new(vec![0.0, 0.0, 1000.0, 1000.0], (2,2)).unwrap();
+    ///
+    /// let res = matrix.softmax_axis(Dimension::Col);
+    ///
+    /// assert_eq!(res.get_vec(), vec![0.5, 0.5, 0.5, 0.5]);
+    /// ```
+    pub fn softmax_axis(&self, dim: Dimension) -> Self {
+        // Dimension is not Copy, and is needed again for normalizing
+        let maxes = match dim {
+            Dimension::Row => self.reduce_axis(Dimension::Row, max_of),
+            Dimension::Col => self.reduce_axis(Dimension::Col, max_of),
+        };
+
+        self.broadcast_op(&maxes, |x, m| (x - m).exp())
+            .unwrap()
+            .normalize_axis(dim)
+    }
+
+    /// Divides every element by the sum of its row or column,
+    /// using the same convention as `sum_axis`.
+    ///
+    /// With `Dimension::Col` every row sums to 1,
+    /// with `Dimension::Row` every column sums to 1.
+    /// A row or column summing to zero becomes all zeros
+    ///
+    /// Examples
+    ///
+    /// ```
+    /// use sukker::{Matrix, Dimension};
+    ///
+    /// let matrix = Matrix::new(vec![1.0, 3.0, 0.0, 0.0], (2,2)).unwrap();
+    ///
+    /// let res = matrix.normalize_axis(Dimension::Col);
+    ///
+    /// assert_eq!(res.get_vec(), vec![0.25, 0.75, 0.0, 0.0]);
+    /// ```
+    pub fn normalize_axis(&self, dim: Dimension) -> Self {
+        let sums = self.sum_axis(dim);
+
+        self.broadcast_op(&sums, |x, s| if s == T::zero() { T::zero() } else { x / s })
+            .unwrap()
+    }
+
+    /// Counts the values of the matrix in `bins` equally wide bins,
+    /// returning the counts and the `bins + 1` bin edges.
+    ///
+    /// Every bin includes its lower edge, and the last bin also includes
+    /// the upper edge. If range is None, the smallest and largest values
+    /// are used, widened by 0.5 on both sides if they are equal.
+    /// Values outside of the range and NaNs are not counted
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::new(vec![0.0, 1.0, 1.5, 4.0], (2,2)).unwrap();
+    ///
+    /// let (counts, edges) = matrix.histogram(2, None);
+    ///
+    /// assert_eq!(counts, vec![3, 1]);
+    /// assert_eq!(edges, vec![0.0, 2.0, 4.0]);
+    /// ```
+    pub fn histogram(&self, bins: usize, range: Option<(T, T)>) -> (Vec<usize>, Vec<T>) {
+        if bins == 0 {
+            return (Vec::new(), Vec::new());
+        }
+
+        let (lo, hi) = match range {
+            Some(range) => range,
+            None => {
+                // Float::min and max skip NaNs
+                let lo = self.data.iter().fold(T::infinity(), |acc, &x| acc.min(x));
+                let hi = self
+                    .data
+                    .iter()
+                    .fold(T::neg_infinity(), |acc, &x| acc.max(x));
+
+                if lo > hi {
+                    (T::zero(), T::one())
+                } else if lo == hi {
+                    let half = T::from(0.5).unwrap();
+                    (lo - half, hi + half)
+                } else {
+                    (lo, hi)
+                }
+            }
+        };
+
+        let nbins = T::from(bins).unwrap();
+        let width = (hi - lo) / nbins;
+
+        let edges: Vec<T> = (0..=bins)
+            .map(|k| lo + width * T::from(k).unwrap())
+            .collect();
+
+        let mut counts = vec![0; bins];
+
+        for &x in self.data.iter() {
+            if !(x >= lo && x <= hi) {
+                continue;
+            }
+
+            let bin = ((x - lo) / width).floor().to_usize().unwrap_or(bins);
+
+            counts[bin.min(bins - 1)] += 1;
+        }
+
+        (counts, edges)
+    }
+
+    /// Takes the square root of each element in a matrix,
+    /// returning a DomainError if any element is negative
+    ///
+    /// Examples
+    ///
+    /// ```
+    /// use sukker::{Matrix, MatrixError};
+    ///
+    /// let matrix: Matrix<f64> = Matrix::new(vec![4.0, 9.0, 0.0, 1.0], (2,2)).unwrap();
+    ///
+    /// assert_eq!(matrix.try_sqrt().unwrap().get_vec(), vec![2.0, 3.0, 0.0, 1.0]);
+    ///
+    /// let matrix: Matrix<f64> = Matrix::new(vec![4.0, -9.0], (1,2)).unwrap();
+    ///
+    /// assert!(matches!(matrix.try_sqrt(), Err(MatrixError::DomainError(_))));
+    /// ```
+    pub fn try_sqrt(&self) -> Result<Self, MatrixError> {
+        if self.data.par_iter().any(|&e| e < T::zero()) {
+            return Err(MatrixError::DomainError("square root of a negative number"));
+        }
+
+        Ok(self.map_elements(|e| e.sqrt()))
+    }
+
+    /// Applies the rectified linear unit max(0, x) element-wise
+    ///
+    /// Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::new(vec![-2.0, -0.5, 0.0, 3.0], (2,2)).unwrap();
+    ///
+    /// assert_eq!(matrix.relu().get_vec(), vec![0.0, 0.0, 0.0, 3.0]);
+    /// ```
+    pub fn relu(&self) -> Self {
+        self.map_elements(|e| e.max(T::zero()))
+    }
+
+    /// Applies leaky ReLU element-wise, where negative values
+    /// are multiplied by alpha instead of being set to 0
+    ///
+    /// Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::new(vec![-2.0, -0.5, 0.0, 3.0], (2,2)).unwrap();
+    ///
+    /// assert_eq!(matrix.leaky_relu(0.1).get_vec(), vec![-0.2, -0.05, 0.0, 3.0]);
+    /// ```
+    pub fn leaky_relu(&self, alpha: T) -> Self {
+        self.map_elements(|e| if e < T::zero() { alpha * e } else { e })
+    }
+
+    /// Applies the logistic sigmoid 1 / (1 + e^-x) element-wise.
+    ///
+    /// Negative values are computed as e^x / (1 + e^x),
+    /// which avoids overflow for large magnitudes
+    ///
+    /// Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix: Matrix<f64> = Matrix::zeros((2,2));
+    ///
+    /// assert_eq!(matrix.sigmoid().get_vec(), vec![0.5; 4]);
+    /// ```
+    pub fn sigmoid(&self) -> Self {
+        self.map_elements(|e| {
+            if e >= T::zero() {
+                T::one() / (T::one() + (-e).exp())
+            } else {
+                let exp = e.exp();
+                exp / (T::one() + exp)
+            }
+        })
+    }
+
+    /// LU decomposition with partial pivoting.
+    ///
+    /// Returns (P, L, U) such that PA = LU, where P is a permutation matrix,
+    /// L is lower triangular with ones on the diagonal and U is upper triangular.
+    ///
+    /// Returns None if the matrix is not square
+    ///
+    /// Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::new(vec![1.0, 2.0, 4.0, 4.0], (2,2)).unwrap();
+    ///
+    /// let (p, l, u) = matrix.lu().unwrap();
+    ///
+    /// assert_eq!(p.get_vec(), vec![0.0, 1.0, 1.0, 0.0]);
+    /// assert_eq!(l.get_vec(), vec![1.0, 0.0, 0.25, 1.0]);
+    /// assert_eq!(u.get_vec(), vec![4.0, 4.0, 0.0, 1.0]);
+    /// ```
+    pub fn lu(&self) -> Option<(Self, Self, Self)> {
+        if self.nrows != self.ncols {
+            return None;
+        }
+
+        let n = self.nrows;
+        let (lu, perm, _) = self.lu_helper();
+
+        let mut p = Self::zeros((n, n));
+        let mut l = Self::eye(n);
+        let mut u = Self::zeros((n, n));
+
+        for i in 0..n {
+            p.data[at!(i, perm[i], n)] = T::one();
+
+            for j in 0..n {
+                if j < i {
+                    l.data[at!(i, j, n)] = lu[at!(i, j, n)];
+                } else {
+                    u.data[at!(i, j, n)] = lu[at!(i, j, n)];
+                }
+            }
+        }
+
+        Some((p, l, u))
+    }
+
+    /// QR decomposition using Householder reflections.
+    ///
+    /// Returns (Q, R) such that A = QR, where Q is an M x M orthogonal matrix
+    /// and R is an M x N upper triangular matrix.
+    ///
+    /// Returns None if the matrix has more columns than rows
+    ///
+    /// Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix: Matrix<f64> = Matrix::new(vec![3.0, 1.0, 4.0, 2.0, 0.0, 5.0], (3,2)).unwrap();
+    ///
+    /// let (q, r) = matrix.qr().unwrap();
+    ///
+    /// assert_eq!(q.shape(), (3,3));
+    /// assert_eq!(r.shape(), (3,2));
+    /// assert_eq!(r.at(1,0), 0.0);
+    /// assert!((r.at(0,0).abs() - 5.0).abs() < 1e-12);
+    /// ```
+    pub fn qr(&self) -> Option<(Self, Self)> {
+        if self.nrows < self.ncols {
+            return None;
+        }
+
+        Some(self.householder_qr())
+    }
+
+    /// Cholesky decomposition of a symmetric positive-definite matrix.
+    ///
+    /// Returns the lower triangular L such that A = L * L^T.
+    ///
+    /// Returns NotSquareError if the matrix is not square, and a DomainError
+    /// if it is not symmetric up to rounding, or not positive-definite. The
+    /// latter shows up as a diagonal element of L needing the square root
+    /// of a number that is not positive
+    ///
+    /// Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::new(vec![4.0, 2.0, 2.0, 5.0], (2,2)).unwrap();
+    ///
+    /// let l = matrix.cholesky().unwrap();
+    ///
+    /// assert_eq!(l.get_vec(), vec![2.0, 0.0, 1.0, 2.0]);
+    /// ```
+    pub fn cholesky(&self) -> Result<Self, MatrixError> {
+        if self.nrows != self.ncols {
+            return Err(MatrixError::NotSquareError);
+        }
+
+        let n = self.nrows;
+
+        // Only the lower triangle is read below, so an asymmetric
+        // matrix would otherwise give a factor of some other matrix
+        if !self.is_symmetric(Self::pivot_tolerance(&self.data, n)) {
+            return Err(MatrixError::DomainError("matrix is not symmetric"));
+        }
+
+        let mut l = Self::zeros((n, n));
+
+        for i in 0..n {
+            for j in 0..=i {
+                let sum = (0..j)
+                    .map(|k| l.data[at!(i, k, n)] * l.data[at!(j, k, n)])
+                    .sum::<T>();
+
+                let val = self.data[at!(i, j, n)] - sum;
+
+                l.data[at!(i, j, n)] = if i == j {
+                    if val <= T::zero() {
+                        return Err(MatrixError::DomainError("matrix is not positive-definite"));
+                    }
+                    val.sqrt()
+                } else {
+                    val / l.data[at!(j, j, n)]
+                };
+            }
+        }
+
+        Ok(l)
+    }
+
+    /// Checks if a matrix is symmetric, meaning A = A^T
+    /// with every pair of elements within epsilon of each other.
+    ///
+    /// Non-square matrices are never symmetric
+    ///
+    /// Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix: Matrix<f64> = Matrix::new(vec![1.0, 2.0, 2.0, 3.0], (2,2)).unwrap();
+    ///
+    /// assert!(matrix.is_symmetric(1e-12));
+    /// ```
+    pub fn is_symmetric(&self, epsilon: T) -> bool {
+        if self.nrows != self.ncols {
+            return false;
+        }
+
+        let n = self.nrows;
+
+        (0..n).all(|i| {
+            (i + 1..n).all(|j| (self.data[at!(i, j, n)] - self.data[at!(j, i, n)]).abs() <= epsilon)
+        })
+    }
+
+    /// Checks if every element off the main diagonal is within epsilon of 0.
+    ///
+    /// Works on non-square matrices as well
+    ///
+    /// Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix: Matrix<f64> = Matrix::from_diagonal(&[1.0, 2.0, 3.0]);
+    ///
+    /// assert!(matrix.is_diagonal(1e-12));
+    /// ```
+    pub fn is_diagonal(&self, epsilon: T) -> bool {
+        iproduct!(0..self.nrows, 0..self.ncols)
+            .filter(|(i, j)| i != j)
+            .all(|(i, j)| self.data[at!(i, j, self.ncols)].abs() <= epsilon)
+    }
+
+    /// Checks if a matrix is orthogonal, meaning A^T * A is the identity
+    /// with every element within epsilon.
+    ///
+    /// Non-square matrices are never orthogonal
+    ///
+    /// Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let (s, c) = 0.5f64.sin_cos();
+    /// let rotation = Matrix::new(vec![c, -s, s, c], (2,2)).unwrap();
+    ///
+    /// assert!(rotation.is_orthogonal(1e-12));
+    /// ```
+    pub fn is_orthogonal(&self, epsilon: T) -> bool
+    where
+        T: 'static,
+    {
+        if self.nrows != self.ncols {
+            return false;
+        }
+
+        let n = self.nrows;
+        let gram = self.gram();
+
+        iproduct!(0..n, 0..n).all(|(i, j)| {
+            let expected = if i == j { T::one() } else { T::zero() };
+            (gram.data[at!(i, j, n)] - expected).abs() <= epsilon
+        })
+    }
+}
+
+/// trait MatrixLinAlg contains all common Linear Algebra functions to be
+/// performed on matrices
+impl<'a, T> Matrix<'a, T>
+where
+    T: MatrixElement,
+    <T as FromStr>::Err: Error + 'static,
+    Vec<T>: IntoParallelIterator,
+    Vec<&'a T>: IntoParallelRefIterator<'a>,
+{
+    /// Adds one matrix to another
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix1 = Matrix::init(10.0, (2,2));
+    /// let matrix2 = Matrix::init(10.0, (2,2));
+    ///
+    /// assert_eq!(matrix1.add(&matrix2).unwrap().get(0,0).unwrap(), 20.0);
+    /// ```
+    pub fn add(&self, other: &Self) -> Result<Self, MatrixError> {
+        if self.shape() != other.shape() {
+            return Err(MatrixError::MatrixDimensionMismatchError.into());
+        }
+
+        let data: Vec<T> = self
+            .data
+            .iter()
+            .zip(other.data.iter())
+            .map(|(&x, &y)| x + y)
+            .collect();
+
+        Ok(Self::new(data, self.shape()).unwrap())
+    }
+
+    /// Subtracts one matrix from another
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix1 = Matrix::init(10.0, (2,2));
+    /// let matrix2 = Matrix::init(10.0, (2,2));
+    ///
+    /// assert_eq!(matrix1.sub(&matrix2).unwrap().get(1,0).unwrap(), 0.0);
+    /// ```
+    pub fn sub(&self, other: &Self) -> Result<Self, MatrixError> {
+        if self.shape() != other.shape() {
+            return Err(MatrixError::MatrixDimensionMismatchError.into());
+        }
+
+        let data: Vec<T> = self
+            .data
+            .iter()
+            .zip(other.data.iter())
+            .map(|(&x, &y)| x - y)
+            .collect();
+
+        Ok(Self::new(data, self.shape()).unwrap())
+    }
+
+    /// Subtracts one array from another and returns the absolute value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix1 = Matrix::init(10.0f32, (2,2));
+    /// let matrix2 = Matrix::init(15.0f32, (2,2));
+    ///
+    /// assert_eq!(matrix1.sub_abs(&matrix2).unwrap().get(0,0).unwrap(), 5.0);
+    /// ```
+    pub fn sub_abs(&self, other: &Self) -> Result<Self, MatrixError> {
+        if self.shape() != other.shape() {
+            return Err(MatrixError::MatrixDimensionMismatchError.into());
+        }
+
+        let data = self
+            .data
+            .iter()
+            .zip(other.data.iter())
+            .map(|(&x, &y)| if x > y { x - y } else { y - x })
+            .collect_vec();
+
+        Ok(Self::new(data, self.shape()).unwrap())
+    }
+
+    /// Element-wise multiplication of two matrices of the same shape.
+    ///
+    /// This is not matrix multiplication, for that use `matmul`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix1 = Matrix::init(20.0, (2,2));
+    /// let matrix2 = Matrix::init(10.0, (2,2));
+    ///
+    /// assert_eq!(matrix1.mul(&matrix2).unwrap().get(0,0).unwrap(), 200.0);
+    /// ```
+    pub fn mul(&self, other: &Self) -> Result<Self, MatrixError> {
+        if self.shape() != other.shape() {
+            return Err(MatrixError::MatrixDimensionMismatchError.into());
+        }
+
+        let data = self
+            .data
+            .iter()
+            .zip(other.data.iter())
+            .map(|(&x, &y)| x * y)
+            .collect_vec();
+
+        Ok(Self::new(data, self.shape()).unwrap())
+    }
+
+    /// Hadamard product, meaning element-wise multiplication
+    /// of two matrices of the same shape.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix1: Matrix<i32> = Matrix::new(vec![1, 2, 3, 4], (2,2)).unwrap();
+    /// let matrix2: Matrix<i32> = Matrix::new(vec![5, 6, 7, 8], (2,2)).unwrap();
+    ///
+    /// assert_eq!(matrix1.hadamard(&matrix2).unwrap().get_vec(), vec![5, 12, 21, 32]);
+    /// ```
+    pub fn hadamard(&self, other: &Self) -> Result<Self, MatrixError> {
+        self.mul(other)
+    }
+
+    /// Applies a closure element-wise across two matrices of the same shape,
+    /// in parallel. Element (i, j) of the result is f(self(i, j), other(i, j))
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix1: Matrix<i32> = Matrix::new(vec![1, 6, 3, 8], (2,2)).unwrap();
+    /// let matrix2: Matrix<i32> = Matrix::new(vec![5, 2, 7, 4], (2,2)).unwrap();
+    ///
+    /// let res = matrix1.zip_map(&matrix2, |a, b| a.max(b)).unwrap();
+    ///
+    /// assert_eq!(res.get_vec(), vec![5, 6, 7, 8]);
+    /// ```
+    pub fn zip_map<F>(&self, other: &Self, f: F) -> Result<Self, MatrixError>
+    where
+        F: Fn(T, T) -> T + Sync,
+    {
+        if !self.same_shape(other) {
+            return Err(MatrixError::MatrixDimensionMismatchError);
+        }
+
+        let data: Vec<T> = self
+            .data
+            .par_iter()
+            .zip(other.data.par_iter())
+            .map(|(&x, &y)| f(x, y))
+            .collect();
+
+        Self::new(data, self.shape())
+    }
+
+    /// Applies a closure to every window sized patch of the matrix,
+    /// sliding one element at a time, in parallel.
+    ///
+    /// The patch is passed in row-major order. Only patches fully inside
+    /// the matrix are used, so the result has shape
+    /// `(nrows - window.0 + 1, ncols - window.1 + 1)`.
+    /// If the window is empty or larger than the matrix,
+    /// the result is an empty 0 x 0 matrix
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::new(vec![1, 5, 2, 4, 3, 6], (2,3)).unwrap();
+    ///
+    /// let res = matrix.window_map((2,2), |patch| patch.iter().copied().sum());
+    ///
+    /// assert_eq!(res.shape(), (1,2));
+    /// assert_eq!(res.get_vec(), vec![13, 16]);
+    /// ```
+    pub fn window_map<F>(&self, window: Shape, f: F) -> Self
+    where
+        F: Fn(&[T]) -> T + Sync,
+    {
+        let (wr, wc) = window;
+
+        if wr == 0 || wc == 0 || wr > self.nrows || wc > self.ncols {
+            return Self::new(Vec::new(), (0, 0)).unwrap();
+        }
+
+        let out_rows = self.nrows - wr + 1;
+        let out_cols = self.ncols - wc + 1;
+
+        let data: Vec<T> = (0..out_rows * out_cols)
+            .into_par_iter()
+            .map(|idx| {
+                let (i, j) = (idx / out_cols, idx % out_cols);
+
+                let patch: Vec<T> = (i..i + wr)
+                    .flat_map(|r| {
+                        self.data[at!(r, j, self.ncols)..at!(r, j + wc, self.ncols)]
+                            .iter()
+                            .copied()
+                    })
+                    .collect();
+
+                f(&patch)
+            })
+            .collect();
+
+        Self::new(data, (out_rows, out_cols)).unwrap()
+    }
+
+    /// 2D convolution of the matrix with a kernel, with zero padding
+    /// outside of the matrix.
+    ///
+    /// This is a true convolution, so the kernel is flipped in both
+    /// dimensions. For cross-correlation, flip the kernel first with
+    /// `rot90(2)`. For an m x n matrix and a p x q kernel the mode gives
+    /// the shape of the result:
+    ///
+    /// - `Full`: (m + p - 1) x (n + q - 1)
+    /// - `Same`: m x n
+    /// - `Valid`: (m - p + 1) x (n - q + 1)
+    ///
+    /// If the matrix or the kernel is empty, or the kernel is larger
+    /// than the matrix in `Valid` mode, the result is an empty 0 x 0 matrix
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::{ConvMode, Matrix};
+    ///
+    /// let matrix = Matrix::new(vec![1, 2, 3, 4], (2,2)).unwrap();
+    /// let kernel = Matrix::new(vec![1, -1], (1,2)).unwrap();
+    ///
+    /// let res = matrix.convolve2d(&kernel, ConvMode::Full);
+    ///
+    /// assert_eq!(res.shape(), (2,3));
+    /// assert_eq!(res.get_vec(), vec![1, 1, -2, 3, 1, -4]);
+    /// assert_eq!(matrix.convolve2d(&kernel, ConvMode::Valid).get_vec(), vec![1, 1]);
+    /// ```
+    pub fn convolve2d(&self, kernel: &Self, mode: ConvMode) -> Self {
+        let (m, n) = self.shape();
+        let (p, q) = kernel.shape();
+
+        if m == 0 || n == 0 || p == 0 || q == 0 || (mode == ConvMode::Valid && (p > m || q > n)) {
+            return Self::new(Vec::new(), (0, 0)).unwrap();
+        }
+
+        // Offset into, and shape of, the full convolution
+        let ((row_off, col_off), shape) = match mode {
+            ConvMode::Full => ((0, 0), (m + p - 1, n + q - 1)),
+            ConvMode::Same => (((p - 1) / 2, (q - 1) / 2), (m, n)),
+            ConvMode::Valid => ((p - 1, q - 1), (m - p + 1, n - q + 1)),
+        };
+
+        let data: Vec<T> = (0..shape.0 * shape.1)
+            .into_par_iter()
+            .map(|idx| {
+                let fi = idx / shape.1 + row_off;
+                let fj = idx % shape.1 + col_off;
+
+                // Kernel rows and cols that land inside the matrix
+                let a_range = fi.saturating_sub(m - 1)..p.min(fi + 1);
+                let b_range = fj.saturating_sub(n - 1)..q.min(fj + 1);
+
+                iproduct!(a_range, b_range).fold(T::zero(), |acc, (a, b)| {
+                    acc + self.data[at!(fi - a, fj - b, n)] * kernel.data[at!(a, b, q)]
+                })
+            })
+            .collect();
+
+        Self::new(data, shape).unwrap()
+    }
+
+    /// Element-wise maximum of two matrices of the same shape.
+    ///
+    /// Like numpy's `maximum`, a NaN in either matrix propagates to the result
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix1: Matrix<i32> = Matrix::new(vec![1, -6, 3, 8], (2,2)).unwrap();
+    /// let matrix2: Matrix<i32> = Matrix::new(vec![5, -2, -7, 4], (2,2)).unwrap();
+    ///
+    /// assert_eq!(matrix1.maximum(&matrix2).unwrap().get_vec(), vec![5, -2, 3, 8]);
+    /// ```
+    pub fn maximum(&self, other: &Self) -> Result<Self, MatrixError> {
+        self.zip_map(other, |x, y| match x.partial_cmp(&y) {
+            Some(std::cmp::Ordering::Less) => y,
+            Some(_) => x,
+            None if x.partial_cmp(&x).is_none() => x,
+            None => y,
+        })
+    }
+
+    /// Element-wise minimum of two matrices of the same shape.
+    ///
+    /// Like numpy's `minimum`, a NaN in either matrix propagates to the result
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix1: Matrix<i32> = Matrix::new(vec![1, -6, 3, 8], (2,2)).unwrap();
+    /// let matrix2: Matrix<i32> = Matrix::new(vec![5, -2, -7, 4], (2,2)).unwrap();
+    ///
+    /// assert_eq!(matrix1.minimum(&matrix2).unwrap().get_vec(), vec![1, -6, -7, 4]);
+    /// ```
+    pub fn minimum(&self, other: &Self) -> Result<Self, MatrixError> {
+        self.zip_map(other, |x, y| match x.partial_cmp(&y) {
+            Some(std::cmp::Ordering::Greater) => y,
+            Some(_) => x,
+            None if x.partial_cmp(&x).is_none() => x,
+            None => y,
+        })
+    }
+
+    /// Element-wise multiplication of two matrices.
     ///
-    /// sum(2, Dimension::Col) means summig up these ones
+    /// Despite the name, this is not a dot product.
+    /// Use `hadamard` for element-wise multiplication, `matmul` for
+    /// matrix multiplication or `vdot` for the dot product of vectors
     ///
-    /// [ 10 10 (10) 10 10
-    ///   10 10 (10) 10 10
-    ///   10 10 (10) 10 10
-    ///   10 10 (10) 10 10
-    ///   10 10 (10) 10 10 ]
+    /// # Examples
     ///
-    ///   = 10 * 5 = 50
+    /// ```
+    /// #![allow(deprecated)]
+    /// use sukker::Matrix;
+    ///
+    /// let matrix1 = Matrix::init(20.0, (2,2));
+    /// let matrix2 = Matrix::init(10.0, (2,2));
+    ///
+    /// assert_eq!(matrix1.dot(&matrix2).unwrap().get(0,0).unwrap(), 200.0);
+    /// ```
+    #[deprecated(
+        since = "1.1.3",
+        note = "dot is element-wise, use `hadamard`, or `matmul` for matrix multiplication"
+    )]
+    pub fn dot(&self, other: &Self) -> Result<Self, MatrixError> {
+        self.hadamard(other)
+    }
+
+    /// Bad handling of zero div
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
-    /// use sukker::Dimension;
     ///
-    /// let matrix = Matrix::init(10f32, (5,5));
+    /// let matrix1 = Matrix::init(20.0, (2,2));
+    /// let matrix2 = Matrix::init(10.0, (2,2));
     ///
-    /// assert_eq!(matrix.sum(0, Dimension::Row), 50.0);
-    /// assert_eq!(matrix.sum(3, Dimension::Col), 50.0);
+    /// assert_eq!(matrix1.div(&matrix2).unwrap().get(0,0).unwrap(), 2.0);
     /// ```
-    pub fn sum(&self, rowcol: usize, dimension: Dimension) -> T {
-        // TODO: Add out of bounds options
-        if self.size() == 1 {
-            return self.at(0, 0);
+    pub fn div(&self, other: &Self) -> Result<Self, MatrixError> {
+        if self.shape() != other.shape() {
+            return Err(MatrixError::MatrixDimensionMismatchError.into());
         }
 
-        match dimension {
-            Dimension::Row => self
-                .data
-                .par_iter()
-                .skip(rowcol * self.ncols)
-                .take(self.ncols)
-                .copied()
-                .sum(),
-            Dimension::Col => self
-                .data
-                .par_iter()
-                .skip(rowcol)
-                .step_by(self.ncols)
-                .copied()
-                .sum(),
+        if other.any(|e| e == &T::zero()) {
+            return Err(MatrixError::MatrixDivideByZeroError.into());
         }
+
+        let data = self
+            .data
+            .iter()
+            .zip(other.data.iter())
+            .map(|(&x, &y)| x / y)
+            .collect_vec();
+
+        Ok(Self::new(data, self.shape()).unwrap())
     }
 
-    /// Prods up elements over given rowcol and dimension
-    /// Will return 1 if you're out of bounds.
+    /// Divides two matrices element-wise without failing on zero divisors.
     ///
-    /// See `sum` for example on how this is calculated
+    /// Returns the quotient, with 0 wherever the divisor was 0, together
+    /// with a mask that is 1 at those positions and 0 elsewhere. The mask
+    /// is an i8 matrix, like the comparison masks. Only a shape mismatch
+    /// gives a MatrixDimensionMismatchError
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
-    /// use sukker::Dimension;
     ///
-    /// let matrix = Matrix::init(10f32, (2,2));
+    /// let matrix1 = Matrix::new(vec![6.0, 4.0, 2.0, 9.0], (2,2)).unwrap();
+    /// let matrix2 = Matrix::new(vec![3.0, 0.0, 4.0, 0.0], (2,2)).unwrap();
     ///
-    /// assert_eq!(matrix.prod(0, Dimension::Row), 100.0);
-    /// assert_eq!(matrix.prod(0, Dimension::Col), 100.0);
+    /// let (quotient, mask) = matrix1.div_checked(&matrix2).unwrap();
+    ///
+    /// assert_eq!(quotient.get_vec(), vec![2.0, 0.0, 0.5, 0.0]);
+    /// assert_eq!(mask.get_vec(), vec![0, 1, 0, 1]);
     /// ```
-    pub fn prod(&self, rowcol: usize, dimension: Dimension) -> T {
-        match dimension {
-            Dimension::Row => self
-                .data
-                .par_iter()
-                .skip(rowcol * self.ncols)
-                .take(self.ncols)
-                .copied()
-                .product(),
-            Dimension::Col => self
-                .data
-                .par_iter()
-                .skip(rowcol)
-                .step_by(self.ncols)
-                .copied()
-                .product(),
+    pub fn div_checked(&self, other: &Self) -> Result<(Self, Matrix<'a, i8>), MatrixError> {
+        if !self.same_shape(other) {
+            return Err(MatrixError::MatrixDimensionMismatchError);
         }
+
+        let (data, mask): (Vec<T>, Vec<i8>) = self
+            .data
+            .iter()
+            .zip(other.data.iter())
+            .map(|(&x, &y)| {
+                if y == T::zero() {
+                    (T::zero(), 1)
+                } else {
+                    (x / y, 0)
+                }
+            })
+            .unzip();
+
+        Ok((
+            Self::new(data, self.shape()).unwrap(),
+            Matrix::<i8>::new(mask, self.shape()).unwrap(),
+        ))
     }
-}
 
-/// Linalg on floats
-impl<'a, T> LinAlgFloats<'a, T> for Matrix<'a, T>
-where
-    T: MatrixElement + Float + 'a,
-    <T as FromStr>::Err: Error + 'static,
-    Vec<T>: IntoParallelIterator,
-    Vec<&'a T>: IntoParallelRefIterator<'a>,
-{
-    /// Takes the logarithm of each element
+    /// Adds a matrix, a row vector or a column vector element-wise.
+    ///
+    /// A 1 x N matrix is repeated for every row,
+    /// and an M x 1 matrix is repeated for every column
     ///
     /// # Examples
     ///
     /// ```
-    /// use sukker::{Matrix, LinAlgFloats};
+    /// use sukker::Matrix;
     ///
-    /// let matrix = Matrix::init(10.0, (2,2));
-    /// let result = matrix.log(10.0);
+    /// let matrix: Matrix<i32> = Matrix::new(vec![2, 4, 6, 8, 10, 12], (2,3)).unwrap();
+    /// let row: Matrix<i32> = Matrix::new(vec![1, 2, 2], (1,3)).unwrap();
     ///
-    /// assert_eq!(result.all(|&e| e == 1.0), true);
+    /// assert_eq!(matrix.add_broadcast(&row).unwrap().get_vec(), vec![3, 6, 8, 9, 12, 14]);
+    /// ```
+    pub fn add_broadcast(&self, other: &Self) -> Result<Self, MatrixError> {
+        self.broadcast_op(other, |x, y| x + y)
+    }
+
+    /// Subtracts a matrix, a row vector or a column vector element-wise.
+    ///
+    /// A 1 x N matrix is repeated for every row,
+    /// and an M x 1 matrix is repeated for every column
+    ///
+    /// # Examples
     ///
     /// ```
-    fn log(&self, base: T) -> Self {
-        let data: Vec<T> = self.data.par_iter().map(|&e| e.log(base)).collect();
+    /// use sukker::Matrix;
+    ///
+    /// let matrix: Matrix<i32> = Matrix::new(vec![2, 4, 6, 8, 10, 12], (2,3)).unwrap();
+    /// let row: Matrix<i32> = Matrix::new(vec![1, 2, 2], (1,3)).unwrap();
+    ///
+    /// assert_eq!(matrix.sub_broadcast(&row).unwrap().get_vec(), vec![1, 2, 4, 7, 8, 10]);
+    /// ```
+    pub fn sub_broadcast(&self, other: &Self) -> Result<Self, MatrixError> {
+        self.broadcast_op(other, |x, y| x - y)
+    }
 
-        Self::new(data, self.shape()).unwrap()
+    /// Multiplies a matrix, a row vector or a column vector element-wise.
+    ///
+    /// A 1 x N matrix is repeated for every row,
+    /// and an M x 1 matrix is repeated for every column
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix: Matrix<i32> = Matrix::new(vec![2, 4, 6, 8, 10, 12], (2,3)).unwrap();
+    /// let row: Matrix<i32> = Matrix::new(vec![1, 2, 2], (1,3)).unwrap();
+    ///
+    /// assert_eq!(matrix.mul_broadcast(&row).unwrap().get_vec(), vec![2, 8, 12, 8, 20, 24]);
+    /// ```
+    pub fn mul_broadcast(&self, other: &Self) -> Result<Self, MatrixError> {
+        self.broadcast_op(other, |x, y| x * y)
     }
 
-    /// Takes the natural logarithm of each element in a matrix
+    /// Divides a matrix, a row vector or a column vector element-wise.
+    ///
+    /// A 1 x N matrix is repeated for every row,
+    /// and an M x 1 matrix is repeated for every column.
+    /// Dividing by a zero gives a MatrixDivideByZeroError
     ///
     /// # Examples
     ///
     /// ```
-    /// use sukker::{Matrix, LinAlgFloats};
-    /// use sukker::constants::EF64;
+    /// use sukker::Matrix;
     ///
-    /// let matrix: Matrix<f64> = Matrix::init(EF64, (2,2));
+    /// let matrix: Matrix<i32> = Matrix::new(vec![2, 4, 6, 8, 10, 12], (2,3)).unwrap();
+    /// let row: Matrix<i32> = Matrix::new(vec![1, 2, 2], (1,3)).unwrap();
     ///
-    /// let res = matrix.ln();
+    /// assert_eq!(matrix.div_broadcast(&row).unwrap().get_vec(), vec![2, 2, 3, 8, 5, 6]);
     /// ```
-    fn ln(&self) -> Self {
-        let data: Vec<T> = self.data.par_iter().map(|&e| e.ln()).collect();
+    pub fn div_broadcast(&self, other: &Self) -> Result<Self, MatrixError> {
+        if other.any(|e| e == &T::zero()) {
+            return Err(MatrixError::MatrixDivideByZeroError);
+        }
 
-        Self::new(data, self.shape()).unwrap()
+        self.broadcast_op(other, |x, y| x / y)
     }
 
-    /// Takes the square root of each element in a matrix.
-    /// If some elements are negative, these will be kept the same
+    /// Negates every value in the matrix
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::{Matrix, LinAlgFloats};
     ///
-    /// let matrix: Matrix<f64> = Matrix::init(9.0, (3,3));
+    /// let matrix = Matrix::<f32>::ones((2,2));
     ///
-    /// let res = matrix.sqrt();
+    /// let negated = matrix.neg();
     ///
-    /// assert_eq!(res.all(|&e| e == 3.0), true);
+    /// assert_eq!(negated.all(|&e| e == -1.0), true);
     /// ```
-    fn sqrt(&self) -> Self {
-        let data: Vec<T> = self
-            .data
-            .par_iter()
-            .map(|&e| if e > T::zero() { e.sqrt() } else { e })
-            .collect();
+    pub fn neg(&self) -> Self {
+        let data: Vec<T> = self.data.par_iter().map(|&e| e.neg()).collect();
 
         Self::new(data, self.shape()).unwrap()
     }
 
-    /// Gets sin of every value
+    /// Adds a value to a matrix and returns a new matrix
     ///
     /// # Examples
     ///
     /// ```
-    /// use sukker::{Matrix, LinAlgFloats};
-    ///
-    /// let matrix = Matrix::init(1.0, (2,2));
+    /// use sukker::Matrix;
     ///
-    /// let res = matrix.sin();
+    /// let matrix = Matrix::init(20.0, (2,2));
+    /// let value: f32 = 2.0;
+    /// assert_eq!(matrix.add_val(value).get(0,0).unwrap(), 22.0);
     /// ```
-    fn sin(&self) -> Self {
-        let data: Vec<T> = self.data.par_iter().map(|&e| e.sin()).collect();
+    pub fn add_val(&self, val: T) -> Self {
+        let data: Vec<T> = self.data.par_iter().map(|&e| e + val).collect();
 
         Self::new(data, self.shape()).unwrap()
     }
 
-    /// Gets cos of every value
+    /// Substracts a value to a matrix and returns a new matrix
     ///
     /// # Examples
     ///
     /// ```
-    /// use sukker::{Matrix, LinAlgFloats};
-    /// use sukker::constants::EF32;
+    /// use sukker::Matrix;
     ///
-    /// let matrix = Matrix::init(EF32, (2,2));
+    /// let matrix = Matrix::init(20.0, (2,2));
+    /// let value: f32 = 2.0;
+    /// assert_eq!(matrix.sub_val(value).get(0,0).unwrap(), 18.0);
+    /// ```
+    pub fn sub_val(&self, val: T) -> Self {
+        let data: Vec<T> = self.data.par_iter().map(|&e| e - val).collect();
+
+        Self::new(data, self.shape()).unwrap()
+    }
+
+    /// Multiplies a value to a matrix and returns a new matrix
+    ///
+    /// # Examples
     ///
-    /// let res = matrix.cos();
     /// ```
-    fn cos(&self) -> Self {
-        let data: Vec<T> = self.data.par_iter().map(|&e| e.cos()).collect();
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::init(20.0, (2,2));
+    /// let value: f32 = 2.0;
+    /// assert_eq!(matrix.mul_val(value).get(0,0).unwrap(), 40.0);
+    /// ```
+    pub fn mul_val(&self, val: T) -> Self {
+        let data: Vec<T> = self.data.par_iter().map(|&e| e * val).collect();
 
         Self::new(data, self.shape()).unwrap()
     }
 
-    /// Gets tan of every value
+    /// Divides a value to a matrix and returns a new matrix
     ///
     /// # Examples
     ///
     /// ```
-    /// use sukker::{Matrix, LinAlgFloats};
-    /// use sukker::constants::EF32;
+    /// use sukker::Matrix;
     ///
-    /// let matrix = Matrix::init(EF32, (2,2));
+    /// let matrix = Matrix::init(20.0, (2,2));
+    /// let value: f32 = 2.0;
     ///
-    /// let res = matrix.tan();
+    /// let result_mat = matrix.div_val(value);
+    ///
+    /// assert_eq!(result_mat.get(0,0).unwrap(), 10.0);
     /// ```
-    fn tan(&self) -> Self {
-        let data: Vec<T> = self.data.par_iter().map(|&e| e.tan()).collect();
+    pub fn div_val(&self, val: T) -> Self {
+        let data: Vec<T> = self.data.par_iter().map(|&e| e / val).collect();
 
         Self::new(data, self.shape()).unwrap()
     }
 
-    /// Gets sinh of every value
+    /// Pows each value in a matrix by val times
     ///
     /// # Examples
     ///
     /// ```
-    /// use sukker::{Matrix, LinAlgFloats};
-    /// use sukker::constants::EF32;
+    /// use sukker::Matrix;
     ///
-    /// let matrix = Matrix::init(EF32, (2,2));
+    /// let matrix = Matrix::init(2.0, (2,2));
     ///
-    /// let res = matrix.sinh();
+    /// let result_mat = matrix.pow(2);
+    ///
+    /// assert_eq!(result_mat.get_vec(), vec![4.0, 4.0, 4.0, 4.0]);
     /// ```
-    fn sinh(&self) -> Self {
-        let data: Vec<T> = self.data.par_iter().map(|&e| e.sinh()).collect();
+    pub fn pow(&self, val: usize) -> Self {
+        let data: Vec<T> = self.data.par_iter().map(|&e| pow(e, val)).collect();
 
         Self::new(data, self.shape()).unwrap()
     }
 
-    /// Gets cosh of every value
+    /// Takes the absolute values of the matrix
     ///
     /// # Examples
     ///
     /// ```
-    /// use sukker::{Matrix, LinAlgFloats};
-    /// use sukker::constants::EF32;
+    /// use sukker::Matrix;
     ///
-    /// let matrix = Matrix::init(EF32, (2,2));
+    /// let matrix = Matrix::init(-20.0, (2,2));
     ///
-    /// let res = matrix.cosh();
+    /// let res = matrix.abs();
+    ///
+    /// assert_eq!(res.all(|&e| e == 20.0), true);
     /// ```
-    fn cosh(&self) -> Self {
-        let data: Vec<T> = self.data.par_iter().map(|&e| e.cosh()).collect();
+    pub fn abs(&self) -> Self {
+        let data: Vec<T> = self.data.par_iter().map(|&e| e.abs()).collect();
 
         Self::new(data, self.shape()).unwrap()
     }
 
-    /// Gets tanh of every value
+    /// Clamps every element into the range [min, max].
+    ///
+    /// Elements that are not comparable to the bounds,
+    /// like NaN, are left untouched
+    ///
+    /// # Panics
+    ///
+    /// Panics if min > max
     ///
     /// # Examples
     ///
     /// ```
-    /// use sukker::{Matrix, LinAlgFloats};
-    /// use sukker::constants::EF32;
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::new(vec![-2.0, 0.5, 3.0, 1.0], (2,2)).unwrap();
     ///
-    /// let matrix = Matrix::init(EF32, (2,2));
+    /// let res = matrix.clamp(0.0, 1.0);
     ///
-    /// let res = matrix.tanh();
+    /// assert_eq!(res.get_vec(), vec![0.0, 0.5, 1.0, 1.0]);
     /// ```
-    fn tanh(&self) -> Self {
-        let data: Vec<T> = self.data.par_iter().map(|&e| e.tanh()).collect();
-
-        Self::new(data, self.shape()).unwrap()
+    pub fn clamp(&self, min: T, max: T) -> Self {
+        let mut res = self.clone();
+        res.clamp_self(min, max);
+        res
     }
 
-    /// Find the eigenvale of a matrix
+    /// Multiply a matrix with itself n number of times.
+    /// This is done with exponentiation by squaring, so only
+    /// O(log n) matrix multiplications are performed.
     ///
-    /// # Examples
+    /// `exp(0)` gives the identity matrix and `exp(1)` a copy of self.
+    ///
+    /// If matrix is not in form NxN, this function returns None
+    ///
+    /// Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let mut matrix = Matrix::init(2.0, (2,100));
+    /// let a = Matrix::<i32>::init(2, (2,2));
+    ///
+    /// let res = a.exp(3).unwrap();
     ///
+    /// assert_eq!(res.all(|&e| e == 32), true);
+    /// assert_eq!(a.exp(0).unwrap(), Matrix::eye(2));
     /// ```
-    fn get_eigenvalues(&self) -> Option<Vec<T>> {
-        todo!()
-    }
+    pub fn exp(&self, n: usize) -> Option<Self>
+    where
+        T: 'static,
+    {
+        if self.nrows != self.ncols {
+            return None;
+        }
 
-    /// Find the eigenvectors
-    fn get_eigenvectors(&self) -> Option<Vec<T>> {
-        unimplemented!()
+        if n == 0 {
+            return Some(Self::eye(self.nrows));
+        }
+
+        let mut res: Option<Self> = None;
+        let mut base = self.clone();
+        let mut n = n;
+
+        loop {
+            if n & 1 == 1 {
+                res = Some(match res {
+                    Some(acc) => acc.matmul(&base).unwrap(),
+                    None => base.clone(),
+                });
+            }
+
+            n >>= 1;
+
+            if n == 0 {
+                break;
+            }
+
+            base = base.matmul(&base).unwrap();
+        }
+
+        res
     }
-}
 
-/// trait MatrixLinAlg contains all common Linear Algebra functions to be
-/// performed on matrices
-impl<'a, T> Matrix<'a, T>
-where
-    T: MatrixElement,
-    <T as FromStr>::Err: Error + 'static,
-    Vec<T>: IntoParallelIterator,
-    Vec<&'a T>: IntoParallelRefIterator<'a>,
-{
-    /// Adds one matrix to another
+    /// Adds a matrix in-place to a matrix
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let matrix1 = Matrix::init(10.0, (2,2));
-    /// let matrix2 = Matrix::init(10.0, (2,2));
+    /// let mut matrix1 = Matrix::init(20.0, (2,2));
+    /// let matrix2 = Matrix::init(2.0, (2,2));
     ///
-    /// assert_eq!(matrix1.add(&matrix2).unwrap().get(0,0).unwrap(), 20.0);
+    /// matrix1.add_self(&matrix2);
+    ///
+    /// assert_eq!(matrix1.get(0,0).unwrap(), 22.0);
     /// ```
-    pub fn add(&self, other: &Self) -> Result<Self, MatrixError> {
-        if self.shape() != other.shape() {
-            return Err(MatrixError::MatrixDimensionMismatchError.into());
-        }
-
-        let data: Vec<T> = self
-            .data
-            .iter()
-            .zip(other.data.iter())
-            .map(|(&x, &y)| x + y)
-            .collect();
-
-        Ok(Self::new(data, self.shape()).unwrap())
+    pub fn add_self(&mut self, other: &Self) {
+        self.data
+            .par_iter_mut()
+            .zip(&other.data)
+            .for_each(|(a, b)| *a += *b);
     }
 
-    /// Subtracts one matrix from another
+    /// Subtracts a matrix in-place to a matrix
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let matrix1 = Matrix::init(10.0, (2,2));
-    /// let matrix2 = Matrix::init(10.0, (2,2));
+    /// let mut matrix1 = Matrix::init(20.0, (2,2));
+    /// let matrix2 = Matrix::init(2.0, (2,2));
     ///
-    /// assert_eq!(matrix1.sub(&matrix2).unwrap().get(1,0).unwrap(), 0.0);
+    /// matrix1.sub_self(&matrix2);
+    ///
+    /// assert_eq!(matrix1.get(0,0).unwrap(), 18.0);
     /// ```
-    pub fn sub(&self, other: &Self) -> Result<Self, MatrixError> {
-        if self.shape() != other.shape() {
-            return Err(MatrixError::MatrixDimensionMismatchError.into());
-        }
-
-        let data: Vec<T> = self
-            .data
-            .iter()
-            .zip(other.data.iter())
-            .map(|(&x, &y)| x - y)
-            .collect();
-
-        Ok(Self::new(data, self.shape()).unwrap())
+    pub fn sub_self(&mut self, other: &Self) {
+        self.data
+            .par_iter_mut()
+            .zip(&other.data)
+            .for_each(|(a, b)| *a -= *b);
     }
 
-    /// Subtracts one array from another and returns the absolute value
+    /// Multiplies a matrix in-place to a matrix
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let matrix1 = Matrix::init(10.0f32, (2,2));
-    /// let matrix2 = Matrix::init(15.0f32, (2,2));
+    /// let mut matrix1 = Matrix::init(20.0, (2,2));
+    /// let matrix2 = Matrix::init(2.0, (2,2));
     ///
-    /// assert_eq!(matrix1.sub_abs(&matrix2).unwrap().get(0,0).unwrap(), 5.0);
+    /// matrix1.mul_self(&matrix2);
+    ///
+    /// assert_eq!(matrix1.get(0,0).unwrap(), 40.0);
     /// ```
-    pub fn sub_abs(&self, other: &Self) -> Result<Self, MatrixError> {
-        if self.shape() != other.shape() {
-            return Err(MatrixError::MatrixDimensionMismatchError.into());
-        }
-
-        let data = self
-            .data
-            .iter()
-            .zip(other.data.iter())
-            .map(|(&x, &y)| if x > y { x - y } else { y - x })
-            .collect_vec();
-
-        Ok(Self::new(data, self.shape()).unwrap())
+    pub fn mul_self(&mut self, other: &Self) {
+        self.data
+            .par_iter_mut()
+            .zip(&other.data)
+            .for_each(|(a, b)| *a *= *b);
     }
 
-    /// Dot product of two matrices
+    /// Divides a matrix in-place to a matrix
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let matrix1 = Matrix::init(20.0, (2,2));
-    /// let matrix2 = Matrix::init(10.0, (2,2));
+    /// let mut matrix1 = Matrix::init(20.0, (2,2));
+    /// let matrix2 = Matrix::init(2.0, (2,2));
     ///
-    /// assert_eq!(matrix1.mul(&matrix2).unwrap().get(0,0).unwrap(), 200.0);
+    /// matrix1.div_self(&matrix2);
+    ///
+    /// assert_eq!(matrix1.get(0,0).unwrap(), 10.0);
     /// ```
-    pub fn mul(&self, other: &Self) -> Result<Self, MatrixError> {
-        if self.shape() != other.shape() {
-            return Err(MatrixError::MatrixDimensionMismatchError.into());
-        }
-
-        let data = self
-            .data
-            .iter()
-            .zip(other.data.iter())
-            .map(|(&x, &y)| x * y)
-            .collect_vec();
-
-        Ok(Self::new(data, self.shape()).unwrap())
+    pub fn div_self(&mut self, other: &Self) {
+        self.data
+            .par_iter_mut()
+            .zip(&other.data)
+            .for_each(|(a, b)| *a /= *b);
     }
 
-    /// Dot product of two matrices
+    /// Abs matrix in-place to a matrix
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let matrix1 = Matrix::init(20.0, (2,2));
-    /// let matrix2 = Matrix::init(10.0, (2,2));
+    /// let mut matrix = Matrix::init(20.0, (2,2));
     ///
-    /// assert_eq!(matrix1.dot(&matrix2).unwrap().get(0,0).unwrap(), 200.0);
+    /// matrix.abs_self()
+    ///
+    /// // assert_eq!(matrix1.get(0,0).unwrap(), 22.0);
     /// ```
-    pub fn dot(&self, other: &Self) -> Result<Self, MatrixError> {
-        self.mul(other)
+    pub fn abs_self(&mut self) {
+        self.data.par_iter_mut().for_each(|e| *e = abs(*e))
     }
 
-    /// Bad handling of zero div
+    /// Clamps every element in-place into the range [min, max].
+    ///
+    /// Elements that are not comparable to the bounds,
+    /// like NaN, are left untouched
+    ///
+    /// # Panics
+    ///
+    /// Panics if min > max
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let matrix1 = Matrix::init(20.0, (2,2));
-    /// let matrix2 = Matrix::init(10.0, (2,2));
+    /// let mut matrix = Matrix::new(vec![-5, 2, 9, 4], (2,2)).unwrap();
     ///
-    /// assert_eq!(matrix1.div(&matrix2).unwrap().get(0,0).unwrap(), 2.0);
+    /// matrix.clamp_self(0, 4);
+    ///
+    /// assert_eq!(matrix.get_vec(), vec![0, 2, 4, 4]);
     /// ```
-    pub fn div(&self, other: &Self) -> Result<Self, MatrixError> {
-        if self.shape() != other.shape() {
-            return Err(MatrixError::MatrixDimensionMismatchError.into());
-        }
-
-        if other.any(|e| e == &T::zero()) {
-            return Err(MatrixError::MatrixDivideByZeroError.into());
-        }
-
-        let data = self
-            .data
-            .iter()
-            .zip(other.data.iter())
-            .map(|(&x, &y)| x / y)
-            .collect_vec();
+    pub fn clamp_self(&mut self, min: T, max: T) {
+        assert!(
+            min <= max,
+            "Can not clamp to [{min}, {max}], min is larger than max"
+        );
 
-        Ok(Self::new(data, self.shape()).unwrap())
+        self.data.par_iter_mut().for_each(|e| {
+            if *e < min {
+                *e = min;
+            } else if *e > max {
+                *e = max;
+            }
+        });
     }
 
-    /// Negates every value in the matrix
+    /// Adds a value in-place to a matrix
     ///
     /// # Examples
     ///
     /// ```
-    /// use sukker::{Matrix, LinAlgFloats};
+    /// use sukker::Matrix;
     ///
-    /// let matrix = Matrix::<f32>::ones((2,2));
+    /// let mut matrix = Matrix::init(20.0, (2,2));
+    /// let value: f32 = 2.0;
     ///
-    /// let negated = matrix.neg();
+    /// matrix.add_val_self(value);
     ///
-    /// assert_eq!(negated.all(|&e| e == -1.0), true);
+    /// assert_eq!(matrix.get(0,0).unwrap(), 22.0);
     /// ```
-    pub fn neg(&self) -> Self {
-        let data: Vec<T> = self.data.par_iter().map(|&e| e.neg()).collect();
-
-        Self::new(data, self.shape()).unwrap()
+    pub fn add_val_self(&mut self, val: T) {
+        self.data.par_iter_mut().for_each(|e| *e += val);
     }
 
-    /// Adds a value to a matrix and returns a new matrix
+    /// Subtracts a value in-place to a matrix
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let matrix = Matrix::init(20.0, (2,2));
+    /// let mut matrix = Matrix::init(20.0, (2,2));
     /// let value: f32 = 2.0;
-    /// assert_eq!(matrix.add_val(value).get(0,0).unwrap(), 22.0);
+    ///
+    /// matrix.sub_val_self(value);
+    ///
+    /// assert_eq!(matrix.get(0,0).unwrap(), 18.0);
     /// ```
-    pub fn add_val(&self, val: T) -> Self {
-        let data: Vec<T> = self.data.par_iter().map(|&e| e + val).collect();
-
-        Self::new(data, self.shape()).unwrap()
+    pub fn sub_val_self(&mut self, val: T) {
+        self.data.par_iter_mut().for_each(|e| *e -= val);
     }
 
-    /// Substracts a value to a matrix and returns a new matrix
+    /// Mults a value in-place to a matrix
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let matrix = Matrix::init(20.0, (2,2));
+    /// let mut matrix = Matrix::init(20.0, (2,2));
     /// let value: f32 = 2.0;
-    /// assert_eq!(matrix.sub_val(value).get(0,0).unwrap(), 18.0);
+    ///
+    /// matrix.mul_val_self(value);
+    ///
+    /// assert_eq!(matrix.get(0,0).unwrap(), 40.0);
     /// ```
-    pub fn sub_val(&self, val: T) -> Self {
-        let data: Vec<T> = self.data.par_iter().map(|&e| e - val).collect();
-
-        Self::new(data, self.shape()).unwrap()
+    pub fn mul_val_self(&mut self, val: T) {
+        self.data.par_iter_mut().for_each(|e| *e *= val);
     }
 
-    /// Multiplies a value to a matrix and returns a new matrix
+    /// Divs a value in-place to a matrix
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let matrix = Matrix::init(20.0, (2,2));
+    /// let mut matrix = Matrix::init(20.0, (2,2));
     /// let value: f32 = 2.0;
-    /// assert_eq!(matrix.mul_val(value).get(0,0).unwrap(), 40.0);
+    ///
+    /// matrix.div_val_self(value);
+    ///
+    /// assert_eq!(matrix.get(0,0).unwrap(), 10.0);
     /// ```
-    pub fn mul_val(&self, val: T) -> Self {
-        let data: Vec<T> = self.data.par_iter().map(|&e| e * val).collect();
-
-        Self::new(data, self.shape()).unwrap()
+    pub fn div_val_self(&mut self, val: T) {
+        self.data.par_iter_mut().for_each(|e| *e /= val);
     }
 
-    /// Divides a value to a matrix and returns a new matrix
+    /// Adds a 1 x ncols row vector in-place to every row of the matrix.
+    ///
+    /// Returns MatrixDimensionMismatchError if `row` is not 1 x ncols
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let matrix = Matrix::init(20.0, (2,2));
-    /// let value: f32 = 2.0;
+    /// let mut matrix = Matrix::init(1, (2,3));
+    /// let bias = Matrix::new(vec![1, 2, 3], (1,3)).unwrap();
     ///
-    /// let result_mat = matrix.div_val(value);
+    /// matrix.add_row_vector_self(&bias).unwrap();
     ///
-    /// assert_eq!(result_mat.get(0,0).unwrap(), 10.0);
+    /// assert_eq!(matrix.get_vec(), vec![2, 3, 4, 2, 3, 4]);
     /// ```
-    pub fn div_val(&self, val: T) -> Self {
-        let data: Vec<T> = self.data.par_iter().map(|&e| e / val).collect();
+    pub fn add_row_vector_self(&mut self, row: &Self) -> Result<(), MatrixError> {
+        if row.shape() != (1, self.ncols) {
+            return Err(MatrixError::MatrixDimensionMismatchError);
+        }
 
-        Self::new(data, self.shape()).unwrap()
+        self.data
+            .par_chunks_mut(self.ncols.max(1))
+            .for_each(|r| r.iter_mut().zip(&row.data).for_each(|(a, b)| *a += *b));
+
+        Ok(())
     }
 
-    /// Pows each value in a matrix by val times
+    /// Adds an nrows x 1 column vector in-place to every column of the matrix,
+    /// so every element of row i gets `col(i)` added.
+    ///
+    /// Returns MatrixDimensionMismatchError if `col` is not nrows x 1
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let matrix = Matrix::init(2.0, (2,2));
+    /// let mut matrix = Matrix::init(1, (2,3));
+    /// let bias = Matrix::new(vec![10, 20], (2,1)).unwrap();
     ///
-    /// let result_mat = matrix.pow(2);
+    /// matrix.add_col_vector_self(&bias).unwrap();
     ///
-    /// assert_eq!(result_mat.get_vec(), vec![4.0, 4.0, 4.0, 4.0]);
+    /// assert_eq!(matrix.get_vec(), vec![11, 11, 11, 21, 21, 21]);
     /// ```
-    pub fn pow(&self, val: usize) -> Self {
-        let data: Vec<T> = self.data.par_iter().map(|&e| pow(e, val)).collect();
+    pub fn add_col_vector_self(&mut self, col: &Self) -> Result<(), MatrixError> {
+        if col.shape() != (self.nrows, 1) {
+            return Err(MatrixError::MatrixDimensionMismatchError);
+        }
 
-        Self::new(data, self.shape()).unwrap()
+        self.data
+            .par_chunks_mut(self.ncols.max(1))
+            .zip(&col.data)
+            .for_each(|(r, &b)| r.iter_mut().for_each(|a| *a += b));
+
+        Ok(())
     }
 
-    /// Takes the absolute values of the matrix
+    /// Transposed matrix multiplications
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let matrix = Matrix::init(-20.0, (2,2));
+    /// let mut matrix1 = Matrix::init(2.0, (2,4));
+    /// let matrix2 = Matrix::init(2.0, (4,2));
     ///
-    /// let res = matrix.abs();
+    /// let result = matrix1.matmul(&matrix2).unwrap();
     ///
-    /// assert_eq!(res.all(|&e| e == 20.0), true);
+    /// assert_eq!(result.get(0,0).unwrap(), 16.0);
+    /// assert_eq!(result.shape(), (2,2));
     /// ```
-    pub fn abs(&self) -> Self {
-        let data: Vec<T> = self.data.par_iter().map(|&e| e.abs()).collect();
+    pub fn matmul(&self, other: &Self) -> Result<Self, MatrixError>
+    where
+        T: 'static,
+    {
+        // assert M N x N P
+        if self.ncols != other.nrows {
+            return Err(MatrixError::MatrixDimensionMismatchError.into());
+        }
 
-        Self::new(data, self.shape()).unwrap()
+        Ok(self.matmul_helper(other))
     }
 
-    /// Multiply a matrix with itself n number of times.
-    /// This is done by performing a matrix multiplication
-    /// several time on self and the result of mat.exp(i-1).
+    /// Shorthand method for matmul
+    pub fn mm(&self, other: &Self) -> Result<Self, MatrixError>
+    where
+        T: 'static,
+    {
+        self.matmul(other)
+    }
+
+    /// Matrix multiplication with an explicitly chosen algorithm.
     ///
-    /// If matrix is not in form NxN, this function returns None
+    /// Mostly useful for debugging and benchmarking, as all
+    /// algorithms are supposed to give the same result
     ///
-    /// Examples
+    /// # Examples
     ///
     /// ```
-    /// use sukker::Matrix;
+    /// use sukker::{Matrix, MatMulAlgo};
     ///
-    /// let a = Matrix::<i32>::init(2, (2,2));
+    /// let matrix1 = Matrix::init(2.0, (2,4));
+    /// let matrix2 = Matrix::init(2.0, (4,2));
     ///
-    /// let res = a.exp(3).unwrap();
+    /// let result = matrix1.matmul_with(&matrix2, MatMulAlgo::Naive).unwrap();
     ///
-    /// assert_eq!(res.all(|&e| e == 32), true);
+    /// assert_eq!(result, matrix1.matmul(&matrix2).unwrap());
     /// ```
-    pub fn exp(&self, n: usize) -> Option<Self> {
-        if self.nrows != self.ncols {
-            return None;
+    pub fn matmul_with(&self, other: &Self, algo: MatMulAlgo) -> Result<Self, MatrixError>
+    where
+        T: 'static,
+    {
+        if self.ncols != other.nrows {
+            return Err(MatrixError::MatrixDimensionMismatchError);
         }
 
-        let mut res = self.clone();
-
-        (0..n - 1).for_each(|_| res = res.matmul(self).unwrap());
-
-        Some(res)
+        Ok(match algo {
+            MatMulAlgo::Naive => self.naive(other),
+            MatMulAlgo::Blocked => self.blocked_matmul(other, self.get_block_size(other)),
+            MatMulAlgo::AutoBlocked => self.matmul_helper(other),
+        })
     }
 
-    /// Adds a matrix in-place to a matrix
+    /// Matrix multiplication with a sparse matrix on the right hand side.
+    ///
+    /// Only the stored entries of the sparse matrix are visited,
+    /// and the result is dense
     ///
     /// # Examples
     ///
     /// ```
-    /// use sukker::Matrix;
+    /// use sukker::{Matrix, SparseMatrix};
     ///
-    /// let mut matrix1 = Matrix::init(20.0, (2,2));
-    /// let matrix2 = Matrix::init(2.0, (2,2));
+    /// let dense = Matrix::init(2.0, (2,3));
+    /// let sparse = SparseMatrix::<f64>::eye(3);
     ///
-    /// matrix1.add_self(&matrix2);
+    /// let result = dense.matmul_sparse(&sparse).unwrap();
     ///
-    /// assert_eq!(matrix1.get(0,0).unwrap(), 22.0);
+    /// assert_eq!(result, dense);
     /// ```
-    pub fn add_self(&mut self, other: &Self) {
-        self.data
-            .par_iter_mut()
-            .zip(&other.data)
-            .for_each(|(a, b)| *a += *b);
+    pub fn matmul_sparse(&self, other: &SparseMatrix<'a, T>) -> Result<Self, MatrixError> {
+        if self.ncols != other.nrows {
+            return Err(MatrixError::MatrixMultiplicationDimensionMismatchError);
+        }
+
+        let (n, p) = (self.ncols, other.ncols);
+
+        let mut data = vec![T::zero(); self.nrows * p];
+
+        data.par_chunks_mut(p.max(1))
+            .enumerate()
+            .for_each(|(i, row)| {
+                for (&(k, j), &val) in other.data.iter() {
+                    row[j] += self.data[at!(i, k, n)] * val;
+                }
+            });
+
+        Self::new(data, (self.nrows, p))
     }
 
-    /// Subtracts a matrix in-place to a matrix
+    /// Adds a sparse matrix element-wise, giving a dense matrix.
+    ///
+    /// Only the stored entries of the sparse matrix are visited
     ///
     /// # Examples
     ///
     /// ```
-    /// use sukker::Matrix;
+    /// use sukker::{Matrix, SparseMatrix};
     ///
-    /// let mut matrix1 = Matrix::init(20.0, (2,2));
-    /// let matrix2 = Matrix::init(2.0, (2,2));
+    /// let dense = Matrix::init(3, (2,2));
+    /// let sparse = SparseMatrix::<i32>::eye(2);
     ///
-    /// matrix1.sub_self(&matrix2);
+    /// let result = dense.add_sparse(&sparse).unwrap();
     ///
-    /// assert_eq!(matrix1.get(0,0).unwrap(), 18.0);
+    /// assert_eq!(result.get_vec(), vec![4, 3, 3, 4]);
     /// ```
-    pub fn sub_self(&mut self, other: &Self) {
-        self.data
-            .par_iter_mut()
-            .zip(&other.data)
-            .for_each(|(a, b)| *a -= *b);
+    pub fn add_sparse(&self, other: &SparseMatrix<'a, T>) -> Result<Self, MatrixError> {
+        if self.shape() != other.shape() {
+            return Err(MatrixError::MatrixDimensionMismatchError);
+        }
+
+        let mut res = self.clone();
+
+        for (&(i, j), &val) in other.data.iter() {
+            res.data[at!(i, j, self.ncols)] += val;
+        }
+
+        Ok(res)
     }
 
-    /// Multiplies a matrix in-place to a matrix
+    /// Subtracts a sparse matrix element-wise, giving a dense matrix.
+    ///
+    /// Only the stored entries of the sparse matrix are visited
     ///
     /// # Examples
     ///
     /// ```
-    /// use sukker::Matrix;
+    /// use sukker::{Matrix, SparseMatrix};
     ///
-    /// let mut matrix1 = Matrix::init(20.0, (2,2));
-    /// let matrix2 = Matrix::init(2.0, (2,2));
+    /// let dense = Matrix::init(3, (2,2));
+    /// let sparse = SparseMatrix::<i32>::eye(2);
     ///
-    /// matrix1.mul_self(&matrix2);
+    /// let result = dense.sub_sparse(&sparse).unwrap();
     ///
-    /// assert_eq!(matrix1.get(0,0).unwrap(), 40.0);
+    /// assert_eq!(result.get_vec(), vec![2, 3, 3, 2]);
     /// ```
-    pub fn mul_self(&mut self, other: &Self) {
-        self.data
-            .par_iter_mut()
-            .zip(&other.data)
-            .for_each(|(a, b)| *a *= *b);
+    pub fn sub_sparse(&self, other: &SparseMatrix<'a, T>) -> Result<Self, MatrixError> {
+        if self.shape() != other.shape() {
+            return Err(MatrixError::MatrixDimensionMismatchError);
+        }
+
+        let mut res = self.clone();
+
+        for (&(i, j), &val) in other.data.iter() {
+            res.data[at!(i, j, self.ncols)] -= val;
+        }
+
+        Ok(res)
     }
 
-    /// Divides a matrix in-place to a matrix
+    /// Multiplies a sparse matrix element-wise, giving a dense matrix.
+    ///
+    /// Only the stored entries of the sparse matrix are visited,
+    /// so every cell where the sparse matrix is zero is zero in the result
     ///
     /// # Examples
     ///
     /// ```
-    /// use sukker::Matrix;
+    /// use sukker::{Matrix, SparseMatrix};
     ///
-    /// let mut matrix1 = Matrix::init(20.0, (2,2));
-    /// let matrix2 = Matrix::init(2.0, (2,2));
+    /// let dense = Matrix::init(3, (2,2));
+    /// let sparse = SparseMatrix::<i32>::eye(2);
     ///
-    /// matrix1.div_self(&matrix2);
+    /// let result = dense.mul_sparse(&sparse).unwrap();
     ///
-    /// assert_eq!(matrix1.get(0,0).unwrap(), 10.0);
+    /// assert_eq!(result.get_vec(), vec![3, 0, 0, 3]);
     /// ```
-    pub fn div_self(&mut self, other: &Self) {
-        self.data
-            .par_iter_mut()
-            .zip(&other.data)
-            .for_each(|(a, b)| *a /= *b);
+    pub fn mul_sparse(&self, other: &SparseMatrix<'a, T>) -> Result<Self, MatrixError> {
+        if self.shape() != other.shape() {
+            return Err(MatrixError::MatrixDimensionMismatchError);
+        }
+
+        let mut res = Self::zeros(self.shape());
+
+        for (&(i, j), &val) in other.data.iter() {
+            let idx = at!(i, j, self.ncols);
+            res.data[idx] = self.data[idx] * val;
+        }
+
+        Ok(res)
     }
 
-    /// Abs matrix in-place to a matrix
+    /// Kronecker product of two matrices.
+    ///
+    /// For an M x N and a P x Q matrix, the result is MP x NQ,
+    /// where every element of self is replaced by that element times other.
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let mut matrix = Matrix::init(20.0, (2,2));
+    /// let a: Matrix<i32> = Matrix::eye(2);
+    /// let b: Matrix<i32> = Matrix::init(3, (1,2));
     ///
-    /// matrix.abs_self()
+    /// let result = a.kron(&b);
     ///
-    /// // assert_eq!(matrix1.get(0,0).unwrap(), 22.0);
+    /// assert_eq!(result.shape(), (2,4));
+    /// assert_eq!(result.get_vec(), vec![3, 3, 0, 0, 0, 0, 3, 3]);
     /// ```
-    pub fn abs_self(&mut self) {
-        self.data.par_iter_mut().for_each(|e| *e = abs(*e))
+    pub fn kron(&self, other: &Self) -> Self {
+        let (m, n) = self.shape();
+        let (p, q) = other.shape();
+
+        let ncols = n * q;
+        let mut data = vec![T::zero(); m * p * ncols];
+
+        // Each chunk holds the block row for one row of self
+        data.par_chunks_mut((p * ncols).max(1))
+            .enumerate()
+            .for_each(|(i, block_row)| {
+                for k in 0..p {
+                    for j in 0..n {
+                        let a = self.data[at!(i, j, n)];
+                        for l in 0..q {
+                            block_row[at!(k, j * q + l, ncols)] = a * other.data[at!(k, l, q)];
+                        }
+                    }
+                }
+            });
+
+        Self::new(data, (m * p, ncols)).unwrap()
     }
 
-    /// Adds a value in-place to a matrix
+    /// Dot product of two vectors.
+    ///
+    /// Both matrices have to be either 1 x N or N x 1,
+    /// with the same number of elements, but can have different orientation.
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let mut matrix = Matrix::init(20.0, (2,2));
-    /// let value: f32 = 2.0;
-    ///
-    /// matrix.add_val_self(value);
+    /// let row: Matrix<i32> = Matrix::new(vec![1, 2, 3], (1,3)).unwrap();
+    /// let col: Matrix<i32> = Matrix::new(vec![4, 5, 6], (3,1)).unwrap();
     ///
-    /// assert_eq!(matrix.get(0,0).unwrap(), 22.0);
+    /// assert_eq!(row.vdot(&col).unwrap(), 32);
     /// ```
-    pub fn add_val_self(&mut self, val: T) {
-        self.data.par_iter_mut().for_each(|e| *e += val);
+    pub fn vdot(&self, other: &Self) -> Result<T, MatrixError> {
+        if !self.is_vector() || !other.is_vector() || self.size() != other.size() {
+            return Err(MatrixError::MatrixDimensionMismatchError);
+        }
+
+        Ok(self
+            .data
+            .par_iter()
+            .zip(other.data.par_iter())
+            .map(|(&x, &y)| x * y)
+            .sum())
     }
 
-    /// Subtracts a value in-place to a matrix
+    /// Outer product of two vectors.
+    ///
+    /// An M element vector and an N element vector gives an M x N matrix,
+    /// regardless of whether the vectors are rows or columns.
     ///
     /// # Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let mut matrix = Matrix::init(20.0, (2,2));
-    /// let value: f32 = 2.0;
+    /// let a: Matrix<i32> = Matrix::new(vec![1, 2], (2,1)).unwrap();
+    /// let b: Matrix<i32> = Matrix::new(vec![3, 4, 5], (1,3)).unwrap();
     ///
-    /// matrix.sub_val_self(value);
+    /// let result = a.outer(&b).unwrap();
     ///
-    /// assert_eq!(matrix.get(0,0).unwrap(), 18.0);
+    /// assert_eq!(result.shape(), (2,3));
+    /// assert_eq!(result.get_vec(), vec![3, 4, 5, 6, 8, 10]);
     /// ```
-    pub fn sub_val_self(&mut self, val: T) {
-        self.data.par_iter_mut().for_each(|e| *e -= val);
+    pub fn outer(&self, other: &Self) -> Result<Self, MatrixError> {
+        if !self.is_vector() || !other.is_vector() {
+            return Err(MatrixError::MatrixDimensionMismatchError);
+        }
+
+        let data: Vec<T> = self
+            .data
+            .par_iter()
+            .flat_map(|&x| other.data.par_iter().map(move |&y| x * y))
+            .collect();
+
+        Self::new(data, (self.size(), other.size()))
     }
 
-    /// Mults a value in-place to a matrix
+    // A vector is a matrix with a single row or a single column
+    fn is_vector(&self) -> bool {
+        self.nrows == 1 || self.ncols == 1
+    }
+
+    /// Get's the determinat of a N x N matrix
     ///
-    /// # Examples
+    /// Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let mut matrix = Matrix::init(20.0, (2,2));
-    /// let value: f32 = 2.0;
+    /// let mat: Matrix<i32> = Matrix::new(vec![1,3,5,9,1,3,1,7,4,3,9,7,5,2,0,9], (4,4)).unwrap();
     ///
-    /// matrix.mul_val_self(value);
     ///
-    /// assert_eq!(matrix.get(0,0).unwrap(), 40.0);
+    /// let res = mat.determinant().unwrap();
+    ///
+    /// assert_eq!(res, -376);
     /// ```
-    pub fn mul_val_self(&mut self, val: T) {
-        self.data.par_iter_mut().for_each(|e| *e *= val);
+    pub fn determinant(&self) -> Option<T> {
+        if self.nrows != self.ncols {
+            return None;
+        }
+
+        Some(self.determinant_helper())
     }
 
-    /// Divs a value in-place to a matrix
+    /// Shorthand call for `determinant`
+    pub fn det(&self) -> Option<T> {
+        self.determinant()
+    }
+
+    /// Sums up the main diagonal of a N x N matrix
     ///
-    /// # Examples
+    /// Returns None if the matrix is not square
+    ///
+    /// Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let mut matrix = Matrix::init(20.0, (2,2));
-    /// let value: f32 = 2.0;
-    ///
-    /// matrix.div_val_self(value);
+    /// let mat: Matrix<i32> = Matrix::new(vec![1, 2, 3, 4], (2,2)).unwrap();
     ///
-    /// assert_eq!(matrix.get(0,0).unwrap(), 10.0);
+    /// assert_eq!(mat.trace(), Some(5));
     /// ```
-    pub fn div_val_self(&mut self, val: T) {
-        self.data.par_iter_mut().for_each(|e| *e /= val);
+    pub fn trace(&self) -> Option<T> {
+        if self.nrows != self.ncols {
+            return None;
+        }
+
+        Some((0..self.nrows).map(|i| self.at(i, i)).sum())
     }
 
-    /// Transposed matrix multiplications
+    /// Computes the cofactor matrix of a N x N matrix, where
+    /// element (i, j) is (-1)^(i+j) times the determinant of the minor
+    /// left after removing row i and column j.
     ///
-    /// # Examples
+    /// Returns None if the matrix is not square
+    ///
+    /// Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let mut matrix1 = Matrix::init(2.0, (2,4));
-    /// let matrix2 = Matrix::init(2.0, (4,2));
-    ///
-    /// let result = matrix1.matmul(&matrix2).unwrap();
+    /// let mat: Matrix<i32> = Matrix::new(vec![1, 2, 3, 4], (2,2)).unwrap();
     ///
-    /// assert_eq!(result.get(0,0).unwrap(), 16.0);
-    /// assert_eq!(result.shape(), (2,2));
+    /// assert_eq!(mat.cofactor_matrix().unwrap().get_vec(), vec![4, -3, -2, 1]);
     /// ```
-    pub fn matmul(&self, other: &Self) -> Result<Self, MatrixError> {
-        // assert M N x N P
-        if self.ncols != other.nrows {
-            return Err(MatrixError::MatrixDimensionMismatchError.into());
+    pub fn cofactor_matrix(&self) -> Option<Self> {
+        if self.nrows != self.ncols || self.nrows == 0 {
+            return None;
         }
 
-        Ok(self.matmul_helper(other))
-    }
+        let n = self.nrows;
+
+        if n == 1 {
+            return Some(Self::init(T::one(), (1, 1)));
+        }
+
+        let data: Vec<T> = iproduct!(0..n, 0..n)
+            .map(|(i, j)| {
+                let minor = Self::submatrix(self.data.clone(), n, i, j);
+                let det = Self::new(minor, (n - 1, n - 1))
+                    .unwrap()
+                    .determinant_helper();
+
+                if (i + j) % 2 == 0 {
+                    det
+                } else {
+                    -det
+                }
+            })
+            .collect();
 
-    /// Shorthand method for matmul
-    pub fn mm(&self, other: &Self) -> Result<Self, MatrixError> {
-        self.matmul(other)
+        Some(Self::new(data, (n, n)).unwrap())
     }
 
-    /// Get's the determinat of a N x N matrix
+    /// Computes the adjugate of a N x N matrix,
+    /// which is the transpose of the cofactor matrix.
+    ///
+    /// Returns None if the matrix is not square
     ///
     /// Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let mat: Matrix<i32> = Matrix::new(vec![1,3,5,9,1,3,1,7,4,3,9,7,5,2,0,9], (4,4)).unwrap();
-    ///
+    /// let mat: Matrix<i32> = Matrix::new(vec![1, 2, 3, 4], (2,2)).unwrap();
     ///
-    /// let res = mat.determinant().unwrap();
-    ///
-    /// assert_eq!(res, -376);
+    /// assert_eq!(mat.adjugate().unwrap().get_vec(), vec![4, -2, -3, 1]);
     /// ```
-    pub fn determinant(&self) -> Option<T> {
-        if self.nrows != self.ncols {
-            return None;
-        }
-
-        Some(self.determinant_helper())
-    }
-
-    /// Shorthand call for `determinant`
-    pub fn det(&self) -> Option<T> {
-        self.determinant()
+    pub fn adjugate(&self) -> Option<Self> {
+        self.cofactor_matrix().map(|c| c.transpose_copy())
     }
 
-    /// Finds the inverse of a matrix if possible
+    /// Finds the inverse by dividing the adjugate by the determinant.
     ///
-    /// Definition: AA^-1 = A^-1A = I
+    /// Useful for exact inverses of small integer matrices.
+    /// Returns None if the matrix is not square or is singular.
+    /// For integer types it is also None if some element of the adjugate
+    /// is not evenly divisible by the determinant, as the inverse is then
+    /// not representable in T. Floats are divided as is
     ///
     /// Examples
     ///
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let matrix = Matrix::new(vec![4,7,2,6], (2,2)).unwrap();
+    /// let mat: Matrix<i32> = Matrix::new(vec![2, 1, 1, 1], (2,2)).unwrap();
     ///
-    /// // let inverse  = matrix.inverse();
+    /// assert_eq!(mat.inverse_adjugate().unwrap().get_vec(), vec![1, -1, -1, 2]);
     ///
+    /// let mat: Matrix<i32> = Matrix::new(vec![1, 2, 3, 4], (2,2)).unwrap();
+    ///
+    /// assert_eq!(mat.inverse_adjugate(), None);
     /// ```
-    pub fn inverse(&self) -> Option<Self> {
-        if self.shape() != (2, 2) {
-            eprintln!("Function not implemented for inverse on larger matrices yet!");
-            return None;
-        }
-        if self.nrows != self.ncols {
-            eprintln!("Oops");
-            return None;
-        }
+    pub fn inverse_adjugate(&self) -> Option<Self> {
+        let det = self.determinant()?;
 
-        if self.determinant().unwrap() == T::zero() {
+        if det == T::zero() {
             return None;
         }
 
-        let a = self.at(0, 0);
-        let b = self.at(0, 1);
-        let c = self.at(1, 0);
-        let d = self.at(1, 1);
-
-        let mut mat = Self::new(vec![d, -b, -c, a], self.shape()).unwrap();
-
-        mat.mul_val_self(T::one() / (a * d - b * c));
+        let adj = self.adjugate()?;
 
-        return Some(mat);
+        if Self::is_integral() && adj.data.iter().any(|&e| (e / det) * det != e) {
+            return None;
+        }
 
-        // let mut inverse = Self::zeros_like(self);
-        //
-        // let identity_mat = Self::eye_like(self);
-        //
-        // Some(inverse)
+        Some(adj.div_val(det))
     }
 
     /// Transpose a matrix in-place
@@ -2089,19 +5479,20 @@ where
     /// ```
     /// use sukker::Matrix;
     ///
-    /// let mut matrix = Matrix::init(2.0, (2,100));
+    /// let mut matrix = Matrix::new(vec![1, 2, 3, 4, 5, 6], (2,3)).unwrap();
     /// matrix.transpose();
     ///
-    /// assert_eq!(matrix.shape(), (100,2));
+    /// assert_eq!(matrix.shape(), (3,2));
+    /// assert_eq!(matrix.get_vec(), vec![1, 4, 2, 5, 3, 6]);
     /// ```
     pub fn transpose(&mut self) {
-        for i in 0..self.nrows {
-            for j in (i + 1)..self.ncols {
-                let lhs = at!(i, j, self.ncols);
-                let rhs = at!(j, i, self.nrows);
-                self.data.swap(lhs, rhs);
-            }
-        }
+        let (nrows, ncols) = self.shape();
+
+        // Element (j, i) of the transposed matrix is (i, j) of the original
+        self.data = (0..self.size())
+            .into_par_iter()
+            .map(|idx| self.data[at!(idx % nrows, idx / nrows, ncols)])
+            .collect();
 
         swap(&mut self.nrows, &mut self.ncols);
     }
@@ -2139,6 +5530,52 @@ where
         res.transpose();
         res
     }
+
+    /// Computes the Gram matrix `Aᵀ·A` of a matrix.
+    ///
+    /// The result is always square with shape `ncols x ncols`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::<i32>::new(vec![1, 2, 3, 4, 5, 6], (3,2)).unwrap();
+    ///
+    /// let gram = matrix.gram();
+    ///
+    /// assert_eq!(gram.shape(), (2,2));
+    /// assert_eq!(gram.get_vec(), vec![35, 44, 44, 56]);
+    /// ```
+    pub fn gram(&self) -> Self
+    where
+        T: 'static,
+    {
+        self.transpose_copy().matmul(self).unwrap()
+    }
+
+    /// Computes the outer Gram matrix `A·Aᵀ` of a matrix.
+    ///
+    /// The result is always square with shape `nrows x nrows`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let matrix = Matrix::<i32>::new(vec![1, 2, 3, 4, 5, 6], (3,2)).unwrap();
+    ///
+    /// let gram = matrix.gram_outer();
+    ///
+    /// assert_eq!(gram.shape(), (3,3));
+    /// assert_eq!(gram.get_vec(), vec![5, 11, 17, 11, 25, 39, 17, 39, 61]);
+    /// ```
+    pub fn gram_outer(&self) -> Self
+    where
+        T: 'static,
+    {
+        self.matmul(&self.transpose_copy()).unwrap()
+    }
 }
 
 /// Implementations for predicates
@@ -2308,4 +5745,319 @@ where
             Some(data)
         }
     }
+
+    /// Checks if two matrices have the same shape, regardless of data
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let a = Matrix::init(2.0, (2,3));
+    /// let b = Matrix::init(5.0, (2,3));
+    ///
+    /// assert!(a.same_shape(&b));
+    /// assert!(!a.same_shape(&Matrix::init(2.0, (3,2))));
+    /// ```
+    pub fn same_shape(&self, other: &Self) -> bool {
+        self.shape() == other.shape()
+    }
+
+    /// Checks if two matrices hold the same data in the same order,
+    /// regardless of their shapes.
+    ///
+    /// Unlike `==`, a 2x3 and a 3x2 matrix can be equal here
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let a = Matrix::new(vec![1, 2, 3, 4, 5, 6], (2,3)).unwrap();
+    /// let b = Matrix::new(vec![1, 2, 3, 4, 5, 6], (3,2)).unwrap();
+    ///
+    /// assert!(a.reshaped_eq(&b));
+    /// assert_ne!(a, b);
+    /// ```
+    pub fn reshaped_eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+
+    /// Checks if two matrices have the same shape and the same elements,
+    /// treating NaNs in the same position as equal.
+    ///
+    /// Unlike `==`, a matrix containing NaN is structurally equal to itself.
+    /// Other values compare as usual, so 0.0 and -0.0 are still equal
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let a = Matrix::new(vec![1.0, f64::NAN, 3.0, 4.0], (2,2)).unwrap();
+    ///
+    /// assert!(a.structural_eq(&a.clone()));
+    /// assert_ne!(a, a.clone());
+    /// ```
+    pub fn structural_eq(&self, other: &Self) -> bool {
+        // NaN is the only value not comparable to itself
+        let is_nan = |x: &T| x.partial_cmp(x).is_none();
+
+        self.same_shape(other)
+            && self
+                .data
+                .iter()
+                .zip(&other.data)
+                .all(|(x, y)| x == y || (is_nan(x) && is_nan(y)))
+    }
+
+    /// Compares two matrices element-wise, giving a mask with 1 where
+    /// self is greater than other, and 0 elsewhere.
+    ///
+    /// Masks are `Matrix<i8>`, as matrix elements have to be signed.
+    /// Returns MatrixDimensionMismatchError if the shapes differ
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let a = Matrix::new(vec![3, 2, 1, 5], (2,2)).unwrap();
+    /// let b = Matrix::new(vec![1, 2, 4, 2], (2,2)).unwrap();
+    ///
+    /// assert_eq!(a.gt_elem(&b).unwrap().get_vec(), vec![1, 0, 0, 1]);
+    /// ```
+    pub fn gt_elem(&self, other: &Self) -> Result<Matrix<'a, i8>, MatrixError> {
+        self.compare_mask(other, |x, y| x > y)
+    }
+
+    /// Compares two matrices element-wise, giving a mask with 1 where
+    /// self is less than other, and 0 elsewhere.
+    ///
+    /// Returns MatrixDimensionMismatchError if the shapes differ
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let a = Matrix::new(vec![3, 2, 1, 5], (2,2)).unwrap();
+    /// let b = Matrix::new(vec![1, 2, 4, 2], (2,2)).unwrap();
+    ///
+    /// assert_eq!(a.lt_elem(&b).unwrap().get_vec(), vec![0, 0, 1, 0]);
+    /// ```
+    pub fn lt_elem(&self, other: &Self) -> Result<Matrix<'a, i8>, MatrixError> {
+        self.compare_mask(other, |x, y| x < y)
+    }
+
+    /// Compares two matrices element-wise, giving a mask with 1 where
+    /// self is greater than or equal to other, and 0 elsewhere.
+    ///
+    /// Returns MatrixDimensionMismatchError if the shapes differ
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let a = Matrix::new(vec![3, 2, 1, 5], (2,2)).unwrap();
+    /// let b = Matrix::new(vec![1, 2, 4, 2], (2,2)).unwrap();
+    ///
+    /// assert_eq!(a.ge_elem(&b).unwrap().get_vec(), vec![1, 1, 0, 1]);
+    /// ```
+    pub fn ge_elem(&self, other: &Self) -> Result<Matrix<'a, i8>, MatrixError> {
+        self.compare_mask(other, |x, y| x >= y)
+    }
+
+    /// Compares two matrices element-wise, giving a mask with 1 where
+    /// self is less than or equal to other, and 0 elsewhere.
+    ///
+    /// Returns MatrixDimensionMismatchError if the shapes differ
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let a = Matrix::new(vec![3, 2, 1, 5], (2,2)).unwrap();
+    /// let b = Matrix::new(vec![1, 2, 4, 2], (2,2)).unwrap();
+    ///
+    /// assert_eq!(a.le_elem(&b).unwrap().get_vec(), vec![0, 1, 1, 0]);
+    /// ```
+    pub fn le_elem(&self, other: &Self) -> Result<Matrix<'a, i8>, MatrixError> {
+        self.compare_mask(other, |x, y| x <= y)
+    }
+
+    /// Compares two matrices element-wise, giving a mask with 1 where
+    /// the elements are equal, and 0 elsewhere.
+    ///
+    /// Returns MatrixDimensionMismatchError if the shapes differ
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let a = Matrix::new(vec![3, 2, 1, 5], (2,2)).unwrap();
+    /// let b = Matrix::new(vec![1, 2, 4, 2], (2,2)).unwrap();
+    ///
+    /// assert_eq!(a.eq_elem(&b).unwrap().get_vec(), vec![0, 1, 0, 0]);
+    /// ```
+    pub fn eq_elem(&self, other: &Self) -> Result<Matrix<'a, i8>, MatrixError> {
+        self.compare_mask(other, |x, y| x == y)
+    }
+
+    /// Gives a mask with 1 where an element is greater than val, and 0 elsewhere
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let a = Matrix::new(vec![3, 2, 1, 5], (2,2)).unwrap();
+    ///
+    /// assert_eq!(a.gt_val(2).get_vec(), vec![1, 0, 0, 1]);
+    /// ```
+    pub fn gt_val(&self, val: T) -> Matrix<'a, i8> {
+        let data: Vec<i8> = self.data.par_iter().map(|&e| (e > val) as i8).collect();
+
+        Matrix::<i8>::new(data, self.shape()).unwrap()
+    }
+
+    /// Gives a mask with 1 where an element is less than val, and 0 elsewhere
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let a = Matrix::new(vec![3, 2, 1, 5], (2,2)).unwrap();
+    ///
+    /// assert_eq!(a.lt_val(2).get_vec(), vec![0, 0, 1, 0]);
+    /// ```
+    pub fn lt_val(&self, val: T) -> Matrix<'a, i8> {
+        let data: Vec<i8> = self.data.par_iter().map(|&e| (e < val) as i8).collect();
+
+        Matrix::<i8>::new(data, self.shape()).unwrap()
+    }
+
+    /// Gives a mask with 1 where an element is greater than or equal to val, and 0 elsewhere
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let a = Matrix::new(vec![3, 2, 1, 5], (2,2)).unwrap();
+    ///
+    /// assert_eq!(a.ge_val(2).get_vec(), vec![1, 1, 0, 1]);
+    /// ```
+    pub fn ge_val(&self, val: T) -> Matrix<'a, i8> {
+        let data: Vec<i8> = self.data.par_iter().map(|&e| (e >= val) as i8).collect();
+
+        Matrix::<i8>::new(data, self.shape()).unwrap()
+    }
+
+    /// Gives a mask with 1 where an element is less than or equal to val, and 0 elsewhere
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let a = Matrix::new(vec![3, 2, 1, 5], (2,2)).unwrap();
+    ///
+    /// assert_eq!(a.le_val(2).get_vec(), vec![0, 1, 1, 0]);
+    /// ```
+    pub fn le_val(&self, val: T) -> Matrix<'a, i8> {
+        let data: Vec<i8> = self.data.par_iter().map(|&e| (e <= val) as i8).collect();
+
+        Matrix::<i8>::new(data, self.shape()).unwrap()
+    }
+
+    /// Gives a mask with 1 where an element is equal to val, and 0 elsewhere
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let a = Matrix::new(vec![3, 2, 1, 5], (2,2)).unwrap();
+    ///
+    /// assert_eq!(a.eq_val(2).get_vec(), vec![0, 1, 0, 0]);
+    /// ```
+    pub fn eq_val(&self, val: T) -> Matrix<'a, i8> {
+        let data: Vec<i8> = self.data.par_iter().map(|&e| (e == val) as i8).collect();
+
+        Matrix::<i8>::new(data, self.shape()).unwrap()
+    }
+
+    /// Builds a matrix of the same shape as self, with if_true where the
+    /// mask is non-zero and if_false elsewhere. Typically used with the
+    /// masks from comparisons like `gt_val`, like numpy's `where`
+    ///
+    /// Returns MatrixDimensionMismatchError if the mask has a different shape
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let a = Matrix::new(vec![3, 2, 1, 5], (2,2)).unwrap();
+    ///
+    /// let res = a.select(&a.gt_val(2), 1, -1).unwrap();
+    ///
+    /// assert_eq!(res.get_vec(), vec![1, -1, -1, 1]);
+    /// ```
+    pub fn select(
+        &self,
+        mask: &Matrix<'a, i8>,
+        if_true: T,
+        if_false: T,
+    ) -> Result<Self, MatrixError> {
+        if mask.shape() != self.shape() {
+            return Err(MatrixError::MatrixDimensionMismatchError);
+        }
+
+        let data: Vec<T> = mask
+            .data
+            .par_iter()
+            .map(|&m| if m != 0 { if_true } else { if_false })
+            .collect();
+
+        Self::new(data, self.shape())
+    }
+
+    /// Picks element-wise from self where the mask is non-zero,
+    /// and from other elsewhere
+    ///
+    /// Returns MatrixDimensionMismatchError if the shapes differ
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::Matrix;
+    ///
+    /// let a = Matrix::new(vec![3, 2, 1, 5], (2,2)).unwrap();
+    /// let b = Matrix::new(vec![1, 2, 4, 2], (2,2)).unwrap();
+    ///
+    /// // Element-wise maximum
+    /// let res = a.blend(&b, &a.gt_elem(&b).unwrap()).unwrap();
+    ///
+    /// assert_eq!(res.get_vec(), vec![3, 2, 4, 5]);
+    /// ```
+    pub fn blend(&self, other: &Self, mask: &Matrix<'a, i8>) -> Result<Self, MatrixError> {
+        if !self.same_shape(other) || mask.shape() != self.shape() {
+            return Err(MatrixError::MatrixDimensionMismatchError);
+        }
+
+        let data: Vec<T> = mask
+            .data
+            .par_iter()
+            .zip(self.data.par_iter().zip(other.data.par_iter()))
+            .map(|(&m, (&x, &y))| if m != 0 { x } else { y })
+            .collect();
+
+        Self::new(data, self.shape())
+    }
 }
@@ -0,0 +1,128 @@
+//! Operator overloading for dense matrices.
+//!
+//! All arithmetic operators work on references and delegate to the
+//! corresponding methods. Since operators can not return a `Result`,
+//! element-wise operations on mismatched shapes will panic.
+//!
+//! Indexing is done with a `(row, col)` tuple, and panics if out of bounds.
+
+use std::{
+    error::Error,
+    ops::{Add, Div, Index, IndexMut, Mul, Neg, Sub},
+    str::FromStr,
+};
+
+use rayon::prelude::*;
+
+use crate::{at, Matrix, MatrixElement, Shape};
+
+// Generates element-wise matrix-matrix and matrix-scalar operators
+macro_rules! impl_matrix_op {
+    ($trait:ident, $fn:ident, $val_fn:ident) => {
+        impl<'a, T> $trait<&Matrix<'a, T>> for &Matrix<'a, T>
+        where
+            T: MatrixElement,
+            <T as FromStr>::Err: Error + 'static,
+            Vec<T>: IntoParallelIterator,
+            Vec<&'a T>: IntoParallelRefIterator<'a>,
+        {
+            type Output = Matrix<'a, T>;
+
+            fn $fn(self, rhs: &Matrix<'a, T>) -> Self::Output {
+                match Matrix::$fn(self, rhs) {
+                    Ok(res) => res,
+                    Err(e) => panic!(
+                        "Can not {} matrices of shape {:?} and {:?}: {}",
+                        stringify!($fn),
+                        self.shape(),
+                        rhs.shape(),
+                        e
+                    ),
+                }
+            }
+        }
+
+        impl<'a, T> $trait<T> for &Matrix<'a, T>
+        where
+            T: MatrixElement,
+            <T as FromStr>::Err: Error + 'static,
+            Vec<T>: IntoParallelIterator,
+            Vec<&'a T>: IntoParallelRefIterator<'a>,
+        {
+            type Output = Matrix<'a, T>;
+
+            fn $fn(self, rhs: T) -> Self::Output {
+                self.$val_fn(rhs)
+            }
+        }
+    };
+}
+
+impl_matrix_op!(Add, add, add_val);
+impl_matrix_op!(Sub, sub, sub_val);
+impl_matrix_op!(Mul, mul, mul_val);
+impl_matrix_op!(Div, div, div_val);
+
+impl<'a, T> Neg for &Matrix<'a, T>
+where
+    T: MatrixElement,
+    <T as FromStr>::Err: Error + 'static,
+    Vec<T>: IntoParallelIterator,
+    Vec<&'a T>: IntoParallelRefIterator<'a>,
+{
+    type Output = Matrix<'a, T>;
+
+    fn neg(self) -> Self::Output {
+        Matrix::neg(self)
+    }
+}
+
+impl<'a, T> Index<Shape> for Matrix<'a, T>
+where
+    T: MatrixElement,
+    <T as FromStr>::Err: Error + 'static,
+    Vec<T>: IntoParallelIterator,
+    Vec<&'a T>: IntoParallelRefIterator<'a>,
+{
+    type Output = T;
+
+    fn index(&self, idx: Shape) -> &Self::Output {
+        self.check_index(idx);
+
+        &self.data[at!(idx.0, idx.1, self.ncols)]
+    }
+}
+
+impl<'a, T> IndexMut<Shape> for Matrix<'a, T>
+where
+    T: MatrixElement,
+    <T as FromStr>::Err: Error + 'static,
+    Vec<T>: IntoParallelIterator,
+    Vec<&'a T>: IntoParallelRefIterator<'a>,
+{
+    fn index_mut(&mut self, idx: Shape) -> &mut Self::Output {
+        self.check_index(idx);
+
+        &mut self.data[at!(idx.0, idx.1, self.ncols)]
+    }
+}
+
+impl<'a, T> Matrix<'a, T>
+where
+    T: MatrixElement,
+    <T as FromStr>::Err: Error + 'static,
+    Vec<T>: IntoParallelIterator,
+    Vec<&'a T>: IntoParallelRefIterator<'a>,
+{
+    // Panics with the index and shape if the index is out of bounds
+    #[inline(always)]
+    fn check_index(&self, idx: Shape) {
+        if idx.0 >= self.nrows || idx.1 >= self.ncols {
+            panic!(
+                "Index {:?} is out of bounds for matrix of shape {:?}",
+                idx,
+                self.shape()
+            );
+        }
+    }
+}
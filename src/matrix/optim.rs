@@ -1,31 +1,37 @@
-// Helper module for defining how to be able to
+// Helper module for dispatching matmuls to SIMD kernels
+// when the target supports them at runtime
 
-use std::{arch::is_aarch64_feature_detected, error::Error, str::FromStr};
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+use std::any::TypeId;
+use std::{error::Error, str::FromStr};
 
 use crate::{Matrix, MatrixElement};
 
-pub fn get_optimized_matmul<T>(lhs: Matrix<T>, other: Matrix<T>)
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+use rayon::prelude::*;
+
+/// Returns the product from a SIMD kernel if one exists for
+/// the element type and the running CPU, otherwise None so
+/// the caller can fall back to the scalar matmuls
+#[allow(unused_variables)]
+pub(crate) fn get_optimized_matmul<'a, T>(
+    lhs: &Matrix<'a, T>,
+    other: &Matrix<'a, T>,
+) -> Option<Matrix<'a, T>>
 where
-    T: MatrixElement,
+    T: MatrixElement + 'static,
     <T as FromStr>::Err: Error,
 {
-    #[cfg(any(target_arch = "x86", target_arch = "x86-64"))]
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
     {
-        if is_x86_feature_detected!("avx2") {
-            unsafe { /*self.avx_matmul(other) */
-            }
-        } else if is_x86_feature_detected!("sse") {
-            unsafe { /* sse_avx_matmul*/
-            }
+        if TypeId::of::<T>() == TypeId::of::<f64>() && is_x86_feature_detected!("avx2") {
+            // SAFETY: avx2 support was just detected, and the TypeId
+            // check guarantees that T is f64
+            return Some(unsafe { avx_matmul(lhs, other) });
         }
     }
 
-    #[cfg(any(target_arch = "aarch64"))]
-    {
-        if is_aarch64_feature_detected!("avx2") {
-            unsafe {}
-        }
-    }
+    None
 }
 
 // =====================================================================
@@ -36,63 +42,75 @@ where
 
 /// AVX matmul for the IEEE754 Double Precision Floating Point Datatype
 /// https://www.akkadia.org/drepper/cpumemory.pdf
+///
+/// Every output row is computed independently, broadcasting
+/// one element of the lhs row and accumulating 4 columns
+/// of the rhs row at a time.
+///
+/// # Safety
+///
+/// The CPU has to support avx2, and T has to be f64
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
-#[target_feature(enable = "avx2")]
 unsafe fn avx_matmul<'a, T>(lhs: &Matrix<'a, T>, other: &Matrix<'a, T>) -> Matrix<'a, T>
 where
     T: MatrixElement,
     <T as FromStr>::Err: Error,
 {
+    let n = lhs.ncols;
+    let p = other.ncols;
+
+    // SAFETY: T is f64, so the buffers have the same layout
+    let a = std::slice::from_raw_parts(lhs.data.as_ptr() as *const f64, lhs.data.len());
+    let b = std::slice::from_raw_parts(other.data.as_ptr() as *const f64, other.data.len());
+
+    let mut data = vec![T::zero(); lhs.nrows * p];
+
+    data.par_chunks_mut(p.max(1))
+        .enumerate()
+        .for_each(|(i, row)| {
+            // SAFETY: Same as above, and avx2 is guaranteed by the caller
+            unsafe {
+                let out = std::slice::from_raw_parts_mut(row.as_mut_ptr() as *mut f64, row.len());
+                avx_row_f64(&a[i * n..(i + 1) * n], b, p, out);
+            }
+        });
+
+    Matrix::new(data, (lhs.nrows, p)).unwrap()
+}
+
+/// Computes out = a_row @ b for a single output row
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn avx_row_f64(a_row: &[f64], b: &[f64], p: usize, out: &mut [f64]) {
     #[cfg(target_arch = "x86")]
-    use std::arch::x86::_mm256_add_epi64;
+    use std::arch::x86::{
+        _mm256_add_pd, _mm256_loadu_pd, _mm256_mul_pd, _mm256_set1_pd, _mm256_storeu_pd,
+    };
     #[cfg(target_arch = "x86_64")]
     use std::arch::x86_64::{
-        __m128d, _mm256_add_epi64, _mm_add_pd, _mm_load_pd, _mm_mul_pd, _mm_prefetch, _mm_store_pd,
-        _mm_unpacklo_pd, _MM_HINT_NTA,
+        _mm256_add_pd, _mm256_loadu_pd, _mm256_mul_pd, _mm256_set1_pd, _mm256_storeu_pd,
     };
 
-    // let N = self.nrows;
-    //
-    // let res = [0.0; 4];
-    // let mul1 = [0.0; 4];
-    // let mul2 = [0.0; 4];
-    //
-    // let CLS = 420;
-    //
-    // let SM = CLS / size_of::<f64>();
-    //
-    // let mut rres: *const f64;
-    // let mut rmul1: *const f64;
-    // let mut rmul2: *const f64;
-    //
-    // for i in (0..N).step_by(SM) {
-    //     for j in (0..N).step_by(SM) {
-    //         for k in (0..N).step_by(SM) {
-    //             for i2 in 0..SM {
-    //                 _mm_prefetch(&rmul1[8]);
-    //
-    //                 for k2 in 0..SM {
-    //                     rmul2 = 1.0;
-    //
-    //                     // load m1d
-    //                     let m1d: __m128d = _mm_load_pd(rmul1);
-    //
-    //                     for j2 in (0..SM).step_by(2) {
-    //                         let m2: __m128d = _mm_load_pd(rmul2);
-    //                         let r2: __m128d = _mm_load_pd(rres);
-    //                         _mm_store_pd(rres, _mm_add_pd(_mm_mul_pd(m2, m1d), r2));
-    //
-    //                         // Inner most computations
-    //                     }
-    //
-    //                     rmul2 += N as f64;
-    //                 }
-    //             }
-    //         }
-    //     }
-    // }
-
-    Matrix::default()
+    let wide = p - p % 4;
+
+    for (k, &aik) in a_row.iter().enumerate() {
+        let b_row = &b[k * p..(k + 1) * p];
+        let av = _mm256_set1_pd(aik);
+
+        for j in (0..wide).step_by(4) {
+            let bv = _mm256_loadu_pd(b_row.as_ptr().add(j));
+            let cv = _mm256_loadu_pd(out.as_ptr().add(j));
+            _mm256_storeu_pd(
+                out.as_mut_ptr().add(j),
+                _mm256_add_pd(cv, _mm256_mul_pd(av, bv)),
+            );
+        }
+
+        // Scalar tail for the last p % 4 columns
+        for j in wide..p {
+            out[j] += aik * b_row[j];
+        }
+    }
 }
 
 // =====================================================================
@@ -1,11 +1,31 @@
 //! Internal helpers
 
-use std::{error::Error, str::FromStr};
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    str::FromStr,
+};
 
-use rayon::prelude::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterator};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::{MatrixElement, MatrixError, Operation, SparseMatrix, SparseMatrixData};
 
+// First line every Matrix Market file written by this crate starts with
+pub(crate) const MATRIX_MARKET_HEADER: &str = "%%MatrixMarket matrix coordinate real general";
+
+// Largest number of rows and cols a sparse matrix is printed densely with
+pub(crate) const DISPLAY_DENSE_MAX_DIM: usize = 16;
+
+// JSON schema of a sparse matrix, entries are (row, col, value)
+// triplets in row-major order
+#[derive(Serialize, Deserialize)]
+pub(crate) struct SparseMatrixJson<T> {
+    pub(crate) nrows: usize,
+    pub(crate) ncols: usize,
+    pub(crate) entries: Vec<(usize, usize, T)>,
+}
+
 pub fn swap(lhs: &mut usize, rhs: &mut usize) {
     let temp = *lhs;
     *lhs = *rhs;
@@ -19,6 +39,83 @@ where
     Vec<T>: IntoParallelIterator,
     Vec<&'a T>: IntoParallelRefIterator<'a>,
 {
+    // Parses the coordinate variant of the Matrix Market format.
+    // Only general real or integer matrices are supported
+    pub(crate) fn parse_matrix_market(content: &str) -> Result<Self, MatrixError> {
+        let mut lines = content.lines();
+
+        let header = lines
+            .next()
+            .ok_or(MatrixError::MatrixParseError)?
+            .to_lowercase();
+
+        let banner: Vec<&str> = header.split_whitespace().collect();
+
+        match banner.as_slice() {
+            ["%%matrixmarket", "matrix", "coordinate", "real" | "integer", "general"] => {}
+            _ => return Err(MatrixError::MatrixParseError),
+        }
+
+        let mut lines = lines
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('%'));
+
+        let sizes: Vec<usize> = lines
+            .next()
+            .ok_or(MatrixError::MatrixParseError)?
+            .split_whitespace()
+            .map(|s| {
+                s.parse::<usize>()
+                    .map_err(|_| MatrixError::MatrixParseError)
+            })
+            .collect::<Result<_, _>>()?;
+
+        let [nrows, ncols, nnz] = sizes[..] else {
+            return Err(MatrixError::MatrixParseError);
+        };
+
+        // nnz comes from the file, so it is only checked, never allocated from
+        let mut data: SparseMatrixData<T> = HashMap::new();
+        let mut seen: HashSet<(usize, usize)> = HashSet::new();
+
+        for line in lines {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+
+            let [i, j, val] = parts[..] else {
+                return Err(MatrixError::MatrixParseError);
+            };
+
+            let i = i
+                .parse::<usize>()
+                .map_err(|_| MatrixError::MatrixParseError)?;
+            let j = j
+                .parse::<usize>()
+                .map_err(|_| MatrixError::MatrixParseError)?;
+            let val = val
+                .parse::<T>()
+                .map_err(|_| MatrixError::MatrixParseError)?;
+
+            // Indices are 1-based
+            if i == 0 || j == 0 || i > nrows || j > ncols {
+                return Err(MatrixError::MatrixParseError);
+            }
+
+            if !seen.insert((i - 1, j - 1)) {
+                return Err(MatrixError::MatrixParseError);
+            }
+
+            if val != T::zero() {
+                data.insert((i - 1, j - 1), val);
+            }
+        }
+
+        if seen.len() != nnz {
+            return Err(MatrixError::MatrixParseError);
+        }
+
+        Ok(Self::new(data, (nrows, ncols)))
+    }
+
     // Helper for add, sub, mul and div on SparseMatrix - SparseMatrix operation
     #[doc(hidden)]
     pub fn sparse_helper(&self, other: &Self, op: Operation) -> Result<Self, MatrixError> {
@@ -28,44 +125,120 @@ where
 
         let mut result_mat = Self::init(self.nrows, self.ncols);
 
-        for (&idx, &val) in self.data.iter() {
-            result_mat.set(val, idx);
-        }
+        match op {
+            // Union of the non-zeros
+            Operation::ADD | Operation::SUB => {
+                for (&idx, &val) in self.data.iter() {
+                    result_mat.set(val, idx);
+                }
 
-        for (&idx, &val) in other.data.iter() {
-            match result_mat.data.get_mut(&idx) {
-                Some(value) => match op {
-                    Operation::ADD => *value += val,
-                    Operation::SUB => *value += val,
-                    Operation::MUL => *value += val,
-                    Operation::DIV => *value += val,
-                },
-                None => result_mat.set(val, idx),
-            };
+                for (&idx, &val) in other.data.iter() {
+                    match result_mat.data.get_mut(&idx) {
+                        Some(value) => match op {
+                            Operation::SUB => *value -= val,
+                            _ => *value += val,
+                        },
+                        // Only present in other, so self is an implicit zero here
+                        None => match op {
+                            Operation::SUB => result_mat.set(-val, idx),
+                            _ => result_mat.set(val, idx),
+                        },
+                    };
+
+                    // Don't store explicit zeros
+                    if result_mat.data.get(&idx) == Some(&T::zero()) {
+                        result_mat.data.remove(&idx);
+                    }
+                }
+            }
+            // Intersection of the non-zeros
+            Operation::MUL => {
+                for (&idx, &val) in self.data.iter() {
+                    if let Some(&other_val) = other.data.get(&idx) {
+                        let product = val * other_val;
+
+                        // Don't store values that underflow to zero
+                        if product != T::zero() {
+                            result_mat.data.insert(idx, product);
+                        }
+                    }
+                }
+            }
+            // Defined over the non-zeros of self, which all
+            // need a non-zero divisor in other
+            Operation::DIV => {
+                for (&idx, &val) in self.data.iter() {
+                    match other.data.get(&idx) {
+                        Some(&other_val) if other_val != T::zero() => {
+                            let quotient = val / other_val;
+
+                            // Don't store values that truncate to zero
+                            if quotient != T::zero() {
+                                result_mat.data.insert(idx, quotient);
+                            }
+                        }
+                        _ => return Err(MatrixError::MatrixDivideByZeroError),
+                    }
+                }
+            }
         }
 
         Ok(result_mat)
     }
 
     #[doc(hidden)]
-    pub fn sparse_helper_self(&mut self, other: &Self, op: Operation) {
-        // TODO: Mismatch in dimensions might not be an issue? Find out
+    pub fn sparse_helper_self(&mut self, other: &Self, op: Operation) -> Result<(), MatrixError> {
         if self.shape() != other.shape() {
-            eprintln!("Oops, mismatch in dims");
-            return;
+            return Err(MatrixError::MatrixDimensionMismatchError);
         }
 
-        for (&idx, &val) in other.data.iter() {
-            match self.data.get_mut(&idx) {
-                Some(value) => match op {
-                    Operation::ADD => *value += val,
-                    Operation::SUB => *value -= val,
-                    Operation::MUL => *value *= val,
-                    Operation::DIV => *value /= val,
-                },
-                None => self.set(val, idx),
-            };
+        match op {
+            Operation::ADD | Operation::SUB => {
+                for (&idx, &val) in other.data.iter() {
+                    match self.data.get_mut(&idx) {
+                        Some(value) => match op {
+                            Operation::SUB => *value -= val,
+                            _ => *value += val,
+                        },
+                        // Only present in other, so self is an implicit zero here
+                        None => match op {
+                            Operation::SUB => self.set(-val, idx),
+                            _ => self.set(val, idx),
+                        },
+                    };
+
+                    // Don't store explicit zeros
+                    if self.data.get(&idx) == Some(&T::zero()) {
+                        self.data.remove(&idx);
+                    }
+                }
+            }
+            Operation::MUL => {
+                self.data.retain(|idx, value| match other.data.get(idx) {
+                    Some(&other_val) => {
+                        *value *= other_val;
+                        *value != T::zero()
+                    }
+                    None => false,
+                });
+            }
+            Operation::DIV => {
+                if self
+                    .data
+                    .keys()
+                    .any(|idx| other.data.get(idx).is_none_or(|&val| val == T::zero()))
+                {
+                    return Err(MatrixError::MatrixDivideByZeroError);
+                }
+
+                self.data.retain(|idx, value| {
+                    *value /= other.data[idx];
+                    *value != T::zero()
+                });
+            }
         }
+
+        Ok(())
     }
 
     #[doc(hidden)]
@@ -108,55 +281,45 @@ where
     // For nn x nn
     #[doc(hidden)]
     pub fn matmul_sparse_nn(&self, other: &Self) -> Self {
-        // For now, more or less same as mn np
-        let N = self.nrows;
-
-        let data: SparseMatrixData<T> = (0..N)
-            .flat_map(|i| (0..N).map(move |j| (i, j)))
-            .collect::<Vec<(usize, usize)>>()
-            .into_par_iter()
-            .filter_map(|(i, j)| {
-                if self.at(i, j) == T::zero() {
-                    return None;
-                }
-
-                let result = (0..N)
-                    .into_par_iter()
-                    .map(|k| self.at(i, j) * other.at(j, k))
-                    .sum();
-
-                Some(((i, j), result))
-            })
-            .collect();
-
-        Self::new(data, (N, N))
+        // For now, same as mn np
+        self.matmul_sparse_mnnp(other)
     }
 
     // mn x np
+    //
+    // Every stored (i, k) in self is multiplied with every stored (k, j)
+    // in other, and accumulated into (i, j). Rows of other are grouped
+    // first, so only the columns actually present in row k are visited
     #[doc(hidden)]
     pub fn matmul_sparse_mnnp(&self, other: &Self) -> Self {
-        let x = self.nrows;
-        let y = self.ncols;
-        let z = other.ncols;
-
-        let data: SparseMatrixData<T> = (0..x)
-            .flat_map(|i| (0..y).map(move |j| (i, j)))
-            .collect::<Vec<(usize, usize)>>()
-            .into_par_iter()
-            .filter_map(|(i, j)| {
-                if self.at(i, j) == T::zero() {
-                    return None;
-                }
+        let mut other_rows: HashMap<usize, Vec<(usize, T)>> = HashMap::new();
 
-                let result = (0..z)
-                    .into_par_iter()
-                    .map(|k| self.at(i, j) * other.at(j, k))
-                    .sum();
+        for (&(k, j), &val) in other.data.iter() {
+            other_rows.entry(k).or_default().push((j, val));
+        }
 
-                Some(((i, j), result))
+        let data: SparseMatrixData<T> = self
+            .data
+            .par_iter()
+            .fold(SparseMatrixData::new, |mut acc, (&(i, k), &a)| {
+                if let Some(row) = other_rows.get(&k) {
+                    for &(j, b) in row {
+                        *acc.entry((i, j)).or_insert_with(T::zero) += a * b;
+                    }
+                }
+                acc
+            })
+            .reduce(SparseMatrixData::new, |mut lhs, rhs| {
+                for (idx, val) in rhs {
+                    *lhs.entry(idx).or_insert_with(T::zero) += val;
+                }
+                lhs
             })
+            .into_iter()
+            // Products that cancel out should not be stored
+            .filter(|&(_, val)| val != T::zero())
             .collect();
 
-        Self::new(data, (x, z))
+        Self::new(data, (self.nrows, other.ncols))
     }
 }
@@ -17,19 +17,26 @@
 
 mod helper;
 
+use anyhow::bail;
 use helper::*;
 use num_traits::Float;
-use rand::Rng;
+use rand::{rngs::StdRng, Rng, SeedableRng};
 
 use itertools::Itertools;
 use std::fmt::Display;
 use std::fs;
-use std::{collections::HashMap, error::Error, marker::PhantomData, str::FromStr};
+use std::path::Path;
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    marker::PhantomData,
+    str::FromStr,
+};
 
 use rayon::prelude::*;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
-use crate::{at, LinAlgFloats, Matrix, MatrixElement, MatrixError, Operation, Shape};
+use crate::{Dimension, LinAlgFloats, Matrix, MatrixElement, MatrixError, Operation, Shape};
 
 /// SparseMatrixData represents the datatype used to store information
 /// about non-zero values in a general matrix.
@@ -89,33 +96,118 @@ where
     <T as FromStr>::Err: Error + 'static,
 {
     type Err = anyhow::Error;
+
+    /// Parses a header line `nrows ncols` followed by one
+    /// `row col value` entry per line, with 0-based indexes.
+    ///
+    /// Blank lines before the header and after the last entry are ignored.
+    /// Missing headers, malformed lines, duplicate indexes and indexes
+    /// outside of the shape give an error with the 1-based line number.
+    /// Zero values are not stored, same as in `from_json`
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        // Parse the input string and construct the matrix dynamically
-        let data = s
-            .trim()
-            .lines()
-            .skip(1)
-            .map(|l| {
-                let entry: Vec<&str> = l.split_whitespace().collect();
-
-                let row = entry[0].parse::<usize>().unwrap();
-                let col = entry[1].parse::<usize>().unwrap();
-                let val = entry[2].parse::<T>().unwrap();
-
-                ((row, col), val)
-            })
-            .collect::<SparseMatrixData<T>>();
+        let all_lines: Vec<&str> = s.lines().collect();
+        let last = all_lines
+            .iter()
+            .rposition(|line| !line.trim().is_empty())
+            .map_or(0, |i| i + 1);
+
+        // Blank lines are skipped instead of trimmed away,
+        // so i + 1 stays the real line number
+        let mut lines = all_lines[..last]
+            .iter()
+            .enumerate()
+            .skip_while(|(_, line)| line.trim().is_empty());
+
+        let header_line = lines.clone().next().map_or(1, |(i, _)| i + 1);
+
+        let dims = match lines.next() {
+            Some((_, header)) => header
+                .split_whitespace()
+                .map(|e| e.parse::<usize>())
+                .collect::<Result<Vec<usize>, _>>()
+                .ok()
+                .filter(|dims| dims.len() == 2),
+            None => None,
+        };
+
+        let (nrows, ncols) = match dims {
+            Some(dims) => (dims[0], dims[1]),
+            None => bail!("Line {} has to be the shape as 'nrows ncols'", header_line),
+        };
+
+        let mut data: SparseMatrixData<T> = HashMap::new();
+        let mut seen: HashSet<Shape> = HashSet::new();
+
+        for (i, line) in lines {
+            let entry: Vec<&str> = line.split_whitespace().collect();
+
+            if entry.len() != 3 {
+                bail!(
+                    "Line {} has {} values, expected 'row col value'",
+                    i + 1,
+                    entry.len()
+                );
+            }
 
-        let dims = s
-            .trim()
-            .lines()
-            .nth(0)
-            .unwrap()
-            .split_whitespace()
-            .map(|e| e.parse::<usize>().unwrap())
-            .collect::<Vec<usize>>();
+            let (row, col) = match (entry[0].parse::<usize>(), entry[1].parse::<usize>()) {
+                (Ok(row), Ok(col)) => (row, col),
+                _ => bail!(
+                    "Invalid index '{} {}' at line {}",
+                    entry[0],
+                    entry[1],
+                    i + 1
+                ),
+            };
+
+            if row >= nrows || col >= ncols {
+                bail!(
+                    "Index ({}, {}) at line {} is out of bounds for shape ({}, {})",
+                    row,
+                    col,
+                    i + 1,
+                    nrows,
+                    ncols
+                );
+            }
+
+            let val = match entry[2].parse::<T>() {
+                Ok(val) => val,
+                Err(e) => bail!("Invalid value '{}' at line {}: {}", entry[2], i + 1, e),
+            };
+
+            if !seen.insert((row, col)) {
+                bail!("Duplicate index ({}, {}) at line {}", row, col, i + 1);
+            }
+
+            if val != T::zero() {
+                data.insert((row, col), val);
+            }
+        }
 
-        Ok(Self::new(data, (dims[0], dims[1])))
+        Ok(Self::new(data, (nrows, ncols)))
+    }
+}
+
+impl<'a, T> From<Matrix<'a, T>> for SparseMatrix<'a, T>
+where
+    T: MatrixElement,
+    <T as FromStr>::Err: Error + 'static,
+    Vec<T>: IntoParallelIterator,
+    Vec<&'a T>: IntoParallelRefIterator<'a>,
+{
+    /// Creates a sparse matrix from a dense one, same as `SparseMatrix::from_dense`
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use sukker::{Matrix, SparseMatrix};
+    ///
+    /// let sparse: SparseMatrix<i32> = Matrix::eye(3).into();
+    ///
+    /// assert_eq!(sparse.nnz(), 3);
+    /// ```
+    fn from(matrix: Matrix<'a, T>) -> Self {
+        Self::from_dense(matrix)
     }
 }
 
@@ -126,17 +218,34 @@ where
     Vec<T>: IntoParallelIterator,
     Vec<&'a T>: IntoParallelRefIterator<'a>,
 {
+    /// Small matrices are printed densely. Larger ones are printed
+    /// as a header with the shape and nnz, followed by one
+    /// `(i, j): value` line per stored entry in row-major order
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for i in 0..self.nrows {
-            for j in 0..self.ncols {
-                let elem = match self.data.get(&(i, j)) {
-                    Some(&val) => val,
-                    None => T::zero(),
-                };
-
-                write!(f, "{elem} ");
+        if self.nrows <= DISPLAY_DENSE_MAX_DIM && self.ncols <= DISPLAY_DENSE_MAX_DIM {
+            for i in 0..self.nrows {
+                for j in 0..self.ncols {
+                    let elem = match self.data.get(&(i, j)) {
+                        Some(&val) => val,
+                        None => T::zero(),
+                    };
+
+                    write!(f, "{elem} ")?;
+                }
+                writeln!(f)?;
+            }
+        } else {
+            writeln!(
+                f,
+                "SparseMatrix {}x{}, nnz = {}",
+                self.nrows,
+                self.ncols,
+                self.nnz()
+            )?;
+
+            for ((i, j), val) in self.entries_sorted() {
+                writeln!(f, "({i}, {j}): {val}")?;
             }
-            writeln!(f);
         }
         writeln!(f, "\ndtype = {}", std::any::type_name::<T>())
     }
@@ -235,6 +344,32 @@ where
         Self::new(data, (size, size))
     }
 
+    /// Creates a square sparse matrix with the given values on the diagonal.
+    ///
+    /// Only the non-zero values are stored
+    ///
+    /// Examples
+    ///
+    /// ```
+    /// use sukker::SparseMatrix;
+    ///
+    /// let sparse = SparseMatrix::<i32>::from_diagonal(&[2, 0, 4]);
+    ///
+    /// assert_eq!(sparse.shape(), (3, 3));
+    /// assert_eq!(sparse.at(2, 2), 4);
+    /// assert_eq!(sparse.data.len(), 2);
+    /// ```
+    pub fn from_diagonal(values: &[T]) -> Self {
+        let data: SparseMatrixData<'a, T> = values
+            .iter()
+            .enumerate()
+            .filter(|(_, &val)| val != T::zero())
+            .map(|(i, &val)| ((i, i), val))
+            .collect();
+
+        Self::new(data, (values.len(), values.len()))
+    }
+
     /// Produces an eye with the same shape as another
     /// sparse matrix
     pub fn eye_like(matrix: &Self) -> Self {
@@ -315,6 +450,38 @@ where
         Self::new(data, matrix.shape())
     }
 
+    /// Constructs a sparse matrix from a dense matrix,
+    /// only keeping values whose absolute value is larger than epsilon.
+    ///
+    /// Useful for dropping float noise that would otherwise be stored
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use sukker::{Matrix, SparseMatrix};
+    ///
+    /// let dense = Matrix::new(vec![1.0, 1e-12, -1e-13, 2.0], (2,2)).unwrap();
+    ///
+    /// let sparse = SparseMatrix::from_dense_threshold(dense, 1e-9);
+    ///
+    /// assert_eq!(sparse.nnz(), 2);
+    /// assert_eq!(sparse.get(0,1), Some(0.0));
+    /// ```
+    pub fn from_dense_threshold(matrix: Matrix<'a, T>, epsilon: T) -> Self {
+        let data: SparseMatrixData<'a, T> = matrix
+            .row_iter()
+            .enumerate()
+            .flat_map(|(i, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, val)| val.abs() > epsilon)
+                    .map(move |(j, &val)| ((i, j), val))
+            })
+            .collect();
+
+        Self::new(data, matrix.shape())
+    }
+
     /// Constructs a sparse matrix from 3 slices.
     /// One for the rows, one for the cols, and one for the value.
     /// A combination of values fromt the same index corresponds to
@@ -358,6 +525,40 @@ where
         Ok(Self::new(data, shape))
     }
 
+    /// Exports the sparse matrix to 3 slices of rows, cols and values,
+    /// the inverse of `from_slices`.
+    ///
+    /// The entries are sorted by (row, col), so the output is deterministic
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use sukker::SparseMatrix;
+    ///
+    /// let sparse = SparseMatrix::from_slices(&[2, 0], &[1, 3], &[5, 7], (3,4)).unwrap();
+    ///
+    /// let (rows, cols, vals) = sparse.to_slices();
+    ///
+    /// assert_eq!(rows, vec![0, 2]);
+    /// assert_eq!(cols, vec![3, 1]);
+    /// assert_eq!(vals, vec![7, 5]);
+    /// ```
+    pub fn to_slices(&self) -> (Vec<usize>, Vec<usize>, Vec<T>) {
+        let entries = self.entries_sorted();
+
+        let mut rows = Vec::with_capacity(entries.len());
+        let mut cols = Vec::with_capacity(entries.len());
+        let mut vals = Vec::with_capacity(entries.len());
+
+        for ((i, j), val) in entries {
+            rows.push(i);
+            cols.push(j);
+            vals.push(val);
+        }
+
+        (rows, cols, vals)
+    }
+
     /// Parses from file, but will return a default sparse matrix if nothing is given
     ///
     /// # Examples
@@ -369,14 +570,145 @@ where
     ///
     /// // m.print(4);
     /// ```
-    pub fn from_file(path: &'static str) -> Result<Self, MatrixError> {
-        let data =
-            fs::read_to_string(path).map_err(|_| MatrixError::MatrixFileReadError(path).into())?;
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, MatrixError> {
+        let path = path.as_ref();
+        let data = fs::read_to_string(path)
+            .map_err(|_| MatrixError::MatrixFileReadError(path.display().to_string()))?;
 
         data.parse::<Self>()
             .map_err(|_| MatrixError::MatrixParseError.into())
     }
 
+    /// Reads a sparse matrix from a Matrix Market (.mtx) file.
+    ///
+    /// Only the coordinate format with real or integer values and
+    /// general symmetry is supported. Indices in the file are 1-based.
+    /// Malformed files, including ones with duplicate coordinates or
+    /// an entry count not matching the header, give a MatrixParseError
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::SparseMatrix;
+    ///
+    /// // let m: SparseMatrix<f64> = SparseMatrix::from_matrix_market("matrix.mtx").unwrap();
+    ///
+    /// // m.print(4);
+    /// ```
+    pub fn from_matrix_market<P: AsRef<Path>>(path: P) -> Result<Self, MatrixError> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .map_err(|_| MatrixError::MatrixFileReadError(path.display().to_string()))?;
+
+        Self::parse_matrix_market(&content)
+    }
+
+    /// Writes the sparse matrix to a Matrix Market (.mtx) file
+    /// in coordinate format, with 1-based indices in row-major order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::SparseMatrix;
+    ///
+    /// let sparse = SparseMatrix::<f64>::eye(3);
+    ///
+    /// // sparse.to_matrix_market("eye.mtx").unwrap();
+    /// ```
+    pub fn to_matrix_market<P: AsRef<Path>>(&self, path: P) -> Result<(), MatrixError> {
+        let mut content = format!(
+            "{}\n{} {} {}\n",
+            MATRIX_MARKET_HEADER,
+            self.nrows,
+            self.ncols,
+            self.data.len()
+        );
+
+        for ((i, j), val) in self.entries_sorted() {
+            content += &format!("{} {} {}\n", i + 1, j + 1, val);
+        }
+
+        let path = path.as_ref();
+
+        fs::write(path, content)
+            .map_err(|_| MatrixError::MatrixFileWriteError(path.display().to_string()))
+    }
+
+    /// Encodes the sparse matrix as JSON with the schema
+    /// `{ "nrows": .., "ncols": .., "entries": [[i, j, value], ..] }`,
+    /// where entries are in row-major order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::SparseMatrix;
+    ///
+    /// let sparse = SparseMatrix::<i32>::eye(2);
+    ///
+    /// assert_eq!(
+    ///     sparse.to_json().unwrap(),
+    ///     r#"{"nrows":2,"ncols":2,"entries":[[0,0,1],[1,1,1]]}"#
+    /// );
+    /// ```
+    pub fn to_json(&self) -> Result<String, MatrixError>
+    where
+        T: Serialize,
+    {
+        let json = SparseMatrixJson {
+            nrows: self.nrows,
+            ncols: self.ncols,
+            entries: self
+                .entries_sorted()
+                .into_iter()
+                .map(|((i, j), val)| (i, j, val))
+                .collect(),
+        };
+
+        serde_json::to_string(&json)
+            .map_err(|e| MatrixError::MatrixSerializationError(e.to_string()))
+    }
+
+    /// Decodes a sparse matrix from JSON written by `to_json`.
+    ///
+    /// Returns MatrixParseError if the JSON does not follow the
+    /// schema, or if an entry is out of bounds or repeated.
+    /// Entries with the value 0 are dropped
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sukker::{MatrixError, SparseMatrix};
+    ///
+    /// let json = r#"{"nrows":2,"ncols":3,"entries":[[1,2,5]]}"#;
+    /// let sparse = SparseMatrix::<i32>::from_json(json).unwrap();
+    ///
+    /// assert_eq!(sparse.at(1, 2), 5);
+    /// assert_eq!(
+    ///     SparseMatrix::<i32>::from_json(r#"{"nrows":2,"ncols":3,"entries":[[2,0,5]]}"#),
+    ///     Err(MatrixError::MatrixParseError)
+    /// );
+    /// ```
+    pub fn from_json(json: &str) -> Result<Self, MatrixError>
+    where
+        T: DeserializeOwned,
+    {
+        let parsed: SparseMatrixJson<T> =
+            serde_json::from_str(json).map_err(|_| MatrixError::MatrixParseError)?;
+
+        let mut data: SparseMatrixData<T> = HashMap::with_capacity(parsed.entries.len());
+
+        for (i, j, val) in parsed.entries {
+            if i >= parsed.nrows || j >= parsed.ncols || data.insert((i, j), val).is_some() {
+                return Err(MatrixError::MatrixParseError);
+            }
+        }
+
+        // Don't store explicit zeros
+        data.retain(|_, val| *val != T::zero());
+
+        Ok(Self::new(data, (parsed.nrows, parsed.ncols)))
+    }
+
     /// Gets an element from the sparse matrix.
     ///
     /// Returns None if index is out of bounds.
@@ -391,12 +723,10 @@ where
     /// assert_eq!(sparse.get(0,0), Some(1));
     /// assert_eq!(sparse.get(1,0), Some(0));
     /// assert_eq!(sparse.get(4,0), None);
+    /// assert_eq!(sparse.get(0,3), None);
     /// ```
     pub fn get(&self, i: usize, j: usize) -> Option<T> {
-        let idx = at!(i, j, self.ncols);
-
-        if idx >= self.size() {
-            eprintln!("Error, index out of bounds. Not setting value");
+        if !self.in_bounds(i, j) {
             return None;
         }
 
@@ -421,8 +751,48 @@ where
     /// assert_eq!(sparse.size(), 16);
     /// ```
     pub fn randomize_range(start: T, end: T, sparsity: f64, shape: Shape) -> Self {
-        let mut rng = rand::thread_rng();
+        Self::randomize_range_with(start, end, sparsity, shape, &mut rand::thread_rng())
+    }
 
+    /// Same as `randomize_range`, but both the positions and values
+    /// are drawn from a generator seeded with `seed`,
+    /// so the same seed always gives the same sparse matrix
+    ///
+    /// Examples
+    ///
+    /// ```
+    /// use sukker::SparseMatrix;
+    ///
+    /// let a = SparseMatrix::<f32>::randomize_range_seeded(1.0, 2.0, 0.75, (4,4), 7);
+    /// let b = SparseMatrix::<f32>::randomize_range_seeded(1.0, 2.0, 0.75, (4,4), 7);
+    ///
+    /// assert_eq!(a, b);
+    /// assert_eq!(a.sparsity(), 0.75);
+    /// ```
+    pub fn randomize_range_seeded(
+        start: T,
+        end: T,
+        sparsity: f64,
+        shape: Shape,
+        seed: u64,
+    ) -> Self {
+        Self::randomize_range_with(
+            start,
+            end,
+            sparsity,
+            shape,
+            &mut StdRng::seed_from_u64(seed),
+        )
+    }
+
+    // Shared by the seeded and unseeded constructors
+    fn randomize_range_with<R: Rng>(
+        start: T,
+        end: T,
+        sparsity: f64,
+        shape: Shape,
+        rng: &mut R,
+    ) -> Self {
         let (rows, cols) = shape;
 
         // If we insert in a position that's already filled up,
@@ -519,6 +889,13 @@ where
     /// ```
     #[inline(always)]
     pub fn at(&self, i: usize, j: usize) -> T {
+        assert!(
+            self.in_bounds(i, j),
+            "Index {:?} is out of bounds for sparse matrix of shape {:?}",
+            (i, j),
+            self.shape()
+        );
+
         match self.data.get(&(i, j)) {
             None => T::zero(),
             Some(val) => val.clone(),
@@ -527,8 +904,8 @@ where
 
     /// Sets an element
     ///
-    /// If you're trying to insert a zero-value, this function
-    /// does nothing
+    /// If you're trying to insert a zero-value, or the index
+    /// is out of bounds, this function does nothing
     ///
     /// Mutates or inserts a value based on indeces given
     pub fn set(&mut self, value: T, idx: Shape) {
@@ -537,10 +914,7 @@ where
             return;
         }
 
-        let i = at!(idx.0, idx.1, self.ncols);
-
-        if i >= self.size() {
-            eprintln!("Error, index out of bounds. Not setting value");
+        if !self.in_bounds(idx.0, idx.1) {
             return;
         }
 
@@ -551,15 +925,132 @@ where
     }
 
     /// A way of inserting with individual row and col
+    ///
+    /// Like `set`, nothing is inserted if the index is out of bounds
     pub fn insert(&mut self, i: usize, j: usize, value: T) {
         self.set(value, (i, j));
     }
 
+    /// Removes every stored entry whose absolute value is at most epsilon.
+    ///
+    /// Use `T::zero()` to only remove explicit zeros
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use sukker::SparseMatrix;
+    ///
+    /// let mut sparse = SparseMatrix::<f64>::from_slices(&[0, 1, 2], &[0, 1, 2], &[1.0, 1e-12, -3.0], (3, 3)).unwrap();
+    ///
+    /// sparse.prune(1e-9);
+    ///
+    /// assert_eq!(sparse.nnz(), 2);
+    /// assert_eq!(sparse.get(1, 1), Some(0.0));
+    /// ```
+    pub fn prune(&mut self, epsilon: T) {
+        self.data.retain(|_, val| val.abs() > epsilon);
+    }
+
+    /// Removes all stored entries, keeping the shape
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use sukker::SparseMatrix;
+    ///
+    /// let mut sparse = SparseMatrix::<i32>::eye(3);
+    ///
+    /// sparse.clear();
+    ///
+    /// assert_eq!(sparse.nnz(), 0);
+    /// assert_eq!(sparse.shape(), (3, 3));
+    /// ```
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    // Checks row and column separately, as a flattened index
+    // can wrap around into the next row
+    #[inline(always)]
+    fn in_bounds(&self, i: usize, j: usize) -> bool {
+        i < self.nrows && j < self.ncols
+    }
+
+    /// Gets all stored entries sorted by (row, col).
+    ///
+    /// Iterating over `data` directly is faster,
+    /// but the order is not the same between runs
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use sukker::{SparseMatrix, SparseMatrixData};
+    ///
+    /// let mut indexes: SparseMatrixData<i32> = SparseMatrixData::new();
+    ///
+    /// indexes.insert((1, 0), 4);
+    /// indexes.insert((0, 2), 3);
+    ///
+    /// let sparse = SparseMatrix::new(indexes, (2, 3));
+    ///
+    /// assert_eq!(sparse.entries_sorted(), vec![((0, 2), 3), ((1, 0), 4)]);
+    /// ```
+    pub fn entries_sorted(&self) -> Vec<(Shape, T)> {
+        let mut entries: Vec<(Shape, T)> =
+            self.data.iter().map(|(&idx, &val)| (idx, val)).collect();
+        entries.sort_unstable_by_key(|&(idx, _)| idx);
+        entries
+    }
+
+    /// Gets a rectangular window of the sparse matrix out as a new
+    /// sparse matrix of shape `size`, with indexes relative to `start_idx`.
+    ///
+    /// Returns a MatrixIndexOutOfBoundsError if the window
+    /// does not fit inside the matrix
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use sukker::SparseMatrix;
+    ///
+    /// let sparse = SparseMatrix::<i32>::eye(4);
+    ///
+    /// let sub = sparse.get_sub_matrix((1, 1), (2, 3)).unwrap();
+    ///
+    /// assert_eq!(sub.shape(), (2, 3));
+    /// assert_eq!(sub.at(0, 0), 1);
+    /// assert_eq!(sub.at(1, 1), 1);
+    /// assert_eq!(sub.data.len(), 2);
+    /// ```
+    pub fn get_sub_matrix(&self, start_idx: Shape, size: Shape) -> Result<Self, MatrixError> {
+        let (start_row, start_col) = start_idx;
+        let (rows, cols) = size;
+
+        let (end_row, end_col) = match (start_row.checked_add(rows), start_col.checked_add(cols)) {
+            (Some(end_row), Some(end_col)) if end_row <= self.nrows && end_col <= self.ncols => {
+                (end_row, end_col)
+            }
+            _ => return Err(MatrixError::MatrixIndexOutOfBoundsError),
+        };
+
+        let data: SparseMatrixData<T> = self
+            .data
+            .iter()
+            .filter(|(&(i, j), _)| {
+                (start_row..end_row).contains(&i) && (start_col..end_col).contains(&j)
+            })
+            .map(|(&(i, j), &val)| ((i - start_row, j - start_col), val))
+            .collect();
+
+        Ok(Self::new(data, size))
+    }
+
     /// Prints out the sparse matrix data
     ///
-    /// Only prints out the hashmap with a set amount of decimals
+    /// Only prints out the hashmap with a set amount of decimals,
+    /// sorted by index
     pub fn print(&self, decimals: usize) {
-        self.data
+        self.entries_sorted()
             .iter()
             .for_each(|((i, j), val)| println!("{i} {j}: {:.decimals$}", val));
     }
@@ -612,6 +1103,85 @@ where
         1.0 - self.data.par_iter().count() as f64 / self.size() as f64
     }
 
+    /// Number of stored non-zero values in the matrix
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use sukker::SparseMatrix;
+    ///
+    /// let sparse = SparseMatrix::<i32>::eye(4);
+    ///
+    /// assert_eq!(sparse.nnz(), 4);
+    /// ```
+    #[inline(always)]
+    pub fn nnz(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Calculates density for the given matrix,
+    /// which is the fraction of non-zero values, or 1 - sparsity
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use sukker::SparseMatrix;
+    ///
+    /// let sparse = SparseMatrix::<i32>::eye(4);
+    ///
+    /// assert_eq!(sparse.density(), 0.25);
+    /// ```
+    #[inline(always)]
+    pub fn density(&self) -> f64 {
+        self.nnz() as f64 / self.size() as f64
+    }
+
+    /// Counts the stored entries in every row.
+    ///
+    /// For an adjacency matrix this is the out-degree of every node
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use sukker::SparseMatrix;
+    ///
+    /// let sparse = SparseMatrix::<i32>::from_slices(&[0, 0, 2], &[1, 2, 0], &[1, 1, 1], (3, 3)).unwrap();
+    ///
+    /// assert_eq!(sparse.row_nnz(), vec![2, 0, 1]);
+    /// ```
+    pub fn row_nnz(&self) -> Vec<usize> {
+        let mut counts = vec![0; self.nrows];
+
+        for &(i, _) in self.data.keys() {
+            counts[i] += 1;
+        }
+
+        counts
+    }
+
+    /// Counts the stored entries in every column.
+    ///
+    /// For an adjacency matrix this is the in-degree of every node
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use sukker::SparseMatrix;
+    ///
+    /// let sparse = SparseMatrix::<i32>::from_slices(&[0, 0, 2], &[1, 2, 0], &[1, 1, 1], (3, 3)).unwrap();
+    ///
+    /// assert_eq!(sparse.col_nnz(), vec![1, 1, 1]);
+    /// ```
+    pub fn col_nnz(&self) -> Vec<usize> {
+        let mut counts = vec![0; self.ncols];
+
+        for &(_, j) in self.data.keys() {
+            counts[j] += 1;
+        }
+
+        counts
+    }
+
     /// Shape of the matrix outputted as a tuple
     ///
     /// Examples:
@@ -712,17 +1282,30 @@ where
     }
 
     /// Finds minimum element of a sparse matrix
+    ///
+    /// Implicit zeros are taken into account, so if there are
+    /// any empty cells, the minimum is at most 0.
     /// Will return 0 if matrix is empty
+    ///
+    /// Examples
+    ///
+    /// ```
+    /// use sukker::SparseMatrix;
+    ///
+    /// let sparse = SparseMatrix::<i32>::eye(3);
+    ///
+    /// assert_eq!(sparse.min(), 0);
+    /// ```
     pub fn min(&self) -> T {
         let elem = self
             .data
-            .iter()
-            .max_by(|(_, v1), (_, v2)| v1.partial_cmp(v2).unwrap());
+            .values()
+            .min_by(|v1, v2| v1.partial_cmp(v2).unwrap());
 
-        return match elem {
-            Some((_, &v)) => v,
-            None => T::zero(),
-        };
+        match elem {
+            Some(&v) if self.get_zero_count() == 0 || v < T::zero() => v,
+            _ => T::zero(),
+        }
     }
 
     /// Negates all items
@@ -736,6 +1319,56 @@ where
         Self::new(data, self.shape())
     }
 
+    /// Finds total sum of matrix.
+    ///
+    /// Only stored entries are visited, since zeros contribute nothing
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use sukker::SparseMatrix;
+    ///
+    /// let sparse = SparseMatrix::<i32>::from_slices(&[0, 1, 1], &[0, 0, 2], &[3, 4, -2], (2, 3)).unwrap();
+    ///
+    /// assert_eq!(sparse.cumsum(), 5);
+    /// ```
+    pub fn cumsum(&self) -> T {
+        self.data.par_iter().map(|(_, &val)| val).sum()
+    }
+
+    /// Sums up every row or column of the matrix into a dense vector.
+    ///
+    /// Reducing over `Dimension::Row` sums each column and gives ncols values,
+    /// while reducing over `Dimension::Col` sums each row and gives nrows values
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use sukker::{Dimension, SparseMatrix};
+    ///
+    /// let sparse = SparseMatrix::<i32>::from_slices(&[0, 1, 1], &[0, 0, 2], &[3, 4, -2], (2, 3)).unwrap();
+    ///
+    /// assert_eq!(sparse.sum_axis(Dimension::Row), vec![7, 0, -2]);
+    /// assert_eq!(sparse.sum_axis(Dimension::Col), vec![3, 2]);
+    /// ```
+    pub fn sum_axis(&self, dim: Dimension) -> Vec<T> {
+        let len = match dim {
+            Dimension::Row => self.ncols,
+            Dimension::Col => self.nrows,
+        };
+
+        let mut sums = vec![T::zero(); len];
+
+        for (&(i, j), &val) in self.data.iter() {
+            match dim {
+                Dimension::Row => sums[j] += val,
+                Dimension::Col => sums[i] += val,
+            }
+        }
+
+        sums
+    }
+
     /// Finds average value of a matrix
     ///
     /// Returns 0 if matrix is empty
@@ -751,49 +1384,102 @@ where
 
     /// Finds the median value of a matrix
     ///
+    /// Every cell that is not stored counts as a 0, so the
+    /// median is taken over all `size()` elements.
+    ///
     /// If the matrix is empty, 0 is returned
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use sukker::{SparseMatrix, SparseMatrixData};
+    ///
+    /// let mut indexes: SparseMatrixData<f64> = SparseMatrixData::new();
+    ///
+    /// indexes.insert((0, 0), -3.0);
+    /// indexes.insert((0, 1), -2.0);
+    /// indexes.insert((1, 0), -1.0);
+    ///
+    /// let sparse = SparseMatrix::new(indexes, (2, 3));
+    ///
+    /// // Sorted: [-3, -2, -1, 0, 0, 0]
+    /// assert_eq!(sparse.median(), -0.5);
+    /// ```
     pub fn median(&self) -> T {
         if self.size() == 0 {
             return T::zero();
         }
 
-        if self.size() == 1 {
-            return self.at(0, 0);
-        }
-
-        // If more than half the values are 0 and we only have
-        // values > 0, 0 is returned
-        if self.min() >= T::zero() && self.sparsity() >= 0.5 {
-            return T::zero();
-        }
-
         let sorted_values: Vec<T> = self
             .data
             .values()
             .copied()
-            .sorted_by(|a, b| a.partial_cmp(&b).unwrap())
+            .sorted_by(|a, b| a.partial_cmp(b).unwrap())
             .collect::<Vec<T>>();
 
-        match self.data.len() % 2 {
-            0 => {
-                let half: usize = self.data.len() / 2;
-
-                sorted_values
-                    .iter()
-                    .skip(half - 1)
-                    .take(2)
-                    .copied()
-                    .sum::<T>()
-                    / (T::one() + T::one())
+        let negatives = sorted_values.partition_point(|&v| v < T::zero());
+        let zeros = self.get_zero_count();
+
+        // Value at rank `k` of the full matrix, with the implicit
+        // zeros placed right after the stored negative values
+        let nth = |k: usize| -> T {
+            if k < negatives {
+                sorted_values[k]
+            } else if k < negatives + zeros {
+                T::zero()
+            } else {
+                sorted_values[k - zeros]
             }
-            1 => {
-                let half: usize = self.data.len() / 2;
+        };
 
-                sorted_values.iter().nth(half).unwrap().to_owned()
-            }
-            _ => unreachable!(),
+        let half = self.size() / 2;
+
+        match self.size() % 2 {
+            0 => (nth(half - 1) + nth(half)) / (T::one() + T::one()),
+            _ => nth(half),
         }
     }
+
+    /// Sums up the main diagonal of the matrix.
+    ///
+    /// Diagonal cells not stored are treated as zero
+    ///
+    /// Examples
+    ///
+    /// ```
+    /// use sukker::SparseMatrix;
+    ///
+    /// let sparse = SparseMatrix::<i32>::eye(3);
+    ///
+    /// assert_eq!(sparse.trace(), 3);
+    /// ```
+    pub fn trace(&self) -> T {
+        self.data
+            .iter()
+            .filter(|(&(i, j), _)| i == j)
+            .map(|(_, &val)| val)
+            .sum()
+    }
+
+    /// Gets the main diagonal of the sparse matrix,
+    /// with as many elements as the shortest dimension.
+    ///
+    /// Diagonal cells not stored are read as zero
+    ///
+    /// Examples
+    ///
+    /// ```
+    /// use sukker::SparseMatrix;
+    ///
+    /// let sparse = SparseMatrix::<i32>::eye(2);
+    ///
+    /// assert_eq!(sparse.diagonal(), vec![1, 1]);
+    /// ```
+    pub fn diagonal(&self) -> Vec<T> {
+        (0..self.nrows.min(self.ncols))
+            .map(|i| self.at(i, i))
+            .collect()
+    }
 }
 
 /// Linear algebra on sparse matrices
@@ -913,13 +1599,15 @@ where
     /// let res = sparse1.sub(&sparse2).unwrap();
     ///
     /// assert_eq!(res.shape(), (3,3));
-    /// assert_eq!(res.get(0,0).unwrap(), 2);
+    /// assert_eq!(res.get(0,0).unwrap(), 0);
     /// ```
     pub fn sub(&self, other: &Self) -> Result<Self, MatrixError> {
         Self::sparse_helper(&self, other, Operation::SUB)
     }
     /// Multiplies two sparse matrices together
-    /// and return a new one
+    /// and return a new one.
+    ///
+    /// The result is only non-zero where both matrices are non-zero
     ///
     /// Examples:
     ///
@@ -932,7 +1620,7 @@ where
     /// let res = sparse1.mul(&sparse2).unwrap();
     ///
     /// assert_eq!(res.shape(), (3,3));
-    /// assert_eq!(res.get(0,0).unwrap(), 2);
+    /// assert_eq!(res.get(0,0).unwrap(), 1);
     /// ```
     pub fn mul(&self, other: &Self) -> Result<Self, MatrixError> {
         Self::sparse_helper(&self, other, Operation::MUL)
@@ -945,7 +1633,10 @@ where
     }
 
     /// Divides two sparse matrices
-    /// and return a new one
+    /// and return a new one.
+    ///
+    /// Only the non-zeros of lhs are divided, and every one of them
+    /// needs a non-zero in rhs, otherwise MatrixDivideByZeroError
     ///
     /// Examples:
     ///
@@ -958,7 +1649,7 @@ where
     /// let res = sparse1.div(&sparse2).unwrap();
     ///
     /// assert_eq!(res.shape(), (3,3));
-    /// assert_eq!(res.get(0,0).unwrap(), 2);
+    /// assert_eq!(res.get(0,0).unwrap(), 1);
     /// ```
     pub fn div(&self, other: &Self) -> Result<Self, MatrixError> {
         Self::sparse_helper(&self, other, Operation::DIV)
@@ -972,6 +1663,7 @@ where
 
     /// Adds rhs matrix on to lhs matrix.
     /// All elements from rhs gets inserted into lhs
+    /// Returns MatrixDimensionMismatchError if the shapes differ
     ///
     /// Examples:
     ///
@@ -981,17 +1673,18 @@ where
     /// let mut sparse1 = SparseMatrix::<i32>::eye(3);
     /// let sparse2 = SparseMatrix::<i32>::eye(3);
     ///
-    /// sparse1.add_self(&sparse2);
+    /// sparse1.add_self(&sparse2).unwrap();
     ///
     /// assert_eq!(sparse1.shape(), (3,3));
     /// assert_eq!(sparse1.get(0,0).unwrap(), 2);
     /// ```
-    pub fn add_self(&mut self, other: &Self) {
-        Self::sparse_helper_self(self, other, Operation::ADD);
+    pub fn add_self(&mut self, other: &Self) -> Result<(), MatrixError> {
+        Self::sparse_helper_self(self, other, Operation::ADD)
     }
 
     /// Subs rhs matrix on to lhs matrix.
     /// All elements from rhs gets inserted into lhs
+    /// Returns MatrixDimensionMismatchError if the shapes differ
     ///
     /// Examples:
     ///
@@ -1001,17 +1694,18 @@ where
     /// let mut sparse1 = SparseMatrix::<i32>::eye(3);
     /// let sparse2 = SparseMatrix::<i32>::eye(3);
     ///
-    /// sparse1.sub_self(&sparse2);
+    /// sparse1.sub_self(&sparse2).unwrap();
     ///
     /// assert_eq!(sparse1.shape(), (3,3));
     /// assert_eq!(sparse1.get(0,0).unwrap(), 0);
     /// ```
-    pub fn sub_self(&mut self, other: &Self) {
-        Self::sparse_helper_self(self, other, Operation::SUB);
+    pub fn sub_self(&mut self, other: &Self) -> Result<(), MatrixError> {
+        Self::sparse_helper_self(self, other, Operation::SUB)
     }
 
     /// Multiplies  rhs matrix on to lhs matrix.
-    /// All elements from rhs gets inserted into lhs
+    /// Only elements non-zero in both matrices are kept
+    /// Returns MatrixDimensionMismatchError if the shapes differ
     ///
     /// Examples:
     ///
@@ -1021,17 +1715,20 @@ where
     /// let mut sparse1 = SparseMatrix::<i32>::eye(3);
     /// let sparse2 = SparseMatrix::<i32>::eye(3);
     ///
-    /// sparse1.mul_self(&sparse2);
+    /// sparse1.mul_self(&sparse2).unwrap();
     ///
     /// assert_eq!(sparse1.shape(), (3,3));
     /// assert_eq!(sparse1.get(0,0).unwrap(), 1);
     /// ```
-    pub fn mul_self(&mut self, other: &Self) {
-        Self::sparse_helper_self(self, other, Operation::MUL);
+    pub fn mul_self(&mut self, other: &Self) -> Result<(), MatrixError> {
+        Self::sparse_helper_self(self, other, Operation::MUL)
     }
 
     /// Divides rhs matrix on to lhs matrix.
-    /// All elements from rhs gets inserted into lhs
+    /// Only the non-zeros of lhs are divided. If any of them
+    /// would be divided by an implicit zero, lhs is left unchanged
+    /// and a MatrixDivideByZeroError is returned.
+    /// Returns MatrixDimensionMismatchError if the shapes differ
     ///
     /// Examples:
     ///
@@ -1041,13 +1738,13 @@ where
     /// let mut sparse1 = SparseMatrix::<i32>::eye(3);
     /// let sparse2 = SparseMatrix::<i32>::eye(3);
     ///
-    /// sparse1.div_self(&sparse2);
+    /// sparse1.div_self(&sparse2).unwrap();
     ///
     /// assert_eq!(sparse1.shape(), (3,3));
     /// assert_eq!(sparse1.get(0,0).unwrap(), 1);
     /// ```
-    pub fn div_self(&mut self, other: &Self) {
-        Self::sparse_helper_self(self, other, Operation::DIV);
+    pub fn div_self(&mut self, other: &Self) -> Result<(), MatrixError> {
+        Self::sparse_helper_self(self, other, Operation::DIV)
     }
 
     // =============================================================
@@ -1059,6 +1756,10 @@ where
     /// Adds value to all non zero values in the matrix
     /// and return a new matrix
     ///
+    /// Note that the implicit zeros are left untouched, so this is
+    /// not the same as adding value to every cell of the dense matrix.
+    /// Use `add_val_dense` for that
+    ///
     /// Examples:
     ///
     /// ```
@@ -1078,6 +1779,9 @@ where
     /// Subs value to all non zero values in the matrix
     /// and return a new matrix
     ///
+    /// Like `add_val`, the implicit zeros are left untouched.
+    /// Use `add_val_dense` with a negated value to subtract from every cell
+    ///
     /// Examples:
     ///
     /// ```
@@ -1094,6 +1798,26 @@ where
         Self::sparse_helper_val(self, value, Operation::SUB)
     }
 
+    /// Adds value to every cell of the matrix, including the implicit zeros.
+    ///
+    /// Since every cell is then in general non-zero,
+    /// the result is returned as a dense matrix
+    ///
+    /// Examples:
+    ///
+    /// ```
+    /// use sukker::SparseMatrix;
+    ///
+    /// let sparse = SparseMatrix::<f32>::eye(2);
+    ///
+    /// let res = sparse.add_val_dense(4.5);
+    ///
+    /// assert_eq!(res.get_vec(), vec![5.5, 4.5, 4.5, 5.5]);
+    /// ```
+    pub fn add_val_dense(&self, value: T) -> Matrix<'a, T> {
+        Matrix::from_sparse(self.clone()).add_val(value)
+    }
+
     /// Multiplies value to all non zero values in the matrix
     /// and return a new matrix
     ///
@@ -1140,7 +1864,8 @@ where
     //
     // =============================================================
 
-    /// Adds value to all non zero elements in matrix
+    /// Adds value to all non zero elements in matrix.
+    /// The implicit zeros are left untouched
     ///
     /// Examples:
     ///
@@ -1158,7 +1883,8 @@ where
         Self::sparse_helper_self_val(self, value, Operation::ADD)
     }
 
-    /// Subtracts value to all non zero elements in matrix
+    /// Subtracts value to all non zero elements in matrix.
+    /// The implicit zeros are left untouched
     ///
     /// Examples:
     ///
@@ -1216,13 +1942,11 @@ where
 
     /// Sparse matrix multiplication
     ///
-    /// For two n x n matrices, we use this algorithm:
-    /// https://theory.stanford.edu/~virgi/cs367/papers/sparsemult.pdf
+    /// Every stored entry (i, k) of A is multiplied with the stored entries
+    /// (k, j) in row k of B, and accumulated into (i, j).
+    /// Only non-zero entries are ever visited.
     ///
-    /// Else, we use this:
-    /// link..
-    ///
-    /// In this example we have these two matrices:
+    /// For these two matrices:
     ///
     /// A:
     ///
@@ -1234,11 +1958,13 @@ where
     ///
     /// 2.0 0.0 0.0
     /// 4.0 8.0 0.0
-    /// 8.0 6.0 0.0
+    /// 0.0 6.0 0.0
+    ///
+    /// A @ B is:
     ///
-    /// 0.0 24.0 0
-    /// 8.0 72.0 0
-    /// 0.0 0.0  48.0
+    /// 8.0  16.0 0.0
+    /// 32.0 48.0 0.0
+    /// 0.0  48.0 0.0
     ///
     /// Examples
     ///
@@ -1282,6 +2008,106 @@ where
 
         Ok(self.matmul_sparse_mnnp(other))
     }
+
+    /// Matrix multiplication with a dense matrix on the right hand side.
+    ///
+    /// Every stored entry (i, k) adds a scaled copy of row k
+    /// of the dense matrix onto row i of the dense result
+    ///
+    /// Examples
+    ///
+    /// ```
+    /// use sukker::{Matrix, SparseMatrix};
+    ///
+    /// let sparse = SparseMatrix::<f64>::eye(2);
+    /// let dense = Matrix::init(3.0, (2,4));
+    ///
+    /// let result = sparse.matmul_dense(&dense).unwrap();
+    ///
+    /// assert_eq!(result, dense);
+    /// ```
+    pub fn matmul_dense(&self, other: &Matrix<'a, T>) -> Result<Matrix<'a, T>, MatrixError> {
+        if self.ncols != other.nrows {
+            return Err(MatrixError::MatrixMultiplicationDimensionMismatchError);
+        }
+
+        let p = other.ncols;
+
+        let mut rows: HashMap<usize, Vec<(usize, T)>> = HashMap::new();
+
+        for (&(i, k), &val) in self.data.iter() {
+            rows.entry(i).or_default().push((k, val));
+        }
+
+        let mut data = vec![T::zero(); self.nrows * p];
+
+        data.par_chunks_mut(p.max(1))
+            .enumerate()
+            .for_each(|(i, row)| {
+                for &(k, val) in rows.get(&i).into_iter().flatten() {
+                    for (res, &b) in row.iter_mut().zip(other.row(k).unwrap()) {
+                        *res += val * b;
+                    }
+                }
+            });
+
+        Matrix::new(data, (self.nrows, p))
+    }
+
+    /// Multiply a sparse matrix with itself n number of times,
+    /// using exponentiation by squaring with `matmul_sparse`.
+    ///
+    /// `pow(0)` gives the identity matrix and `pow(1)` a copy of self.
+    /// For an adjacency matrix, entry (i, j) of `pow(n)` counts
+    /// the walks of length n from i to j.
+    ///
+    /// If matrix is not in form NxN, this function returns None
+    ///
+    /// Examples
+    ///
+    /// ```
+    /// use sukker::SparseMatrix;
+    ///
+    /// let sparse = SparseMatrix::<i32>::from_slices(&[0, 1], &[1, 0], &[2, 3], (2, 2)).unwrap();
+    ///
+    /// let res = sparse.pow(2).unwrap();
+    ///
+    /// assert_eq!(res.at(0, 0), 6);
+    /// assert_eq!(res.at(0, 1), 0);
+    /// assert_eq!(sparse.pow(0).unwrap(), SparseMatrix::eye(2));
+    /// ```
+    pub fn pow(&self, n: usize) -> Option<Self> {
+        if self.nrows != self.ncols {
+            return None;
+        }
+
+        if n == 0 {
+            return Some(Self::eye(self.nrows));
+        }
+
+        let mut res: Option<Self> = None;
+        let mut base = self.clone();
+        let mut n = n;
+
+        loop {
+            if n & 1 == 1 {
+                res = Some(match res {
+                    Some(acc) => acc.matmul_sparse(&base).unwrap(),
+                    None => base.clone(),
+                });
+            }
+
+            n >>= 1;
+
+            if n == 0 {
+                break;
+            }
+
+            base = base.matmul_sparse(&base).unwrap();
+        }
+
+        res
+    }
 }
 
 /// Predicates for sparse matrices
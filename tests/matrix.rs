@@ -1,4 +1,6 @@
-use linalg_rs::Matrix;
+use std::{env, fs};
+
+use linalg_rs::{ConvMode, Dimension, LinAlgFloats, MatMulAlgo, Matrix, MatrixError};
 
 #[test]
 fn basic() {
@@ -31,3 +33,2282 @@ fn new() {
     // To print this beautiful matrix:
     c.print(7);
 }
+
+fn approx_eq(a: &Matrix<f64>, b: &Matrix<f64>, eps: f64) -> bool {
+    a.shape() == b.shape()
+        && a
+            .get_vec()
+            .iter()
+            .zip(b.get_vec().iter())
+            .all(|(x, y)| (x - y).abs() < eps)
+}
+
+#[test]
+fn inverse() {
+    let a = Matrix::new(vec![2.0, -1.0, 0.0, -1.0, 2.0, -1.0, 0.0, -1.0, 2.0], (3, 3)).unwrap();
+    let inv = a.inverse().unwrap();
+    assert!(approx_eq(&a.matmul(&inv).unwrap(), &Matrix::eye(3), 1e-10));
+
+    // Needs pivoting since the first element is 0
+    let b: Matrix<f64> = Matrix::new(
+        vec![
+            0.0, 2.0, 1.0, 4.0, 1.0, 1.0, 0.0, 3.0, 2.0, 5.0, 1.0, 0.0, 1.0, 0.0, 2.0, 1.0,
+        ],
+        (4, 4),
+    )
+    .unwrap();
+    let inv = b.inverse().unwrap();
+    assert!(approx_eq(&b.matmul(&inv).unwrap(), &Matrix::eye(4), 1e-10));
+}
+
+#[test]
+fn inverse_singular() {
+    let a: Matrix<f64> =
+        Matrix::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0], (3, 3)).unwrap();
+    assert_eq!(a.inverse(), Err(MatrixError::SingularMatrixError));
+
+    let b: Matrix<f64> = Matrix::init(1.0, (2, 3));
+    assert_eq!(b.inverse(), Err(MatrixError::NotSquareError));
+
+    let c: Matrix<f64> = Matrix::new(vec![1.0, 2.0, 2.0, 4.0], (2, 2)).unwrap();
+    assert_eq!(c.inverse(), Err(MatrixError::SingularMatrixError));
+}
+
+#[test]
+fn solve() {
+    let a: Matrix<f64> =
+        Matrix::new(vec![0.0, 2.0, 1.0, 1.0, 1.0, 0.0, 3.0, 0.0, 1.0], (3, 3)).unwrap();
+    let b: Matrix<f64> = Matrix::new(vec![5.0, 1.0, 3.0, 1.0, 7.0, 4.0], (3, 2)).unwrap();
+
+    let x = a.solve(&b).unwrap();
+
+    assert_eq!(x.shape(), (3, 2));
+    assert!(approx_eq(&a.matmul(&x).unwrap(), &b, 1e-12));
+
+    let singular: Matrix<f64> =
+        Matrix::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0], (3, 3)).unwrap();
+    assert_eq!(
+        singular.solve(&Matrix::ones((3, 1))),
+        Err(MatrixError::SingularMatrixError)
+    );
+
+    assert_eq!(
+        Matrix::<f64>::ones((2, 3)).solve(&Matrix::ones((2, 1))),
+        Err(MatrixError::NotSquareError)
+    );
+    assert_eq!(
+        a.solve(&Matrix::ones((2, 1))),
+        Err(MatrixError::MatrixDimensionMismatchError)
+    );
+}
+
+#[test]
+fn lu() {
+    let a = Matrix::new(
+        vec![
+            0.0, 2.0, 1.0, 4.0, 1.0, 1.0, 0.0, 3.0, 2.0, 5.0, 1.0, 0.0, 1.0, 0.0, 2.0, 1.0,
+        ],
+        (4, 4),
+    )
+    .unwrap();
+
+    let (p, l, u) = a.lu().unwrap();
+
+    assert!(approx_eq(
+        &p.matmul(&a).unwrap(),
+        &l.matmul(&u).unwrap(),
+        1e-10
+    ));
+    assert_eq!(Matrix::<f64>::zeros((2, 3)).lu(), None);
+}
+
+#[test]
+fn determinant_lu() {
+    // Tridiagonal [-1, 2, -1] has determinant n + 1
+    let mut data = vec![0.0; 36];
+    for i in 0..6 {
+        data[i * 6 + i] = 2.0;
+        if i > 0 {
+            data[i * 6 + i - 1] = -1.0;
+            data[(i - 1) * 6 + i] = -1.0;
+        }
+    }
+    let a: Matrix<f64> = Matrix::new(data, (6, 6)).unwrap();
+
+    assert!((a.determinant().unwrap() - 7.0).abs() < 1e-10);
+
+    // Last row is the sum of the first two
+    let b: Matrix<f64> = Matrix::new(
+        vec![
+            1.0, 2.0, 3.0, 4.0, 2.0, 0.0, 1.0, 5.0, 0.0, 1.0, 4.0, 2.0, 3.0, 2.0, 4.0, 9.0,
+        ],
+        (4, 4),
+    )
+    .unwrap();
+
+    assert!(b.determinant().unwrap().abs() < 1e-10);
+
+    // Integers still use the exact path
+    let c: Matrix<i32> =
+        Matrix::new(vec![1, 3, 5, 9, 1, 3, 1, 7, 4, 3, 9, 7, 5, 2, 0, 9], (4, 4)).unwrap();
+
+    assert_eq!(c.determinant(), Some(-376));
+}
+
+#[test]
+fn operators() {
+    let a = Matrix::new(vec![1f32, 2.0, 3.0, 4.0], (2, 2)).unwrap();
+    let b = Matrix::new(vec![5f32, 6.0, 7.0, 8.0], (2, 2)).unwrap();
+
+    let c = &a + &b;
+    assert_eq!(c, a.add(&b).unwrap());
+    assert_eq!(&a - &b, a.sub(&b).unwrap());
+    assert_eq!(&a * &b, a.mul(&b).unwrap());
+    assert_eq!(&a / &b, a.div(&b).unwrap());
+
+    let d = &a * 2.0f32;
+    assert_eq!(d, a.mul_val(2.0));
+    assert_eq!(&a + 2.0f32, a.add_val(2.0));
+    assert_eq!(&a - 2.0f32, a.sub_val(2.0));
+    assert_eq!(&a / 2.0f32, a.div_val(2.0));
+    assert_eq!(-&a, a.neg());
+
+    // Operators compose
+    let e = &(&a + &b) * 0.5f32;
+    assert_eq!(e.get_vec(), vec![3.0, 4.0, 5.0, 6.0]);
+}
+
+#[test]
+#[should_panic(expected = "Can not add matrices of shape (2, 2) and (2, 3)")]
+fn operators_shape_mismatch() {
+    let a = Matrix::<f32>::ones((2, 2));
+    let b = Matrix::<f32>::ones((2, 3));
+
+    let _ = &a + &b;
+}
+
+#[test]
+fn index_operators() {
+    let mut a = Matrix::new(vec![1, 2, 3, 4, 5, 6], (2, 3)).unwrap();
+
+    assert_eq!(a[(0, 0)], 1);
+    assert_eq!(a[(1, 2)], 6);
+
+    a[(1, 2)] = 42;
+    a[(0, 1)] += 10;
+
+    assert_eq!(a[(1, 2)], 42);
+    assert_eq!(a.get_vec(), vec![1, 12, 3, 4, 5, 42]);
+}
+
+#[test]
+#[should_panic(expected = "Index (0, 3) is out of bounds for matrix of shape (2, 3)")]
+fn index_out_of_bounds() {
+    let a = Matrix::<i32>::zeros((2, 3));
+
+    // Would wrap into the next row with a flat index
+    let _ = a[(0, 3)];
+}
+
+#[test]
+fn row_col_iter() {
+    let a = Matrix::new((1..=12).collect(), (3, 4)).unwrap();
+
+    let row_sums: Vec<i32> = a.row_iter().map(|r| r.iter().sum()).collect();
+    let col_sums: Vec<i32> = a.col_iter().map(|c| c.iter().sum()).collect();
+
+    assert_eq!(row_sums, vec![10, 26, 42]);
+    assert_eq!(col_sums, vec![15, 18, 21, 24]);
+
+    assert_eq!(a.row(2), Some(&[9, 10, 11, 12][..]));
+    assert_eq!(a.col(3), Some(vec![4, 8, 12]));
+    assert_eq!(a.row(3), None);
+    assert_eq!(a.col(4), None);
+
+    let single = Matrix::new(vec![1, 2, 3], (1, 3)).unwrap();
+    let rows: Vec<&[i32]> = single.row_iter().collect();
+    assert_eq!(rows, vec![&[1, 2, 3][..]]);
+
+    let empty = Matrix::<i32>::zeros((0, 0));
+    assert_eq!(empty.row_iter().count(), 0);
+    assert_eq!(empty.col_iter().count(), 0);
+}
+
+#[test]
+fn axis_reductions() {
+    // [ 1  2  3  4
+    //   5  6  7  8
+    //   9 10 11 12 ]
+    let a: Matrix<f64> = Matrix::new((1..=12).map(|e| e as f64).collect(), (3, 4)).unwrap();
+
+    let col_sums = a.sum_axis(Dimension::Row);
+    assert_eq!(col_sums.shape(), (1, 4));
+    assert_eq!(col_sums.get_vec(), vec![15.0, 18.0, 21.0, 24.0]);
+
+    let row_sums = a.sum_axis(Dimension::Col);
+    assert_eq!(row_sums.shape(), (3, 1));
+    assert_eq!(row_sums.get_vec(), vec![10.0, 26.0, 42.0]);
+
+    assert_eq!(a.mean_axis(Dimension::Row).get_vec(), vec![5.0, 6.0, 7.0, 8.0]);
+    assert_eq!(a.mean_axis(Dimension::Col).get_vec(), vec![2.5, 6.5, 10.5]);
+
+    assert_eq!(a.prod_axis(Dimension::Row).get_vec(), vec![45.0, 120.0, 231.0, 384.0]);
+    assert_eq!(a.prod_axis(Dimension::Col).get_vec(), vec![24.0, 1680.0, 11880.0]);
+
+    assert_eq!(a.max_axis(Dimension::Row).get_vec(), vec![9.0, 10.0, 11.0, 12.0]);
+    assert_eq!(a.max_axis(Dimension::Col).get_vec(), vec![4.0, 8.0, 12.0]);
+
+    assert_eq!(a.min_axis(Dimension::Row).get_vec(), vec![1.0, 2.0, 3.0, 4.0]);
+    assert_eq!(a.min_axis(Dimension::Col).get_vec(), vec![1.0, 5.0, 9.0]);
+}
+
+#[test]
+fn argmax_argmin() {
+    // [ 0  3  1  9
+    //   4  2  6 10
+    //  -1  5  7  8 ]
+    let a = Matrix::new(vec![0, 3, 1, 9, 4, 2, 6, 10, -1, 5, 7, 8], (3, 4)).unwrap();
+
+    // Unique max is in the last column
+    assert_eq!(a.argmax(1, Dimension::Row), Some((1, 3)));
+    assert_eq!(a.argmax(3, Dimension::Col), Some((1, 3)));
+    assert_eq!(a.argmax(2, Dimension::Row), Some((2, 3)));
+
+    // Unique min of row 0 is the first element
+    assert_eq!(a.argmin(0, Dimension::Row), Some((0, 0)));
+    assert_eq!(a.argmin(0, Dimension::Col), Some((2, 0)));
+    assert_eq!(a.argmin(3, Dimension::Col), Some((2, 3)));
+
+    assert_eq!(a.argmax(3, Dimension::Row), None);
+    assert_eq!(a.argmin(4, Dimension::Col), None);
+
+    // Every value is below the zero and one the scan used to start from
+    let neg: Matrix<f64> = Matrix::new(vec![-5.0, -2.0, -9.0, -3.0], (2, 2)).unwrap();
+
+    assert_eq!(neg.argmax(0, Dimension::Row), Some((0, 1)));
+    assert_eq!(neg.argmin(1, Dimension::Row), Some((1, 0)));
+    assert_eq!(neg.argmax(0, Dimension::Col), Some((0, 0)));
+    assert_eq!(neg.argmin(0, Dimension::Col), Some((1, 0)));
+
+    let pos: Matrix<f64> = Matrix::new(vec![5.0, 2.0, 9.0, 3.0], (2, 2)).unwrap();
+
+    assert_eq!(pos.argmin(1, Dimension::Row), Some((1, 1)));
+}
+
+#[test]
+fn norms() {
+    // [ 1 -2  3
+    //  -4  5 -6 ]
+    let a: Matrix<f64> = Matrix::new(vec![1.0, -2.0, 3.0, -4.0, 5.0, -6.0], (2, 3)).unwrap();
+
+    assert!((a.norm_frobenius() - 91f64.sqrt()).abs() < 1e-12);
+    // Column sums are 5, 7, 9
+    assert_eq!(a.norm_l1(), 9.0);
+    // Row sums are 6, 15
+    assert_eq!(a.norm_inf(), 15.0);
+
+    let b: Matrix<i32> = Matrix::new(vec![-7, 1, 2, 0, -3, 4], (3, 2)).unwrap();
+
+    assert_eq!(b.norm_l1(), 12);
+    assert_eq!(b.norm_inf(), 8);
+}
+
+#[test]
+fn trace() {
+    assert_eq!(Matrix::<i32>::eye(4).trace(), Some(4));
+    assert_eq!(Matrix::<f64>::init(1.5, (3, 3)).trace(), Some(4.5));
+    assert_eq!(Matrix::<i32>::zeros((2, 3)).trace(), None);
+}
+
+#[test]
+fn display_truncation() {
+    let data: Vec<f64> = (0..10000).map(|e| e as f64).collect();
+    let large: Matrix<f64> = Matrix::new(data, (100, 100)).unwrap();
+
+    let out = format!("{}", large);
+
+    for corner in ["0.0000", "99.0000", "9900.0000", "9999.0000"] {
+        assert!(out.contains(corner), "{corner} missing from\n{out}");
+    }
+    assert!(!out.contains("5050.0000"));
+    assert!(out.contains("\n ...\n"));
+    assert!(out.contains("shape=(100, 100), dtype=f64"));
+    // 7 printed rows of 7 columns
+    assert_eq!(out.lines().count(), 8);
+    assert!(out.len() < 1000);
+
+    let small: Matrix<i32> = Matrix::new(vec![1, 20, 300, 4], (2, 2)).unwrap();
+
+    assert_eq!(
+        format!("{}", small),
+        "[[  1  20]\n [300   4]]\nshape=(2, 2), dtype=i32\n"
+    );
+}
+
+#[test]
+fn kron() {
+    let a: Matrix<i32> = Matrix::new(vec![1, 2, 3, 4], (2, 2)).unwrap();
+    let b: Matrix<i32> = Matrix::new(vec![0, 5, 6, 7], (2, 2)).unwrap();
+
+    let result = a.kron(&b);
+
+    assert_eq!(result.shape(), (4, 4));
+    assert_eq!(
+        result.get_vec(),
+        vec![
+            0, 5, 0, 10, //
+            6, 7, 12, 14, //
+            0, 15, 0, 20, //
+            18, 21, 24, 28,
+        ]
+    );
+
+    let c: Matrix<f64> = Matrix::init(2.0, (2, 3));
+    let d: Matrix<f64> = Matrix::init(0.5, (3, 2));
+
+    let result = c.kron(&d);
+
+    assert_eq!(result.shape(), (6, 6));
+    assert!(result.all(|&e| e == 1.0));
+}
+
+#[test]
+fn vdot_outer() {
+    let row: Matrix<i32> = Matrix::new(vec![1, 2, 3], (1, 3)).unwrap();
+    let col: Matrix<i32> = Matrix::new(vec![4, 5, 6], (3, 1)).unwrap();
+
+    assert_eq!(row.vdot(&col).unwrap(), 32);
+    assert_eq!(col.vdot(&row).unwrap(), 32);
+    assert_eq!(row.vdot(&row).unwrap(), 14);
+
+    let short: Matrix<i32> = Matrix::new(vec![1, 2], (1, 2)).unwrap();
+    let square: Matrix<i32> = Matrix::eye(3);
+
+    assert!(row.vdot(&short).is_err());
+    assert!(row.vdot(&square).is_err());
+
+    let outer = col.outer(&short).unwrap();
+
+    assert_eq!(outer.shape(), (3, 2));
+    assert_eq!(outer.get_vec(), vec![4, 8, 5, 10, 6, 12]);
+
+    let outer = short.outer(&col).unwrap();
+
+    assert_eq!(outer.shape(), (2, 3));
+    assert_eq!(outer.get_vec(), vec![4, 5, 6, 8, 10, 12]);
+
+    assert!(square.outer(&row).is_err());
+    assert!(row.outer(&square).is_err());
+}
+
+#[test]
+fn hadamard() {
+    let a: Matrix<i32> = Matrix::new(vec![1, 2, 3, 4], (2, 2)).unwrap();
+    let b: Matrix<i32> = Matrix::new(vec![5, 6, 7, 8], (2, 2)).unwrap();
+
+    let hadamard = a.hadamard(&b).unwrap();
+
+    assert_eq!(hadamard, a.mul(&b).unwrap());
+    assert_eq!(hadamard.get_vec(), vec![5, 12, 21, 32]);
+
+    let matmul = a.matmul(&b).unwrap();
+
+    assert_eq!(matmul.get_vec(), vec![19, 22, 43, 50]);
+    assert_ne!(hadamard, matmul);
+
+    assert!(a.hadamard(&Matrix::eye(3)).is_err());
+}
+
+#[test]
+fn matmul_small_shapes() {
+    let a: Matrix<i32> = Matrix::new(vec![1, 2], (1, 2)).unwrap();
+    let b: Matrix<i32> = Matrix::new(vec![5, 6, 7, 8], (2, 2)).unwrap();
+
+    assert_eq!(a.matmul(&b).unwrap().get_vec(), vec![19, 22]);
+}
+
+#[test]
+fn variance_std_dev() {
+    let a: Matrix<f64> = Matrix::new(vec![1.0, 2.0, 3.0, 4.0], (2, 2)).unwrap();
+
+    assert_eq!(a.variance(), 1.25);
+    assert_eq!(a.std_dev(), 1.25f64.sqrt());
+    assert!((a.variance_ddof(1) - 5.0 / 3.0).abs() < 1e-12);
+    assert!(a.variance_ddof(4).is_nan());
+
+    let single: Matrix<f64> = Matrix::init(7.0, (1, 1));
+
+    assert_eq!(single.variance(), 0.0);
+    assert_eq!(single.std_dev(), 0.0);
+
+    // [ 1 2
+    //   3 4 ]
+    assert_eq!(a.var_axis(Dimension::Row).get_vec(), vec![1.0, 1.0]);
+    assert_eq!(a.var_axis(Dimension::Row).shape(), (1, 2));
+    assert_eq!(a.var_axis(Dimension::Col).get_vec(), vec![0.25, 0.25]);
+    assert_eq!(a.var_axis(Dimension::Col).shape(), (2, 1));
+}
+
+#[test]
+fn csv_round_trip() {
+    let path = env::temp_dir().join(format!("linalg_rs_round_trip_{}.csv", std::process::id()));
+
+    let data: Vec<f64> = (0..12).map(|e| e as f64 / 4.0 - 1.0).collect();
+    let matrix: Matrix<f64> = Matrix::new(data, (3, 4)).unwrap();
+
+    matrix.to_csv(&path).unwrap();
+    let read: Matrix<f64> = Matrix::from_csv(&path, false).unwrap();
+
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(read, matrix);
+}
+
+#[test]
+fn csv_header_and_ragged_rows() {
+    let path = env::temp_dir().join(format!("linalg_rs_header_{}.csv", std::process::id()));
+
+    fs::write(&path, "a,b\n1, 2\n3,4\n").unwrap();
+    let read: Matrix<i32> = Matrix::from_csv(&path, true).unwrap();
+
+    assert_eq!(read.shape(), (2, 2));
+    assert_eq!(read.get_vec(), vec![1, 2, 3, 4]);
+
+    fs::write(&path, "1,2,3\n4,5\n").unwrap();
+    let ragged = Matrix::<i32>::from_csv(&path, false);
+
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(ragged, Err(MatrixError::MatrixParseError));
+}
+
+#[test]
+fn from_file_runtime_path() {
+    let path = env::temp_dir().join(format!("linalg_rs_from_file_{}.txt", std::process::id()));
+
+    fs::write(&path, "1 2 3\n4 5 6\n").unwrap();
+    let read: Matrix<i32> = Matrix::from_file(&path).unwrap();
+
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(read.shape(), (2, 3));
+    assert_eq!(read.get_vec(), vec![1, 2, 3, 4, 5, 6]);
+
+    let missing = Matrix::<i32>::from_file(&path);
+
+    assert_eq!(
+        missing,
+        Err(MatrixError::MatrixFileReadError(path.display().to_string()))
+    );
+}
+
+#[test]
+fn clamp() {
+    let a: Matrix<f64> = Matrix::new(vec![-3.0, -1.0, 0.0, 2.5, 4.0, 9.0], (2, 3)).unwrap();
+
+    let clamped = a.clamp(-1.0, 4.0);
+
+    assert_eq!(clamped.get_vec(), vec![-1.0, -1.0, 0.0, 2.5, 4.0, 4.0]);
+
+    let mut b = a.clone();
+    b.clamp_self(-1.0, 4.0);
+
+    assert_eq!(b, clamped);
+
+    // NaN is not comparable, so it passes through
+    let nan: Matrix<f64> = Matrix::new(vec![f64::NAN, 10.0], (1, 2)).unwrap();
+    let clamped = nan.clamp(0.0, 1.0);
+
+    assert!(clamped.at(0, 0).is_nan());
+    assert_eq!(clamped.at(0, 1), 1.0);
+}
+
+#[test]
+#[should_panic]
+fn clamp_invalid_range() {
+    Matrix::<i32>::eye(2).clamp(1, 0);
+}
+
+#[test]
+fn pow_powf() {
+    let a: Matrix<f64> = Matrix::new(vec![1.0, 4.0, 9.0, 2.0], (2, 2)).unwrap();
+
+    assert_eq!(a.powf(0.5), LinAlgFloats::sqrt(&a));
+
+    let b: Matrix<i32> = Matrix::new(vec![-2, 0, 3, 5], (2, 2)).unwrap();
+
+    let cubed: Vec<i32> = b.get_vec().iter().map(|e| e * e * e).collect();
+
+    assert_eq!(b.pow(3).get_vec(), cubed);
+}
+
+#[test]
+fn broadcasting() {
+    let a: Matrix<i32> = Matrix::new(vec![1, 2, 3, 4, 5, 6], (2, 3)).unwrap();
+    let row: Matrix<i32> = Matrix::new(vec![10, 20, 30], (1, 3)).unwrap();
+    let col: Matrix<i32> = Matrix::new(vec![100, 200], (2, 1)).unwrap();
+
+    assert_eq!(
+        a.add_broadcast(&row).unwrap().get_vec(),
+        vec![11, 22, 33, 14, 25, 36]
+    );
+    assert_eq!(
+        a.add_broadcast(&col).unwrap().get_vec(),
+        vec![101, 102, 103, 204, 205, 206]
+    );
+    assert_eq!(
+        a.sub_broadcast(&row).unwrap().get_vec(),
+        vec![-9, -18, -27, -6, -15, -24]
+    );
+    assert_eq!(
+        a.mul_broadcast(&col).unwrap().get_vec(),
+        vec![100, 200, 300, 800, 1000, 1200]
+    );
+    assert_eq!(
+        row.mul_val(2)
+            .div_broadcast(&Matrix::init(10, (1, 3)))
+            .unwrap()
+            .get_vec(),
+        vec![2, 4, 6]
+    );
+
+    // Same shape falls back to plain element-wise operations
+    assert_eq!(a.add_broadcast(&a).unwrap(), a.add(&a).unwrap());
+
+    let wrong_row: Matrix<i32> = Matrix::new(vec![1, 2], (1, 2)).unwrap();
+    let wrong_col: Matrix<i32> = Matrix::new(vec![1, 2, 3], (3, 1)).unwrap();
+
+    assert_eq!(
+        a.add_broadcast(&wrong_row),
+        Err(MatrixError::MatrixDimensionMismatchError)
+    );
+    assert_eq!(
+        a.add_broadcast(&wrong_col),
+        Err(MatrixError::MatrixDimensionMismatchError)
+    );
+    assert_eq!(
+        a.div_broadcast(&Matrix::zeros((1, 3))),
+        Err(MatrixError::MatrixDivideByZeroError)
+    );
+}
+
+#[test]
+fn rank() {
+    let eps = 1e-10;
+
+    assert_eq!(Matrix::<f64>::zeros((3, 3)).rank(eps), 0);
+    assert_eq!(Matrix::<f64>::eye(4).rank(eps), 4);
+
+    // Row 0 and row 2 are identical
+    let deficient: Matrix<f64> =
+        Matrix::new(vec![1.0, 2.0, 3.0, 0.0, 1.0, 4.0, 1.0, 2.0, 3.0], (3, 3)).unwrap();
+
+    assert_eq!(deficient.rank(eps), 2);
+
+    let wide: Matrix<f64> = Matrix::new(
+        vec![
+            1.0, 0.0, 2.0, 1.0, //
+            0.0, 1.0, 1.0, 3.0, //
+            2.0, 1.0, 0.0, 1.0,
+        ],
+        (3, 4),
+    )
+    .unwrap();
+
+    assert_eq!(wide.rank(eps), 3);
+    assert_eq!(wide.transpose_copy().rank(eps), 3);
+}
+
+fn sorted(mut v: Vec<f64>) -> Vec<f64> {
+    v.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    v
+}
+
+#[test]
+fn eigenvalues() {
+    // Eigenvalues 3 +- sqrt(2)
+    let a: Matrix<f64> = Matrix::new(vec![4.0, 1.0, 1.0, 2.0], (2, 2)).unwrap();
+    let eig = sorted(a.get_eigenvalues().unwrap());
+
+    assert!((eig[0] - (3.0 - 2f64.sqrt())).abs() < 1e-10);
+    assert!((eig[1] - (3.0 + 2f64.sqrt())).abs() < 1e-10);
+
+    // Eigenvalues 2 - sqrt(2), 2 and 2 + sqrt(2)
+    let b: Matrix<f64> = Matrix::new(
+        vec![2.0, -1.0, 0.0, -1.0, 2.0, -1.0, 0.0, -1.0, 2.0],
+        (3, 3),
+    )
+    .unwrap();
+    let eig = sorted(b.get_eigenvalues().unwrap());
+
+    for (got, expected) in eig.iter().zip([2.0 - 2f64.sqrt(), 2.0, 2.0 + 2f64.sqrt()]) {
+        assert!((got - expected).abs() < 1e-10, "{got} != {expected}");
+    }
+
+    // Already diagonal
+    let c: Matrix<f64> = Matrix::new(vec![5.0, 0.0, 0.0, -1.0], (2, 2)).unwrap();
+
+    assert_eq!(sorted(c.get_eigenvalues().unwrap()), vec![-1.0, 5.0]);
+
+    // Larger than the matmul block size. The eigenvalues have to sum
+    // to the trace, and their squares to the squared Frobenius norm
+    let n = 20;
+    let data: Vec<f64> = (0..n * n)
+        .map(|idx| 1.0 / ((idx / n + idx % n + 1) as f64))
+        .collect();
+    let hilbert: Matrix<f64> = Matrix::new(data, (n, n)).unwrap();
+    let eig = hilbert.get_eigenvalues().unwrap();
+
+    assert!((eig.iter().sum::<f64>() - hilbert.trace().unwrap()).abs() < 1e-10);
+    assert!(
+        (eig.iter().map(|e| e * e).sum::<f64>() - hilbert.norm_frobenius().powi(2)).abs() < 1e-10
+    );
+
+    assert_eq!(Matrix::<f64>::zeros((2, 3)).get_eigenvalues(), None);
+}
+
+#[test]
+fn qr() {
+    let eps = 1e-12;
+
+    let a: Matrix<f64> = Matrix::new(
+        vec![12.0, -51.0, 4.0, 6.0, 167.0, -68.0, -4.0, 24.0, -41.0],
+        (3, 3),
+    )
+    .unwrap();
+    let (q, r) = a.qr().unwrap();
+
+    assert_eq!(q.shape(), (3, 3));
+    assert_eq!(r.shape(), (3, 3));
+    assert!(approx_eq(&q.matmul(&r).unwrap(), &a, 1e-10));
+    assert!(approx_eq(
+        &q.transpose_copy().matmul(&q).unwrap(),
+        &Matrix::eye(3),
+        eps
+    ));
+    assert!(r.at(1, 0) == 0.0 && r.at(2, 0) == 0.0 && r.at(2, 1) == 0.0);
+
+    let tall: Matrix<f64> =
+        Matrix::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 9.0], (4, 2)).unwrap();
+    let (q, r) = tall.qr().unwrap();
+
+    assert_eq!(q.shape(), (4, 4));
+    assert_eq!(r.shape(), (4, 2));
+    assert!(approx_eq(&q.matmul(&r).unwrap(), &tall, 1e-10));
+    assert!(approx_eq(
+        &q.transpose_copy().matmul(&q).unwrap(),
+        &Matrix::eye(4),
+        eps
+    ));
+    assert!((1..4).all(|i| r.at(i, 0) == 0.0));
+    assert!((2..4).all(|i| r.at(i, 1) == 0.0));
+
+    assert!(Matrix::<f64>::zeros((2, 3)).qr().is_none());
+}
+
+#[test]
+fn cholesky() {
+    let a: Matrix<f64> = Matrix::new(
+        vec![4.0, 12.0, -16.0, 12.0, 37.0, -43.0, -16.0, -43.0, 98.0],
+        (3, 3),
+    )
+    .unwrap();
+
+    let l = a.cholesky().unwrap();
+
+    assert_eq!(
+        l.get_vec(),
+        vec![2.0, 0.0, 0.0, 6.0, 1.0, 0.0, -8.0, 5.0, 3.0]
+    );
+    assert!(approx_eq(
+        &l.matmul(&l.transpose_copy()).unwrap(),
+        &a,
+        1e-12
+    ));
+
+    // Eigenvalues 3 and -1
+    let indefinite: Matrix<f64> = Matrix::new(vec![1.0, 2.0, 2.0, 1.0], (2, 2)).unwrap();
+
+    assert!(matches!(
+        indefinite.cholesky(),
+        Err(MatrixError::DomainError(_))
+    ));
+    assert!(Matrix::<f64>::zeros((2, 2)).cholesky().is_err());
+
+    // The lower triangle alone is positive-definite
+    let asymmetric: Matrix<f64> = Matrix::new(vec![4.0, 100.0, 2.0, 5.0], (2, 2)).unwrap();
+
+    assert_eq!(
+        asymmetric.cholesky(),
+        Err(MatrixError::DomainError("matrix is not symmetric"))
+    );
+    assert_eq!(
+        Matrix::<f64>::ones((2, 3)).cholesky(),
+        Err(MatrixError::NotSquareError)
+    );
+}
+
+#[test]
+fn transpose_non_square() {
+    let a: Matrix<i32> = Matrix::new(vec![1, 2, 3, 4, 5, 6], (2, 3)).unwrap();
+
+    let mut b = a.clone();
+    b.transpose();
+
+    assert_eq!(b.shape(), (3, 2));
+    for i in 0..2 {
+        for j in 0..3 {
+            assert_eq!(b.at(j, i), a.at(i, j));
+        }
+    }
+
+    let mut c = a.clone();
+    c.t();
+
+    assert_eq!(c, b);
+    assert_eq!(a.transpose_copy(), b);
+    assert_eq!(b.transpose_copy(), a);
+
+    let wide: Matrix<i32> = Matrix::new((0..200).collect(), (2, 100)).unwrap();
+    let tall = wide.transpose_copy();
+
+    assert_eq!(tall.shape(), (100, 2));
+    assert_eq!(tall.row(42).unwrap(), &[42, 142]);
+}
+
+#[test]
+fn concat_and_extend() {
+    let a: Matrix<i32> = Matrix::new(vec![1, 2, 3, 4], (2, 2)).unwrap();
+    let b: Matrix<i32> = Matrix::new(vec![5, 6], (2, 1)).unwrap();
+
+    let res = a.concat(&b, Dimension::Col).unwrap();
+
+    assert_eq!(res.shape(), (2, 3));
+    assert_eq!(res.get_vec(), vec![1, 2, 5, 3, 4, 6]);
+
+    let mut c = a.clone();
+    c.extend(&b, Dimension::Col);
+
+    assert_eq!(c, res);
+
+    let res = a.concat(&b.transpose_copy(), Dimension::Row).unwrap();
+
+    assert_eq!(res.get_vec(), vec![1, 2, 3, 4, 5, 6]);
+    assert!(a.concat(&b, Dimension::Row).is_err());
+}
+
+#[test]
+fn concat_auto_transpose() {
+    let col: Matrix<i32> = Matrix::new(vec![1, 2, 3, 4], (4, 1)).unwrap();
+    let row: Matrix<i32> = Matrix::new(vec![5, 6, 7, 8], (1, 4)).unwrap();
+
+    assert!(col.concat(&row, Dimension::Col).is_err());
+
+    let res = col.concat_auto(&row, Dimension::Col).unwrap();
+
+    assert_eq!(res.shape(), (4, 2));
+    assert_eq!(res.get_vec(), vec![1, 5, 2, 6, 3, 7, 4, 8]);
+
+    let mut extended = col.clone();
+    assert_eq!(extended.extend_auto(&row, Dimension::Col), Ok(()));
+
+    assert_eq!(extended, res);
+
+    // Fits without transposing, so other is left as is
+    let res = row.concat_auto(&row, Dimension::Row).unwrap();
+
+    assert_eq!(res.shape(), (2, 4));
+
+    // Does not fit either way
+    let other: Matrix<i32> = Matrix::init(0, (3, 2));
+
+    assert_eq!(
+        col.concat_auto(&other, Dimension::Col),
+        Err(MatrixError::MatrixConcatinationError)
+    );
+
+    let mut unchanged = col.clone();
+    assert_eq!(
+        unchanged.extend_auto(&other, Dimension::Row),
+        Err(MatrixError::MatrixConcatinationError)
+    );
+
+    assert_eq!(unchanged, col);
+}
+
+#[test]
+fn try_reshape() {
+    let mut a: Matrix<i32> = Matrix::new((0..6).collect(), (2, 3)).unwrap();
+
+    assert_eq!(a.try_reshape(3, 2), Ok(()));
+    assert_eq!(a.shape(), (3, 2));
+    assert_eq!(a.row(2).unwrap(), &[4, 5]);
+
+    assert_eq!(a.try_reshape(4, 2), Err(MatrixError::MatrixCreationError));
+    assert_eq!(a.shape(), (3, 2));
+    assert_eq!(a.get_vec(), (0..6).collect::<Vec<i32>>());
+}
+
+#[test]
+fn set_rect_and_range() {
+    let mut a: Matrix<i32> = Matrix::zeros((4, 4));
+
+    a.set_rect((1, 1), (2, 3), 7).unwrap();
+
+    assert_eq!(
+        a.get_vec(),
+        vec![
+            0, 0, 0, 0, //
+            0, 7, 7, 7, //
+            0, 7, 7, 7, //
+            0, 0, 0, 0,
+        ]
+    );
+
+    let before = a.clone();
+
+    assert_eq!(
+        a.set_rect((2, 2), (4, 3), 1),
+        Err(MatrixError::MatrixIndexOutOfBoundsError)
+    );
+    assert_eq!(
+        a.set_rect((2, 2), (1, 3), 1),
+        Err(MatrixError::MatrixIndexOutOfBoundsError)
+    );
+    assert_eq!(a, before);
+
+    // Flat range past the end only sets what is in bounds
+    let mut b: Matrix<i32> = Matrix::zeros((2, 2));
+    b.set_range(2, 10, 3);
+
+    assert_eq!(b.get_vec(), vec![0, 0, 3, 3]);
+
+    b.set_range(7, 9, 5);
+
+    assert_eq!(b.get_vec(), vec![0, 0, 3, 3]);
+
+    b.set_range(1, usize::MAX, 4);
+
+    assert_eq!(b.get_vec(), vec![0, 4, 4, 4]);
+}
+
+#[test]
+fn seeded_randomize() {
+    let a: Matrix<f64> = Matrix::randomize_range_seeded(-1.0, 1.0, (5, 4), 1234);
+    let b: Matrix<f64> = Matrix::randomize_range_seeded(-1.0, 1.0, (5, 4), 1234);
+    let c: Matrix<f64> = Matrix::randomize_range_seeded(-1.0, 1.0, (5, 4), 4321);
+
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+    assert!(a.all(|&e| (-1.0..=1.0).contains(&e)));
+}
+
+#[test]
+fn shape_aware_equality() {
+    let a: Matrix<i32> = Matrix::new(vec![1, 2, 3, 4, 5, 6], (2, 3)).unwrap();
+    let b: Matrix<i32> = Matrix::new(vec![1, 2, 3, 4, 5, 6], (3, 2)).unwrap();
+    let c: Matrix<i32> = Matrix::new(vec![1, 2, 3, 4, 5, 7], (2, 3)).unwrap();
+
+    assert!(a.reshaped_eq(&b));
+    assert_ne!(a, b);
+    assert!(!a.same_shape(&b));
+
+    assert!(a.same_shape(&c));
+    assert!(!a.reshaped_eq(&c));
+
+    assert_eq!(a.add(&b), Err(MatrixError::MatrixDimensionMismatchError));
+}
+
+#[test]
+fn diagonals() {
+    let d: Matrix<i32> = Matrix::from_diagonal(&[1, 2, 3]);
+
+    assert_eq!(d.shape(), (3, 3));
+    assert_eq!(d.diagonal(), vec![1, 2, 3]);
+
+    // [ 1 2 3
+    //   4 5 6
+    //   7 8 9 ]
+    let a: Matrix<i32> = Matrix::new((1..10).collect(), (3, 3)).unwrap();
+
+    assert_eq!(a.diagonal_offset(1), vec![2, 6]);
+    assert_eq!(a.diagonal_offset(2), vec![3]);
+    assert_eq!(a.diagonal_offset(-1), vec![4, 8]);
+    assert!(a.diagonal_offset(-3).is_empty());
+
+    let tall: Matrix<i32> = Matrix::new((1..7).collect(), (3, 2)).unwrap();
+
+    assert_eq!(tall.diagonal(), vec![1, 4]);
+    assert_eq!(tall.diagonal_offset(-1), vec![3, 6]);
+
+    assert!(Matrix::<i32>::from_diagonal(&[]).diagonal().is_empty());
+}
+
+#[test]
+fn matrix_exp() {
+    let a: Matrix<i64> = Matrix::new(vec![1, 2, 3, 4], (2, 2)).unwrap();
+
+    assert_eq!(a.exp(0).unwrap(), Matrix::eye(2));
+    assert_eq!(a.exp(1).unwrap(), a);
+
+    let mut naive = a.clone();
+    for _ in 0..4 {
+        naive = naive.matmul(&a).unwrap();
+    }
+
+    assert_eq!(a.exp(5).unwrap(), naive);
+    assert_eq!(a.exp(5).unwrap().get_vec(), vec![1069, 1558, 2337, 3406]);
+
+    let b: Matrix<i64> = Matrix::ones((2, 3));
+
+    assert!(b.exp(0).is_none());
+    assert!(b.exp(2).is_none());
+}
+
+#[test]
+fn gram() {
+    let a: Matrix<f64> = Matrix::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], (3, 2)).unwrap();
+
+    let gram = a.gram();
+    assert_eq!(gram.shape(), (2, 2));
+    assert_eq!(gram.get_vec(), vec![35.0, 44.0, 44.0, 56.0]);
+
+    let outer = a.gram_outer();
+    assert_eq!(outer.shape(), (3, 3));
+    assert_eq!(
+        outer.get_vec(),
+        vec![5.0, 11.0, 17.0, 11.0, 25.0, 39.0, 17.0, 39.0, 61.0]
+    );
+
+    let b: Matrix<f64> = Matrix::new(vec![0.1, -0.7, 2.3, 1.9, 0.4, -1.2], (2, 3)).unwrap();
+
+    for m in [b.gram(), b.gram_outer()] {
+        assert!(approx_eq(&m, &m.transpose_copy(), 1e-12));
+    }
+}
+
+#[test]
+fn structural_predicates() {
+    let symmetric: Matrix<f64> =
+        Matrix::new(vec![2.0, 1.0, 5.0, 1.0, 3.0, -4.0, 5.0, -4.0, 0.5], (3, 3)).unwrap();
+
+    assert!(symmetric.is_symmetric(1e-12));
+    assert!(!symmetric.is_diagonal(1e-12));
+    assert!(!symmetric.is_orthogonal(1e-12));
+
+    let (s, c) = 1.2f64.sin_cos();
+    let rotation = Matrix::new(vec![c, -s, s, c], (2, 2)).unwrap();
+
+    assert!(rotation.is_orthogonal(1e-12));
+    assert!(!rotation.is_symmetric(1e-12));
+
+    let diag: Matrix<f64> = Matrix::from_diagonal(&[1.0, 4.0, 2.0]);
+
+    assert!(diag.is_diagonal(1e-12));
+    assert!(diag.is_symmetric(1e-12));
+    assert!(!diag.is_orthogonal(1e-12));
+    assert!(Matrix::<f64>::eye(4).is_orthogonal(1e-12));
+
+    let random: Matrix<f64> = Matrix::randomize_range_seeded(1.0, 2.0, (3, 3), 42);
+
+    assert!(!random.is_symmetric(1e-6));
+    assert!(!random.is_diagonal(1e-6));
+    assert!(!random.is_orthogonal(1e-6));
+
+    let rect: Matrix<f64> = Matrix::zeros((2, 3));
+
+    assert!(!rect.is_symmetric(1e-12));
+    assert!(!rect.is_orthogonal(1e-12));
+    assert!(rect.is_diagonal(1e-12));
+}
+
+#[test]
+fn binary_round_trip() {
+    let matrix: Matrix<f64> = Matrix::randomize_range_seeded(-1.0, 1.0, (100, 100), 7);
+
+    let bytes = matrix.to_bytes();
+    let decoded = Matrix::<f64>::from_bytes(&bytes).unwrap();
+
+    assert_eq!(decoded.shape(), (100, 100));
+    assert_eq!(decoded, matrix);
+
+    let ints: Matrix<i16> = Matrix::new(vec![-3, 0, 7, 12, -1, 5], (3, 2)).unwrap();
+    assert_eq!(Matrix::<i16>::from_bytes(&ints.to_bytes()).unwrap(), ints);
+
+    // Tag byte, then the elements as little-endian
+    let small: Matrix<i32> = Matrix::new(vec![1, -2], (1, 2)).unwrap();
+    let bytes = small.to_bytes();
+    assert_eq!(bytes[4], 2);
+    assert_eq!(bytes[21..], [1, 0, 0, 0, 0xfe, 0xff, 0xff, 0xff]);
+}
+
+#[test]
+fn binary_invalid_input() {
+    let matrix: Matrix<f64> = Matrix::init(1.5, (4, 4));
+    let bytes = matrix.to_bytes();
+
+    let mut corrupted = bytes.clone();
+    corrupted[0] = b'X';
+    assert_eq!(
+        Matrix::<f64>::from_bytes(&corrupted),
+        Err(MatrixError::MatrixParseError)
+    );
+
+    let mut corrupted = bytes.clone();
+    corrupted[5] = 0xff;
+    assert_eq!(
+        Matrix::<f64>::from_bytes(&corrupted),
+        Err(MatrixError::MatrixParseError)
+    );
+
+    assert!(Matrix::<f64>::from_bytes(&bytes[..10]).is_err());
+    assert!(Matrix::<f64>::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    assert!(Matrix::<f32>::from_bytes(&bytes).is_err());
+}
+
+#[test]
+fn conversions() {
+    let matrix: Matrix<i32> = Matrix::try_from(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+
+    assert_eq!(matrix.shape(), (2, 3));
+    assert_eq!(matrix.get_vec(), vec![1, 2, 3, 4, 5, 6]);
+
+    let ragged = Matrix::<i32>::try_from(vec![vec![1, 2, 3], vec![4, 5]]);
+
+    assert_eq!(ragged, Err(MatrixError::MatrixCreationError));
+
+    let from_tuple: Matrix<i32> = (vec![1, 2, 3, 4, 5, 6], (2, 3)).into();
+
+    assert_eq!(from_tuple, matrix);
+}
+
+#[test]
+fn iterator_integration() {
+    let matrix: Matrix<i32> = Matrix::new(vec![1, 2, 3, 4, 5, 6], (3, 2)).unwrap();
+
+    let sum: i32 = (&matrix).into_iter().sum();
+    assert_eq!(sum, 21);
+
+    let mut visited = Vec::new();
+    for &e in &matrix {
+        visited.push(e);
+    }
+    assert_eq!(visited, matrix.get_vec());
+
+    let built: Matrix<f64> =
+        Matrix::from_iter_shape((0..6).map(|i| i as f64 * 0.5), (2, 3)).unwrap();
+    assert_eq!(built.shape(), (2, 3));
+    assert_eq!(built.at(1, 2), 2.5);
+
+    assert_eq!(
+        Matrix::<f64>::from_iter_shape((0..5).map(|i| i as f64), (2, 3)),
+        Err(MatrixError::MatrixCreationError)
+    );
+}
+
+#[test]
+fn adjugate() {
+    let a: Matrix<i64> = Matrix::new(vec![2, -1, 0, 1, 3, 4, 0, 5, -2], (3, 3)).unwrap();
+
+    let det = a.determinant().unwrap();
+    let adj = a.adjugate().unwrap();
+
+    assert_eq!(det, -54);
+    assert_eq!(a.matmul(&adj).unwrap(), Matrix::eye(3).mul_val(det));
+    assert_eq!(adj.matmul(&a).unwrap(), Matrix::eye(3).mul_val(det));
+
+    assert_eq!(a.inverse_adjugate(), None);
+
+    let unimodular: Matrix<i64> = Matrix::new(vec![1, 2, 3, 0, 1, 4, 5, 6, 0], (3, 3)).unwrap();
+    let inv = unimodular.inverse_adjugate().unwrap();
+
+    assert_eq!(inv.get_vec(), vec![-24, 18, 5, 20, -15, -4, -5, 4, 1]);
+    assert_eq!(unimodular.matmul(&inv).unwrap(), Matrix::eye(3));
+
+    let f: Matrix<f64> = Matrix::new(vec![4.0, 7.0, 2.0, 6.0], (2, 2)).unwrap();
+    assert!(approx_eq(
+        &f.inverse_adjugate().unwrap(),
+        &f.inverse().unwrap(),
+        1e-12
+    ));
+
+    // Determinants that are not powers of two do not divide exactly
+    let diag: Matrix<f64> = Matrix::from_diagonal(&[49.0, 1.0]);
+    assert!(approx_eq(
+        &diag.inverse_adjugate().unwrap(),
+        &Matrix::from_diagonal(&[1.0 / 49.0, 1.0]),
+        1e-15
+    ));
+
+    let g: Matrix<f64> =
+        Matrix::new(vec![2.0, -1.0, 0.0, 1.0, 3.0, 4.0, 0.0, 5.0, -2.0], (3, 3)).unwrap();
+    let inv = g.inverse_adjugate().unwrap();
+    assert!(approx_eq(&inv, &g.inverse().unwrap(), 1e-12));
+    assert!(approx_eq(&g.matmul(&inv).unwrap(), &Matrix::eye(3), 1e-12));
+
+    assert!(Matrix::<i64>::ones((2, 3)).cofactor_matrix().is_none());
+    assert!(Matrix::<i64>::ones((2, 3)).adjugate().is_none());
+}
+
+#[test]
+fn elementary_row_and_col_operations() {
+    let mut matrix: Matrix<i32> = Matrix::new(vec![1, 2, 3, 4, 5, 6, 7, 8, 9], (3, 3)).unwrap();
+
+    matrix.swap_rows(0, 1).unwrap();
+    assert_eq!(matrix.get_vec(), vec![4, 5, 6, 1, 2, 3, 7, 8, 9]);
+
+    matrix.scale_row(2, 2).unwrap();
+    assert_eq!(matrix.get_vec(), vec![4, 5, 6, 1, 2, 3, 14, 16, 18]);
+
+    matrix.add_scaled_row(0, 1, -4).unwrap();
+    assert_eq!(matrix.get_vec(), vec![0, -3, -6, 1, 2, 3, 14, 16, 18]);
+
+    matrix.swap_cols(0, 2).unwrap();
+    assert_eq!(matrix.get_vec(), vec![-6, -3, 0, 3, 2, 1, 18, 16, 14]);
+
+    matrix.scale_col(1, 3).unwrap();
+    assert_eq!(matrix.get_vec(), vec![-6, -9, 0, 3, 6, 1, 18, 48, 14]);
+
+    matrix.add_scaled_col(2, 0, 1).unwrap();
+    assert_eq!(matrix.get_vec(), vec![-6, -9, -6, 3, 6, 4, 18, 48, 32]);
+
+    let before = matrix.clone();
+
+    assert_eq!(
+        matrix.swap_rows(0, 3),
+        Err(MatrixError::MatrixIndexOutOfBoundsError)
+    );
+    assert!(matrix.scale_row(3, 2).is_err());
+    assert!(matrix.add_scaled_row(3, 0, 1).is_err());
+    assert!(matrix.add_scaled_row(0, 3, 1).is_err());
+    assert!(matrix.swap_cols(5, 0).is_err());
+    assert!(matrix.scale_col(3, 2).is_err());
+    assert!(matrix.add_scaled_col(0, 3, 1).is_err());
+
+    assert_eq!(matrix, before);
+}
+
+#[test]
+fn pseudo_inverse() {
+    // Points on the line y = 2x + 1, fitted as [1 x] * [b a]^T = y
+    let a: Matrix<f64> = Matrix::new(vec![1.0, 0.0, 1.0, 1.0, 1.0, 2.0, 1.0, 3.0], (4, 2)).unwrap();
+    let y: Matrix<f64> = Matrix::new(vec![1.0, 3.0, 5.0, 7.0], (4, 1)).unwrap();
+
+    let pinv = a.pinv().unwrap();
+    assert_eq!(pinv.shape(), (2, 4));
+
+    let coeffs = pinv.matmul(&y).unwrap();
+    assert!(approx_eq(
+        &coeffs,
+        &Matrix::new(vec![1.0, 2.0], (2, 1)).unwrap(),
+        1e-10
+    ));
+
+    let reconstructed = a.matmul(&pinv).unwrap().matmul(&a).unwrap();
+    assert!(approx_eq(&reconstructed, &a, 1e-10));
+
+    let wide: Matrix<f64> = Matrix::ones((2, 3));
+    assert!(wide.pinv().is_none());
+
+    let rank_deficient: Matrix<f64> = Matrix::ones((3, 2));
+    assert!(rank_deficient.pinv().is_none());
+}
+
+#[test]
+fn least_squares() {
+    // Noisy samples of y = 1.5x - 4
+    let noise = [
+        0.03, -0.02, 0.01, -0.04, 0.02, 0.0, -0.01, 0.04, -0.03, 0.01,
+    ];
+
+    let xs: Vec<f64> = (0..noise.len()).map(|i| i as f64).collect();
+    let ys: Vec<f64> = xs
+        .iter()
+        .zip(noise.iter())
+        .map(|(x, e)| 1.5 * x - 4.0 + e)
+        .collect();
+
+    let design: Matrix<f64> =
+        Matrix::from_iter_shape(xs.iter().flat_map(|&x| [x, 1.0]), (xs.len(), 2)).unwrap();
+    let b: Matrix<f64> = Matrix::new(ys, (xs.len(), 1)).unwrap();
+
+    let fit = design.lstsq(&b).unwrap();
+
+    assert_eq!(fit.shape(), (2, 1));
+    assert!((fit.at(0, 0) - 1.5).abs() < 1e-2);
+    assert!((fit.at(1, 0) + 4.0).abs() < 5e-2);
+
+    let via_pinv = design.pinv().unwrap().matmul(&b).unwrap();
+    assert!(approx_eq(&fit, &via_pinv, 1e-9));
+
+    let wrong_rows: Matrix<f64> = Matrix::ones((3, 1));
+    assert_eq!(
+        design.lstsq(&wrong_rows),
+        Err(MatrixError::MatrixDimensionMismatchError)
+    );
+
+    let rank_deficient: Matrix<f64> = Matrix::ones((4, 2));
+    assert!(rank_deficient.lstsq(&Matrix::ones((4, 1))).is_err());
+}
+
+#[test]
+fn nnz_and_density() {
+    let eye: Matrix<f64> = Matrix::eye(4);
+
+    assert_eq!(eye.nnz(), 4);
+    assert_eq!(eye.density(), 0.25);
+    assert_eq!(eye.density(), 1.0 - eye.sparsity());
+
+    let mixed: Matrix<i32> = Matrix::new(vec![0, -1, 2, 0, 0, 3], (2, 3)).unwrap();
+
+    assert_eq!(mixed.nnz(), 3);
+    assert_eq!(mixed.density(), 0.5);
+    assert_eq!(Matrix::<i32>::zeros((3, 3)).nnz(), 0);
+}
+
+#[test]
+fn zip_map() {
+    let a: Matrix<f64> = Matrix::new(vec![1.0, -4.0, 3.5, 0.0, 9.0, -1.0], (2, 3)).unwrap();
+    let b: Matrix<f64> = Matrix::new(vec![2.0, -5.0, 3.0, 1.0, -9.0, 0.0], (2, 3)).unwrap();
+
+    let max = a.zip_map(&b, f64::max).unwrap();
+    assert_eq!(max.get_vec(), vec![2.0, -4.0, 3.5, 1.0, 9.0, 0.0]);
+
+    let alpha = 0.25;
+    let blend = a.zip_map(&b, |x, y| alpha * x + (1.0 - alpha) * y).unwrap();
+    assert_eq!(blend.get_vec(), vec![1.75, -4.75, 3.125, 0.75, -4.5, -0.25]);
+
+    let c: Matrix<f64> = Matrix::ones((3, 2));
+    assert_eq!(
+        a.zip_map(&c, f64::max),
+        Err(MatrixError::MatrixDimensionMismatchError)
+    );
+}
+
+#[test]
+fn element_wise_extremes() {
+    let a: Matrix<f64> = Matrix::new(vec![-3.0, 5.0, -0.5, 2.0, -8.0, 7.0], (3, 2)).unwrap();
+    let b: Matrix<f64> = Matrix::new(vec![-4.0, 6.0, -1.5, 3.0, -7.0, 6.5], (3, 2)).unwrap();
+
+    assert_eq!(
+        a.maximum(&b).unwrap().get_vec(),
+        vec![-3.0, 6.0, -0.5, 3.0, -7.0, 7.0]
+    );
+    assert_eq!(
+        a.minimum(&b).unwrap().get_vec(),
+        vec![-4.0, 5.0, -1.5, 2.0, -8.0, 6.5]
+    );
+
+    let nan: Matrix<f64> = Matrix::new(vec![f64::NAN, 1.0], (1, 2)).unwrap();
+    let other: Matrix<f64> = Matrix::new(vec![0.0, f64::NAN], (1, 2)).unwrap();
+
+    assert!(nan.maximum(&other).unwrap().all(|e| e.is_nan()));
+    assert!(nan.minimum(&other).unwrap().all(|e| e.is_nan()));
+
+    assert_eq!(
+        a.maximum(&Matrix::ones((2, 3))),
+        Err(MatrixError::MatrixDimensionMismatchError)
+    );
+    assert!(a.minimum(&Matrix::ones((2, 3))).is_err());
+}
+
+#[test]
+fn softmax_and_normalize() {
+    let matrix: Matrix<f64> = Matrix::new(
+        vec![1.0, 3.0, 2.0, -5.0, 0.5, 0.25, 800.0, 799.0, 801.0],
+        (3, 3),
+    )
+    .unwrap();
+
+    let soft = matrix.softmax_axis(Dimension::Col);
+
+    assert!(soft.all(|e| e.is_finite() && *e > 0.0));
+
+    for (row, original) in soft.row_iter().zip(matrix.row_iter()) {
+        assert!((row.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+
+        let argmax = |v: &[f64]| {
+            v.iter()
+                .enumerate()
+                .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())
+                .unwrap()
+                .0
+        };
+        assert_eq!(argmax(row), argmax(original));
+    }
+
+    let soft_cols = matrix.softmax_axis(Dimension::Row);
+    for sum in soft_cols.sum_axis(Dimension::Row).get_vec() {
+        assert!((sum - 1.0).abs() < 1e-12);
+    }
+
+    let with_zero_row: Matrix<f64> = Matrix::new(vec![2.0, 6.0, 1.0, -1.0], (2, 2)).unwrap();
+
+    assert_eq!(
+        with_zero_row.normalize_axis(Dimension::Col).get_vec(),
+        vec![0.25, 0.75, 0.0, 0.0]
+    );
+    assert_eq!(
+        with_zero_row.normalize_axis(Dimension::Row).get_vec(),
+        vec![2.0 / 3.0, 1.2, 1.0 / 3.0, -0.2]
+    );
+}
+
+#[test]
+fn activations() {
+    let matrix: Matrix<f64> =
+        Matrix::new(vec![-3.0, -0.5, 0.0, 0.5, 2.0, -1000.0], (2, 3)).unwrap();
+
+    assert_eq!(matrix.relu().get_vec(), vec![0.0, 0.0, 0.0, 0.5, 2.0, 0.0]);
+    assert_eq!(
+        matrix.leaky_relu(0.5).get_vec(),
+        vec![-1.5, -0.25, 0.0, 0.5, 2.0, -500.0]
+    );
+
+    let sig = matrix.sigmoid();
+
+    assert_eq!(sig.at(0, 2), 0.5);
+    assert_eq!(sig.at(1, 2), 0.0);
+    assert!((sig.at(0, 1) + sig.at(1, 0) - 1.0).abs() < 1e-15);
+    assert!(sig.all(|e| (0.0..=1.0).contains(e)));
+    assert_eq!(Matrix::<f64>::init(1000.0, (1, 1)).sigmoid().at(0, 0), 1.0);
+}
+
+#[test]
+fn sqrt_domain() {
+    let positive: Matrix<f64> = Matrix::new(vec![16.0, 0.25, 0.0, 2.25], (2, 2)).unwrap();
+
+    assert_eq!(
+        LinAlgFloats::sqrt(&positive).get_vec(),
+        vec![4.0, 0.5, 0.0, 1.5]
+    );
+    assert_eq!(positive.try_sqrt().unwrap(), LinAlgFloats::sqrt(&positive));
+
+    let negative: Matrix<f64> = Matrix::new(vec![16.0, -4.0, 9.0, 1.0], (2, 2)).unwrap();
+
+    let rooted = LinAlgFloats::sqrt(&negative);
+    assert!(rooted.at(0, 1).is_nan());
+    assert_eq!(rooted.at(1, 0), 3.0);
+
+    assert!(matches!(
+        negative.try_sqrt(),
+        Err(MatrixError::DomainError(_))
+    ));
+}
+
+#[test]
+fn remove_row_and_col() {
+    let square: Matrix<i32> = Matrix::new(vec![1, 2, 3, 4, 5, 6, 7, 8, 9], (3, 3)).unwrap();
+
+    let res = square.remove_row(1).unwrap();
+    assert_eq!(res.shape(), (2, 3));
+    assert_eq!(res.get_vec(), vec![1, 2, 3, 7, 8, 9]);
+
+    let wide: Matrix<i32> =
+        Matrix::new(vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12], (3, 4)).unwrap();
+
+    let res = wide.remove_col(2).unwrap();
+    assert_eq!(res.shape(), (3, 3));
+    assert_eq!(res.get_vec(), vec![1, 2, 4, 5, 6, 8, 9, 10, 12]);
+
+    let res = wide.remove_col(3).unwrap();
+    assert_eq!(res.get_vec(), vec![1, 2, 3, 5, 6, 7, 9, 10, 11]);
+
+    assert_eq!(
+        square.remove_row(3),
+        Err(MatrixError::MatrixIndexOutOfBoundsError)
+    );
+    assert_eq!(
+        wide.remove_col(4),
+        Err(MatrixError::MatrixIndexOutOfBoundsError)
+    );
+}
+
+#[test]
+fn insert_row_and_col() {
+    let matrix: Matrix<i32> = Matrix::new(vec![1, 2, 3, 4, 5, 6], (2, 3)).unwrap();
+
+    let start = matrix.insert_row(0, &[7, 8, 9]).unwrap();
+    assert_eq!(start.shape(), (3, 3));
+    assert_eq!(start.get_vec(), vec![7, 8, 9, 1, 2, 3, 4, 5, 6]);
+
+    let middle = matrix.insert_row(1, &[7, 8, 9]).unwrap();
+    assert_eq!(middle.get_vec(), vec![1, 2, 3, 7, 8, 9, 4, 5, 6]);
+
+    let end = matrix.insert_row(2, &[7, 8, 9]).unwrap();
+    assert_eq!(end.get_vec(), vec![1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+    let start = matrix.insert_col(0, &[0, 0]).unwrap();
+    assert_eq!(start.shape(), (2, 4));
+    assert_eq!(start.get_vec(), vec![0, 1, 2, 3, 0, 4, 5, 6]);
+
+    let middle = matrix.insert_col(2, &[-1, -2]).unwrap();
+    assert_eq!(middle.get_vec(), vec![1, 2, -1, 3, 4, 5, -2, 6]);
+
+    let end = matrix.insert_col(3, &[10, 20]).unwrap();
+    assert_eq!(end.get_vec(), vec![1, 2, 3, 10, 4, 5, 6, 20]);
+
+    assert_eq!(middle.remove_col(2).unwrap(), matrix);
+
+    // Removing the only column leaves a 3 x 0 matrix that still has rows
+    let column: Matrix<i32> = Matrix::new(vec![1, 2, 3], (3, 1)).unwrap();
+    let empty = column.remove_col(0).unwrap();
+    assert_eq!(empty.shape(), (3, 0));
+    assert_eq!(empty.insert_col(0, &[1, 2, 3]).unwrap(), column);
+
+    assert_eq!(
+        matrix.insert_row(3, &[7, 8, 9]),
+        Err(MatrixError::MatrixIndexOutOfBoundsError)
+    );
+    assert_eq!(
+        matrix.insert_row(1, &[7, 8]),
+        Err(MatrixError::MatrixDimensionMismatchError)
+    );
+    assert_eq!(
+        matrix.insert_col(4, &[1, 2]),
+        Err(MatrixError::MatrixIndexOutOfBoundsError)
+    );
+    assert_eq!(
+        matrix.insert_col(1, &[1, 2, 3]),
+        Err(MatrixError::MatrixDimensionMismatchError)
+    );
+}
+
+#[test]
+fn tile() {
+    let matrix: Matrix<i32> = Matrix::new(vec![1, 2, 3, 4], (2, 2)).unwrap();
+
+    let tiled = matrix.tile(2, 3);
+
+    assert_eq!(tiled.shape(), (4, 6));
+    assert_eq!(tiled.row(0), Some(&[1, 2, 1, 2, 1, 2][..]));
+    assert_eq!(tiled.row(3), Some(&[3, 4, 3, 4, 3, 4][..]));
+
+    // Block boundaries
+    assert_eq!(tiled.at(1, 1), 4);
+    assert_eq!(tiled.at(1, 2), 3);
+    assert_eq!(tiled.at(2, 1), 2);
+    assert_eq!(tiled.at(2, 2), 1);
+    assert_eq!(tiled.at(3, 5), 4);
+
+    assert_eq!(tiled.get_sub_matrix((2, 4), (2, 2)).unwrap(), matrix);
+
+    assert_eq!(matrix.tile(1, 1), matrix);
+    assert_eq!(matrix.tile(0, 3).shape(), (0, 6));
+    assert_eq!(matrix.tile(2, 0).size(), 0);
+}
+
+#[test]
+fn flips_and_rotations() {
+    // 1 2 3
+    // 4 5 6
+    let matrix: Matrix<i32> = Matrix::new(vec![1, 2, 3, 4, 5, 6], (2, 3)).unwrap();
+
+    assert_eq!(matrix.flip_horizontal().get_vec(), vec![3, 2, 1, 6, 5, 4]);
+    assert_eq!(matrix.flip_vertical().get_vec(), vec![4, 5, 6, 1, 2, 3]);
+
+    let once = matrix.rot90(1);
+    assert_eq!(once.shape(), (3, 2));
+    assert_eq!(once.get_vec(), vec![3, 6, 2, 5, 1, 4]);
+
+    let twice = matrix.rot90(2);
+    assert_eq!(twice.shape(), (2, 3));
+    assert_eq!(twice.get_vec(), vec![6, 5, 4, 3, 2, 1]);
+    assert_eq!(twice, matrix.flip_horizontal().flip_vertical());
+
+    let thrice = matrix.rot90(3);
+    assert_eq!(thrice.shape(), (3, 2));
+    assert_eq!(thrice.get_vec(), vec![4, 1, 5, 2, 6, 3]);
+    assert_eq!(thrice, once.rot90(2));
+
+    assert_eq!(matrix.rot90(4), matrix);
+    assert_eq!(matrix.rot90(0), matrix);
+    assert_eq!(matrix.rot90(5), once);
+}
+
+#[test]
+fn hstack_and_vstack() {
+    let r1: Matrix<i32> = Matrix::new(vec![1, 2, 3], (1, 3)).unwrap();
+    let r2: Matrix<i32> = Matrix::new(vec![4, 5, 6], (1, 3)).unwrap();
+    let r3: Matrix<i32> = Matrix::new(vec![7, 8, 9], (1, 3)).unwrap();
+
+    let v = Matrix::vstack(&[&r1, &r2, &r3]).unwrap();
+    assert_eq!(v.shape(), (3, 3));
+    assert_eq!(v.get_vec(), vec![1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+    let c1: Matrix<i32> = Matrix::new(vec![1, 4, 7], (3, 1)).unwrap();
+    let c2: Matrix<i32> = Matrix::new(vec![2, 5, 8], (3, 1)).unwrap();
+    let c3: Matrix<i32> = Matrix::new(vec![3, 6, 9], (3, 1)).unwrap();
+
+    let h = Matrix::hstack(&[&c1, &c2, &c3]).unwrap();
+    assert_eq!(h.shape(), (3, 3));
+    assert_eq!(h, v);
+
+    assert_eq!(Matrix::vstack(&[&r1]).unwrap(), r1);
+    assert_eq!(Matrix::hstack(&[&c1]).unwrap(), c1);
+
+    assert_eq!(
+        Matrix::<i32>::vstack(&[]),
+        Err(MatrixError::MatrixConcatinationError)
+    );
+    assert_eq!(
+        Matrix::<i32>::hstack(&[]),
+        Err(MatrixError::MatrixConcatinationError)
+    );
+    assert_eq!(
+        Matrix::vstack(&[&r1, &c1]),
+        Err(MatrixError::MatrixConcatinationError)
+    );
+    assert_eq!(
+        Matrix::hstack(&[&c1, &r1]),
+        Err(MatrixError::MatrixConcatinationError)
+    );
+}
+
+#[test]
+fn comparison_masks() {
+    let a: Matrix<f64> = Matrix::new(vec![0.5, -1.0, 2.0, 3.0, 0.0, 7.5], (2, 3)).unwrap();
+    let b: Matrix<f64> = Matrix::new(vec![1.0, -1.0, 1.5, 3.5, -2.0, 7.5], (2, 3)).unwrap();
+
+    assert_eq!(a.gt_elem(&b).unwrap().get_vec(), vec![0, 0, 1, 0, 1, 0]);
+    assert_eq!(a.lt_elem(&b).unwrap().get_vec(), vec![1, 0, 0, 1, 0, 0]);
+    assert_eq!(a.ge_elem(&b).unwrap().get_vec(), vec![0, 1, 1, 0, 1, 1]);
+    assert_eq!(a.le_elem(&b).unwrap().get_vec(), vec![1, 1, 0, 1, 0, 1]);
+    assert_eq!(a.eq_elem(&b).unwrap().get_vec(), vec![0, 1, 0, 0, 0, 1]);
+    assert_eq!(a.gt_elem(&b).unwrap().shape(), (2, 3));
+
+    assert_eq!(a.gt_val(1.0).get_vec(), vec![0, 0, 1, 1, 0, 1]);
+    assert_eq!(a.lt_val(1.0).get_vec(), vec![1, 1, 0, 0, 1, 0]);
+    assert_eq!(a.ge_val(3.0).get_vec(), vec![0, 0, 0, 1, 0, 1]);
+    assert_eq!(a.le_val(0.0).get_vec(), vec![0, 1, 0, 0, 1, 0]);
+    assert_eq!(a.eq_val(2.0).get_vec(), vec![0, 0, 1, 0, 0, 0]);
+
+    assert_eq!(
+        a.gt_elem(&Matrix::ones((3, 2))),
+        Err(MatrixError::MatrixDimensionMismatchError)
+    );
+    assert!(a.eq_elem(&Matrix::ones((3, 2))).is_err());
+
+    // PartialOrd is still reachable through the plain names
+    let bigger: Matrix<f64> = Matrix::init(10.0, (2, 3));
+    assert!(bigger.gt(&a));
+    assert!(a.le(&bigger));
+}
+
+#[test]
+fn select_and_blend() {
+    let a: Matrix<f64> = Matrix::new(vec![0.5, -1.0, 2.0, 3.0, 0.0, 7.5], (2, 3)).unwrap();
+    let b: Matrix<f64> = Matrix::new(vec![1.0, -1.0, 1.5, 3.5, -2.0, 7.5], (2, 3)).unwrap();
+
+    // np.where(a > 1, 1, 0)
+    let thresholded = a.select(&a.gt_val(1.0), 1.0, 0.0).unwrap();
+    assert_eq!(thresholded.get_vec(), vec![0.0, 0.0, 1.0, 1.0, 0.0, 1.0]);
+
+    // np.where(a < 0, 0, a)
+    let clipped = a.blend(&Matrix::zeros(a.shape()), &a.ge_val(0.0)).unwrap();
+    assert_eq!(clipped.get_vec(), vec![0.5, 0.0, 2.0, 3.0, 0.0, 7.5]);
+
+    // np.where(a < b, a, b)
+    let min = a.blend(&b, &a.lt_elem(&b).unwrap()).unwrap();
+    assert_eq!(min, a.minimum(&b).unwrap());
+
+    let wrong_mask = Matrix::<f64>::ones((3, 2)).gt_val(0.0);
+    assert_eq!(
+        a.select(&wrong_mask, 1.0, 0.0),
+        Err(MatrixError::MatrixDimensionMismatchError)
+    );
+    assert!(a.blend(&b, &wrong_mask).is_err());
+    assert!(a.blend(&Matrix::ones((3, 2)), &a.gt_val(0.0)).is_err());
+}
+
+#[test]
+fn matmul_algorithms_agree() {
+    let a: Matrix<f64> =
+        Matrix::from_iter_shape((0..35).map(|i| (i % 7) as f64 - 2.5), (7, 5)).unwrap();
+    let b: Matrix<f64> =
+        Matrix::from_iter_shape((0..45).map(|i| (i % 4) as f64 * 0.5), (5, 9)).unwrap();
+
+    let naive = a.matmul_with(&b, MatMulAlgo::Naive).unwrap();
+    let blocked = a.matmul_with(&b, MatMulAlgo::Blocked).unwrap();
+    let auto = a.matmul_with(&b, MatMulAlgo::AutoBlocked).unwrap();
+
+    assert_eq!(naive.shape(), (7, 9));
+    assert_eq!(naive, blocked);
+    assert_eq!(naive, auto);
+    assert_eq!(auto, a.matmul(&b).unwrap());
+
+    let c: Matrix<f64> = Matrix::from_iter_shape((0..36).map(|i| i as f64), (6, 6)).unwrap();
+
+    assert_eq!(
+        c.matmul_with(&c, MatMulAlgo::Blocked).unwrap(),
+        c.matmul_with(&c, MatMulAlgo::Naive).unwrap()
+    );
+
+    assert_eq!(
+        a.matmul_with(&a, MatMulAlgo::Naive),
+        Err(MatrixError::MatrixDimensionMismatchError)
+    );
+}
+
+#[test]
+fn blocked_matmul_tails() {
+    // Block size 6 is picked here, leaving tails in both the k and j loops
+    let a: Matrix<i64> = Matrix::from_iter_shape((0..66).map(|i| i % 5 - 2), (6, 11)).unwrap();
+    let b: Matrix<i64> = Matrix::from_iter_shape((0..44).map(|i| i % 3 + 1), (11, 4)).unwrap();
+
+    let naive = a.matmul_with(&b, MatMulAlgo::Naive).unwrap();
+
+    assert_eq!(a.matmul_with(&b, MatMulAlgo::Blocked).unwrap(), naive);
+    assert_eq!(a.matmul(&b).unwrap(), naive);
+
+    let c: Matrix<i64> = Matrix::from_iter_shape((0..49).map(|i| i * 3 % 7), (7, 7)).unwrap();
+
+    assert_eq!(
+        c.matmul_with(&c, MatMulAlgo::Blocked).unwrap(),
+        c.matmul_with(&c, MatMulAlgo::Naive).unwrap()
+    );
+
+    // Large enough that the automatic block size splits the inner dimension
+    let d: Matrix<i64> = Matrix::from_iter_shape((0..1600).map(|i| i % 11 - 5), (40, 40)).unwrap();
+    let e: Matrix<i64> = Matrix::from_iter_shape((0..1880).map(|i| i % 7 - 3), (40, 47)).unwrap();
+
+    let naive = d.matmul_with(&d, MatMulAlgo::Naive).unwrap();
+    assert_eq!(d.matmul(&d).unwrap(), naive);
+    assert_eq!(d.matmul_with(&d, MatMulAlgo::Blocked).unwrap(), naive);
+
+    let naive = d.matmul_with(&e, MatMulAlgo::Naive).unwrap();
+    assert_eq!(d.matmul(&e).unwrap(), naive);
+    assert_eq!(d.matmul_with(&e, MatMulAlgo::Blocked).unwrap(), naive);
+}
+
+#[test]
+fn simd_matmul_matches_naive() {
+    let a: Matrix<f64> = Matrix::from_iter_shape(
+        (0..4096).map(|i| ((i * 7) % 13) as f64 * 0.25 - 1.5),
+        (64, 64),
+    )
+    .unwrap();
+    let b: Matrix<f64> = Matrix::from_iter_shape(
+        (0..4096).map(|i| ((i * 5) % 11) as f64 * 0.5 - 2.0),
+        (64, 64),
+    )
+    .unwrap();
+
+    let naive = a.matmul_with(&b, MatMulAlgo::Naive).unwrap();
+
+    assert!(approx_eq(&a.matmul(&b).unwrap(), &naive, 1e-9));
+
+    // Column count not divisible by the vector width
+    let c: Matrix<f64> =
+        Matrix::from_iter_shape((0..64 * 7).map(|i| (i % 9) as f64), (64, 7)).unwrap();
+
+    assert!(approx_eq(
+        &a.matmul(&c).unwrap(),
+        &a.matmul_with(&c, MatMulAlgo::Naive).unwrap(),
+        1e-9
+    ));
+}
+
+#[test]
+fn matmul_regression_100() {
+    let a: Matrix<i64> =
+        Matrix::from_iter_shape((0..10000).map(|i| i % 17 - 8), (100, 100)).unwrap();
+    let b: Matrix<i64> = Matrix::from_iter_shape((0..9900).map(|i| i % 13 - 6), (100, 99)).unwrap();
+
+    let mut expected = vec![0i64; 100 * 99];
+    for i in 0..100 {
+        for j in 0..99 {
+            expected[i * 99 + j] = (0..100)
+                .map(|k| a.get(i, k).unwrap() * b.get(k, j).unwrap())
+                .sum();
+        }
+    }
+    let expected = Matrix::new(expected, (100, 99)).unwrap();
+
+    assert_eq!(a.matmul_with(&b, MatMulAlgo::Naive).unwrap(), expected);
+    assert_eq!(a.matmul(&b).unwrap(), expected);
+
+    let af: Matrix<f64> =
+        Matrix::from_iter_shape((0..10000).map(|i| (i % 17) as f64 * 0.5), (100, 100)).unwrap();
+
+    assert!(approx_eq(
+        &af.matmul_with(&af, MatMulAlgo::Naive).unwrap(),
+        &af.matmul_with(&af, MatMulAlgo::Blocked).unwrap(),
+        1e-9
+    ));
+}
+
+#[test]
+fn row_parallel_matmul_50() {
+    let a: Matrix<i64> = Matrix::from_iter_shape((0..2500).map(|i| i % 23 - 11), (50, 50)).unwrap();
+    let b: Matrix<i64> =
+        Matrix::from_iter_shape((0..2500).map(|i| (i * 3) % 19 - 9), (50, 50)).unwrap();
+
+    let mut expected = vec![0i64; 2500];
+    for i in 0..50 {
+        for j in 0..50 {
+            expected[i * 50 + j] = (0..50)
+                .map(|k| a.get(i, k).unwrap() * b.get(k, j).unwrap())
+                .sum();
+        }
+    }
+    let expected = Matrix::new(expected, (50, 50)).unwrap();
+
+    assert_eq!(a.matmul_with(&b, MatMulAlgo::Naive).unwrap(), expected);
+    assert_eq!(a.matmul_with(&b, MatMulAlgo::Blocked).unwrap(), expected);
+    assert_eq!(a.matmul(&b).unwrap(), expected);
+}
+
+#[test]
+fn cast_between_element_types() {
+    let eye = Matrix::<i32>::eye(3);
+
+    let float: Matrix<f64> = eye.cast();
+    assert_eq!(float, Matrix::<f64>::eye(3));
+
+    let back: Matrix<i32> = float.cast();
+    assert_eq!(back, eye);
+
+    let fractional = Matrix::new(vec![2.9, -2.9, 0.5, -0.5, 7.0, 1e12], (3, 2)).unwrap();
+    let truncated: Matrix<i32> = fractional.cast();
+
+    assert_eq!(truncated.shape(), (3, 2));
+    assert_eq!(truncated.get_vec(), vec![2, -2, 0, 0, 7, i32::MAX]);
+
+    let nan: Matrix<i64> = Matrix::new(vec![f64::NAN], (1, 1)).unwrap().cast();
+    assert_eq!(nan.get_vec(), vec![0]);
+}
+
+#[test]
+fn tridiagonal_and_banded() {
+    let laplacian: Matrix<f64> = Matrix::tridiagonal(-1.0, 2.0, -1.0, 5);
+
+    assert_eq!(laplacian.shape(), (5, 5));
+    assert_eq!(laplacian.at(0, 0), 2.0);
+    assert_eq!(laplacian.at(2, 1), -1.0);
+    assert_eq!(laplacian.at(2, 3), -1.0);
+    assert_eq!(laplacian.at(4, 4), 2.0);
+
+    for i in 0..5usize {
+        for j in 0..5usize {
+            if i.abs_diff(j) > 1 {
+                assert_eq!(laplacian.at(i, j), 0.0);
+            }
+        }
+    }
+
+    // Every row sums to 0 except the boundary rows
+    assert_eq!(
+        laplacian.sum_axis(Dimension::Col).get_vec(),
+        vec![1.0, 0.0, 0.0, 0.0, 1.0]
+    );
+
+    let banded = Matrix::banded(
+        &[(-1, vec![-1.0; 4]), (0, vec![2.0; 5]), (1, vec![-1.0; 4])],
+        (5, 5),
+    )
+    .unwrap();
+
+    assert_eq!(banded, laplacian);
+
+    let rect = Matrix::banded(
+        &[(0, vec![1, 2, 3, 4]), (-2, vec![5, 6]), (3, vec![7])],
+        (4, 4),
+    )
+    .unwrap();
+    assert_eq!(rect.diagonal_offset(-2), vec![5, 6]);
+    assert_eq!(rect.at(0, 3), 7);
+    assert_eq!(rect.at(2, 2), 3);
+    assert_eq!(rect.at(2, 1), 0);
+
+    assert_eq!(
+        Matrix::banded(&[(0, vec![1, 2])], (2, 3))
+            .unwrap()
+            .get_vec(),
+        vec![1, 0, 0, 0, 2, 0]
+    );
+
+    assert_eq!(
+        Matrix::banded(&[(1, vec![1, 2, 3])], (3, 3)),
+        Err(MatrixError::MatrixCreationError)
+    );
+    assert_eq!(
+        Matrix::banded(&[(-3, vec![1])], (3, 3)),
+        Err(MatrixError::MatrixCreationError)
+    );
+    assert_eq!(Matrix::<i32>::tridiagonal(1, 1, 1, 0).size(), 0);
+}
+
+#[test]
+fn hilbert_and_vandermonde() {
+    let n = 6;
+    let hilbert = Matrix::<f64>::hilbert(n);
+
+    assert_eq!(hilbert.shape(), (n, n));
+    assert_eq!(hilbert.at(0, 0), 1.0);
+    assert_eq!(hilbert.at(n - 1, n - 1), 1.0 / 11.0);
+    assert!(hilbert.is_symmetric(0.0));
+
+    let x = [-1.5, 0.0, 2.0, 3.0];
+    let vander = Matrix::vandermonde(&x, 3);
+
+    assert_eq!(vander.shape(), (4, 4));
+    assert_eq!(vander.col(0).unwrap(), vec![1.0; 4]);
+    assert_eq!(vander.col(1).unwrap(), x.to_vec());
+    assert_eq!(vander.row(3).unwrap(), &[1.0, 3.0, 9.0, 27.0]);
+
+    // Distinct nodes give an invertible matrix, solving for the
+    // polynomial coefficients interpolating y = 1 + 2x - x^3
+    let y: Vec<f64> = x.iter().map(|&v| 1.0 + 2.0 * v - v * v * v).collect();
+    let b = Matrix::new(y, (4, 1)).unwrap();
+    let coeffs = vander.solve(&b).unwrap();
+
+    assert!(approx_eq(
+        &coeffs,
+        &Matrix::new(vec![1.0, 2.0, 0.0, -1.0], (4, 1)).unwrap(),
+        1e-9
+    ));
+
+    assert_eq!(Matrix::vandermonde(&[5], 0).get_vec(), vec![1]);
+
+    // The highest power fits the type, nothing past it is computed
+    let edge = Matrix::vandermonde(&[2i32], 30);
+    assert_eq!(edge.at(0, 30), 1 << 30);
+
+    let edge = Matrix::vandermonde(&[2i8], 6);
+    assert_eq!(edge.get_vec(), vec![1, 2, 4, 8, 16, 32, 64]);
+}
+
+#[test]
+fn row_slices_borrow_data() {
+    let mut matrix = Matrix::from_iter_shape(0..12, (3, 4)).unwrap();
+
+    let slice = matrix.row_slice(1).unwrap();
+    assert_eq!(slice, &[4, 5, 6, 7]);
+
+    // The slice points into the matrix storage, no copy is made
+    let storage: *const i32 = (&matrix).into_iter().next().unwrap();
+    assert_eq!(matrix.row_slice(0).unwrap().as_ptr(), storage);
+    assert_eq!(slice.as_ptr(), storage.wrapping_add(4));
+    assert!(matrix.row_slice(3).is_none());
+
+    for (j, elem) in matrix.row_slice_mut(2).unwrap().iter_mut().enumerate() {
+        *elem *= -(j as i32);
+    }
+
+    assert_eq!(matrix.at(2, 0), 0);
+    assert_eq!(matrix.at(2, 3), -33);
+    assert_eq!(matrix.row(2).unwrap(), &[0, -9, -20, -33]);
+    assert_eq!(matrix.row_slice(1).unwrap(), &[4, 5, 6, 7]);
+}
+
+#[test]
+fn json_round_trip() {
+    let matrix: Matrix<f64> = Matrix::new(vec![0.1, -2.5, 1e-7, 3.0, 0.0, 42.125], (3, 2)).unwrap();
+
+    let json = matrix.to_json().unwrap();
+    assert!(json.starts_with(r#"{"nrows":3,"ncols":2,"data":["#));
+    assert_eq!(Matrix::<f64>::from_json(&json).unwrap(), matrix);
+
+    let ints: Matrix<i64> = Matrix::eye(3);
+    assert_eq!(
+        Matrix::<i64>::from_json(&ints.to_json().unwrap()).unwrap(),
+        ints
+    );
+
+    let empty: Matrix<i32> = Matrix::from_json(r#"{"nrows":0,"ncols":4,"data":[]}"#).unwrap();
+    assert_eq!(empty.shape(), (0, 4));
+
+    for bad in [
+        r#"{"nrows":2,"ncols":2,"data":[1,2,3]}"#,
+        r#"{"nrows":2,"data":[1,2]}"#,
+        r#"{"nrows":1,"ncols":2,"data":[1,"a"]}"#,
+        "not json",
+    ] {
+        assert_eq!(
+            Matrix::<i32>::from_json(bad),
+            Err(MatrixError::MatrixParseError)
+        );
+    }
+}
+
+#[test]
+fn gather_and_scatter() {
+    let mut matrix = Matrix::from_iter_shape(0..16, (4, 4)).unwrap();
+
+    let diagonal: Vec<(usize, usize)> = (0..4).map(|i| (i, i)).collect();
+    assert_eq!(matrix.gather(&diagonal), matrix.diagonal());
+    assert_eq!(matrix.gather(&diagonal), vec![0, 5, 10, 15]);
+
+    // Out of bounds positions are skipped
+    assert_eq!(
+        matrix.gather(&[(3, 0), (0, 4), (4, 0), (0, 3)]),
+        vec![12, 3]
+    );
+    assert!(matrix.gather(&[]).is_empty());
+
+    let positions = [(0, 3), (2, 1), (3, 3), (1, 0)];
+    matrix.scatter(&positions, &[-1, -2, -3, -4]).unwrap();
+
+    assert_eq!(matrix.gather(&positions), vec![-1, -2, -3, -4]);
+    assert_eq!(matrix.at(2, 1), -2);
+    assert_eq!(matrix.at(2, 2), 10);
+
+    assert_eq!(
+        matrix.scatter(&positions, &[1, 2]),
+        Err(MatrixError::MatrixDimensionMismatchError)
+    );
+    assert_eq!(matrix.at(0, 3), -1);
+
+    matrix.scatter(&[(9, 9), (0, 0)], &[100, 50]).unwrap();
+    assert_eq!(matrix.at(0, 0), 50);
+    assert_eq!(matrix.size(), 16);
+}
+
+#[test]
+fn window_map_pooling_and_blur() {
+    let matrix: Matrix<i32> =
+        Matrix::new(vec![1, 3, 2, 0, 4, 8, 1, 5, 7, 2, 9, 6, 0, 3, 4, 1], (4, 4)).unwrap();
+
+    let pooled = matrix.window_map((2, 2), |patch| *patch.iter().max().unwrap());
+
+    assert_eq!(pooled.shape(), (3, 3));
+    assert_eq!(pooled.get_vec(), vec![8, 8, 5, 8, 9, 9, 7, 9, 9]);
+
+    let image: Matrix<f64> = Matrix::from_iter_shape((0..25).map(|i| i as f64), (5, 5)).unwrap();
+
+    let blurred = image.window_map((3, 3), |patch| {
+        patch.iter().sum::<f64>() / patch.len() as f64
+    });
+
+    assert_eq!(blurred.shape(), (3, 3));
+    // Mean of a linear ramp is its center value
+    assert_eq!(blurred.at(0, 0), 6.0);
+    assert_eq!(blurred.at(1, 2), 13.0);
+    assert_eq!(blurred.at(2, 2), 18.0);
+
+    // Window covering the whole matrix reduces to a single value
+    assert_eq!(
+        image.window_map((5, 5), |p| p.len() as f64).get_vec(),
+        vec![25.0]
+    );
+    assert_eq!(image.window_map((1, 6), |p| p[0]).shape(), (0, 0));
+    assert_eq!(image.window_map((0, 2), |p| p[0]).shape(), (0, 0));
+}
+
+#[test]
+fn convolve2d_modes() {
+    let image: Matrix<i32> = Matrix::new(
+        vec![0, 1, 2, 0, 4, 5, 0, 7, 8, 0, 10, 11, 0, 13, 14, 0],
+        (4, 4),
+    )
+    .unwrap();
+    let sobel_x: Matrix<i32> = Matrix::new(vec![-1, 0, 1, -2, 0, 2, -1, 0, 1], (3, 3)).unwrap();
+
+    let valid = image.convolve2d(&sobel_x, ConvMode::Valid);
+    assert_eq!(valid.shape(), (2, 2));
+    assert_eq!(valid.get_vec(), vec![4, -14, -14, -11]);
+
+    let same = image.convolve2d(&sobel_x, ConvMode::Same);
+    assert_eq!(same.shape(), (4, 4));
+    assert_eq!(
+        same.get_vec(),
+        vec![-7, 0, 0, 4, -11, 4, -14, 12, -18, -14, -11, 34, -26, -30, 15, 38]
+    );
+
+    let full = image.convolve2d(&sobel_x, ConvMode::Full);
+    assert_eq!(full.shape(), (6, 6));
+    assert_eq!(full.get_sub_matrix((2, 2), (2, 2)).unwrap(), valid);
+    assert_eq!(full.get_sub_matrix((1, 1), (4, 4)).unwrap(), same);
+    assert_eq!(full.cumsum(), image.cumsum() * sobel_x.cumsum());
+
+    // Convolving with a single 1 is the identity
+    let identity = Matrix::new(vec![1], (1, 1)).unwrap();
+    for mode in [ConvMode::Valid, ConvMode::Same, ConvMode::Full] {
+        assert_eq!(image.convolve2d(&identity, mode), image);
+    }
+
+    let big = Matrix::init(1, (5, 5));
+    assert_eq!(image.convolve2d(&big, ConvMode::Valid).shape(), (0, 0));
+    assert_eq!(image.convolve2d(&big, ConvMode::Same).shape(), (4, 4));
+
+    let empty: Matrix<f64> = Matrix::zeros((0, 3));
+    let ones: Matrix<f64> = Matrix::ones((3, 3));
+    for mode in [ConvMode::Full, ConvMode::Same, ConvMode::Valid] {
+        assert_eq!(empty.convolve2d(&ones, mode).shape(), (0, 0));
+        assert_eq!(
+            Matrix::<f64>::zeros((3, 0)).convolve2d(&ones, mode).shape(),
+            (0, 0)
+        );
+    }
+}
+
+#[test]
+fn get_mut_element() {
+    let mut matrix: Matrix<f64> = Matrix::init(1.5, (2, 3));
+
+    if let Some(x) = matrix.get_mut(1, 1) {
+        *x += 1.0;
+    }
+
+    assert_eq!(matrix.at(1, 1), 2.5);
+    assert_eq!(matrix.cumsum(), 10.0);
+
+    assert!(matrix.get_mut(2, 0).is_none());
+    // Would still be inside the flat data, but not in the matrix
+    assert!(matrix.get_mut(0, 3).is_none());
+    assert_eq!(matrix.at(1, 0), 1.5);
+}
+
+#[test]
+fn add_row_and_col_vectors() {
+    let original: Matrix<f64> = Matrix::from_iter_shape((0..12).map(|i| i as f64), (3, 4)).unwrap();
+    let bias = Matrix::new(vec![0.5, -1.0, 2.0, 10.0], (1, 4)).unwrap();
+
+    let mut matrix = original.clone();
+    matrix.add_row_vector_self(&bias).unwrap();
+
+    for i in 0..3 {
+        let diff: Vec<f64> = matrix
+            .row(i)
+            .unwrap()
+            .iter()
+            .zip(original.row(i).unwrap())
+            .map(|(a, b)| a - b)
+            .collect();
+        assert_eq!(diff, bias.get_vec());
+    }
+
+    let col_bias = Matrix::new(vec![100.0, 200.0, 300.0], (3, 1)).unwrap();
+    matrix.add_col_vector_self(&col_bias).unwrap();
+
+    assert_eq!(matrix.row(2).unwrap(), &[308.5, 308.0, 312.0, 321.0]);
+    assert_eq!(matrix.at(0, 0), 100.5);
+
+    // Wrong orientation or length leaves the matrix untouched
+    let before = matrix.clone();
+    assert_eq!(
+        matrix.add_row_vector_self(&bias.transpose_copy()),
+        Err(MatrixError::MatrixDimensionMismatchError)
+    );
+    assert_eq!(
+        matrix.add_col_vector_self(&bias),
+        Err(MatrixError::MatrixDimensionMismatchError)
+    );
+    assert_eq!(
+        matrix.add_row_vector_self(&Matrix::init(1.0, (1, 3))),
+        Err(MatrixError::MatrixDimensionMismatchError)
+    );
+    assert_eq!(matrix, before);
+}
+
+#[test]
+fn from_str_errors() {
+    let matrix: Matrix<f64> = "1.0 2.0 3.0\n4.0 5.0 6.0\n".parse().unwrap();
+
+    assert_eq!(matrix.shape(), (2, 3));
+    assert_eq!(matrix.at(1, 2), 6.0);
+
+    let empty: Matrix<f64> = "  \n ".parse().unwrap();
+    assert_eq!(empty.shape(), (0, 0));
+
+    let bad_token = "1 2 3\n4 x 6"
+        .parse::<Matrix<i32>>()
+        .unwrap_err()
+        .to_string();
+    assert!(bad_token.contains("'x'"));
+    assert!(bad_token.contains("line 2, column 2"));
+
+    let ragged = "1 2 3\n4 5 6\n7 8"
+        .parse::<Matrix<i32>>()
+        .unwrap_err()
+        .to_string();
+    assert!(ragged.contains("line 3"));
+
+    assert!("1 2\n\n3 4".parse::<Matrix<i32>>().is_err());
+
+    // Leading blank lines still count towards the line numbers
+    let leading = "\n1 2\n3 x".parse::<Matrix<i32>>().unwrap_err().to_string();
+    assert!(leading.contains("line 3, column 2"));
+
+    let ragged = "\n\n1 2\n3\n\n"
+        .parse::<Matrix<i32>>()
+        .unwrap_err()
+        .to_string();
+    assert!(ragged.contains("line 4"));
+
+    let padded: Matrix<i32> = "\n  \n1 2\n3 4\n\n".parse().unwrap();
+    assert_eq!(padded.get_vec(), vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn weighted_mean_and_arg_axis() {
+    let matrix: Matrix<f64> = Matrix::new(vec![1.0, 2.0, 3.0, 4.0], (2, 2)).unwrap();
+    let uniform = Matrix::init(1.0, (2, 2));
+
+    assert_eq!(matrix.weighted_mean(&uniform).unwrap(), matrix.mean());
+    assert_eq!(matrix.weighted_mean(&uniform.mul_val(7.0)).unwrap(), 2.5);
+
+    let weights = Matrix::new(vec![0.0, 1.0, 0.0, 3.0], (2, 2)).unwrap();
+    assert_eq!(matrix.weighted_mean(&weights).unwrap(), 3.5);
+
+    assert_eq!(
+        matrix.weighted_mean(&Matrix::init(1.0, (1, 4))),
+        Err(MatrixError::MatrixDimensionMismatchError)
+    );
+    assert_eq!(
+        matrix.weighted_mean(&Matrix::new(vec![1.0, -1.0, 2.0, -2.0], (2, 2)).unwrap()),
+        Err(MatrixError::MatrixDivideByZeroError)
+    );
+
+    let known = Matrix::new(vec![3, 9, 1, 9, 7, 2, 0, 4, 5, 5, 8, 5], (3, 4)).unwrap();
+
+    // Ties keep the first index
+    assert_eq!(known.argmax_axis(Dimension::Col), vec![1, 0, 2]);
+    assert_eq!(known.argmin_axis(Dimension::Col), vec![2, 2, 0]);
+    assert_eq!(known.argmax_axis(Dimension::Row), vec![1, 0, 2, 0]);
+    assert_eq!(known.argmin_axis(Dimension::Row), vec![0, 1, 1, 1]);
+
+    for (i, &j) in known.argmax_axis(Dimension::Col).iter().enumerate() {
+        assert_eq!(known.at(i, j), *known.row(i).unwrap().iter().max().unwrap());
+    }
+}
+
+#[test]
+fn structural_eq_with_nan() {
+    let a: Matrix<f64> = Matrix::new(vec![1.0, f64::NAN, -0.0, f64::INFINITY], (2, 2)).unwrap();
+    let b: Matrix<f64> = Matrix::new(vec![1.0, f64::NAN, 0.0, f64::INFINITY], (2, 2)).unwrap();
+
+    assert!(a.structural_eq(&b));
+    assert_ne!(a, b);
+
+    // NaN only matches NaN in the same position
+    let moved = Matrix::new(vec![f64::NAN, 1.0, 0.0, f64::INFINITY], (2, 2)).unwrap();
+    assert!(!a.structural_eq(&moved));
+
+    let mut numeric = a.clone();
+    numeric.set(2.0, (0, 1));
+    assert!(!a.structural_eq(&numeric));
+
+    // Same data but different shape
+    let reshaped = Matrix::new(a.get_vec(), (4, 1)).unwrap();
+    assert!(!a.structural_eq(&reshaped));
+
+    let ints = Matrix::new(vec![1, 2, 3], (1, 3)).unwrap();
+    assert!(ints.structural_eq(&ints.clone()));
+    assert_eq!(ints.structural_eq(&ints.clone()), ints == ints.clone());
+}
+
+#[test]
+fn histogram_bins() {
+    let matrix: Matrix<f64> =
+        Matrix::from_iter_shape((0..40).map(|i| i as f64 * 0.25), (8, 5)).unwrap();
+
+    let (counts, edges) = matrix.histogram(4, None);
+
+    assert_eq!(counts.iter().sum::<usize>(), matrix.size());
+    assert_eq!(counts, vec![10, 10, 10, 10]);
+    assert_eq!(edges.len(), 5);
+    assert_eq!(edges[0], 0.0);
+    assert_eq!(edges[4], 9.75);
+
+    // Upper edge goes into the last bin, values outside the range are dropped
+    let (counts, edges) = matrix.histogram(2, Some((1.0, 3.0)));
+    assert_eq!(edges, vec![1.0, 2.0, 3.0]);
+    assert_eq!(counts, vec![4, 5]);
+
+    let constant = Matrix::init(2.0, (2, 2));
+    let (counts, edges) = constant.histogram(3, None);
+    assert_eq!(counts, vec![0, 4, 0]);
+    assert_eq!(edges[0], 1.5);
+
+    let with_nan = Matrix::new(vec![1.0, f64::NAN, 2.0], (1, 3)).unwrap();
+    assert_eq!(with_nan.histogram(1, None).0, vec![2]);
+
+    assert_eq!(matrix.histogram(0, None), (vec![], vec![]));
+}
+
+#[test]
+fn div_checked_masks_zero_divisors() {
+    let lhs: Matrix<f64> = Matrix::new(vec![8.0, 3.0, -6.0, 1.0, 0.0, 5.0], (2, 3)).unwrap();
+    let rhs: Matrix<f64> = Matrix::new(vec![2.0, 0.0, 3.0, 0.0, 4.0, -0.0], (2, 3)).unwrap();
+
+    assert_eq!(lhs.div(&rhs), Err(MatrixError::MatrixDivideByZeroError));
+
+    let (quotient, mask) = lhs.div_checked(&rhs).unwrap();
+
+    assert_eq!(mask.shape(), (2, 3));
+    assert_eq!(mask.get_vec(), vec![0, 1, 0, 1, 0, 1]);
+    assert_eq!(quotient.get_vec(), vec![4.0, 0.0, -2.0, 0.0, 0.0, 0.0]);
+    assert!(!quotient.any(|e| e.is_infinite() || e.is_nan()));
+
+    // Without zero divisors it matches div
+    let safe = Matrix::init(2.0, (2, 3));
+    let (quotient, mask) = lhs.div_checked(&safe).unwrap();
+    assert_eq!(quotient, lhs.div(&safe).unwrap());
+    assert!(mask.all(|&m| m == 0));
+
+    let ints = Matrix::new(vec![7, 9], (1, 2)).unwrap();
+    let (quotient, mask) = ints
+        .div_checked(&Matrix::new(vec![2, 0], (1, 2)).unwrap())
+        .unwrap();
+    assert_eq!(quotient.get_vec(), vec![3, 0]);
+    assert_eq!(mask.get_vec(), vec![0, 1]);
+
+    assert_eq!(
+        lhs.div_checked(&Matrix::init(1.0, (3, 2))),
+        Err(MatrixError::MatrixDimensionMismatchError)
+    );
+}
+
+#[test]
+fn from_fn_builds_structured_matrices() {
+    let table: Matrix<i64> = Matrix::from_fn((9, 9), |i, j| ((i + 1) * (j + 1)) as i64);
+
+    assert_eq!(table.shape(), (9, 9));
+    assert_eq!(table.get(0, 0), Some(1));
+    assert_eq!(table.get(2, 6), Some(21));
+    assert_eq!(table.get(6, 2), Some(21));
+    assert_eq!(table.get(8, 8), Some(81));
+    assert_eq!(table, table.transpose_copy());
+
+    let upper: Matrix<f64> = Matrix::from_fn((3, 4), |i, j| if j >= i { 1.0 } else { 0.0 });
+
+    assert_eq!(
+        upper.get_vec(),
+        vec![1.0, 1.0, 1.0, 1.0, 0.0, 1.0, 1.0, 1.0, 0.0, 0.0, 1.0, 1.0]
+    );
+    assert_eq!(upper.get(2, 1), Some(0.0));
+    assert_eq!(upper.get(1, 3), Some(1.0));
+
+    let empty: Matrix<i32> = Matrix::from_fn((0, 3), |_, _| 1);
+    assert_eq!(empty.size(), 0);
+}
+
+#[test]
+fn linalg_reals_agrees_with_floats() {
+    let a: Matrix<f64> = Matrix::new(vec![0.5, 1.0, 2.0, 4.0], (2, 2)).unwrap();
+
+    assert_eq!(
+        linalg_rs::LinAlgReals::log(&a, 2.0),
+        LinAlgFloats::log(&a, 2.0)
+    );
+    assert_eq!(linalg_rs::LinAlgReals::ln(&a), LinAlgFloats::ln(&a));
+    assert_eq!(linalg_rs::LinAlgReals::sqrt(&a), LinAlgFloats::sqrt(&a));
+    assert_eq!(linalg_rs::LinAlgReals::tanh(&a), LinAlgFloats::tanh(&a));
+
+    let sym: Matrix<f64> =
+        Matrix::new(vec![4.0, 1.0, 0.0, 1.0, 3.0, 1.0, 0.0, 1.0, 2.0], (3, 3)).unwrap();
+    assert_eq!(
+        linalg_rs::LinAlgReals::get_eigenvalues(&sym),
+        LinAlgFloats::get_eigenvalues(&sym)
+    );
+    assert!(linalg_rs::LinAlgReals::get_eigenvalues(&sym).is_some());
+    assert_eq!(
+        linalg_rs::LinAlgReals::get_eigenvalues(&Matrix::<f64>::zeros((2, 3))),
+        None
+    );
+
+    assert_eq!(linalg_rs::LinAlgReals::get_eigenvectors(&a), None);
+    assert_eq!(LinAlgFloats::get_eigenvectors(&a), None);
+}
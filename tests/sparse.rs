@@ -1,5 +1,5 @@
-use std::collections::HashMap;
-use linalg_rs::{smd, SparseMatrix, SparseMatrixData};
+use linalg_rs::{smd, Dimension, Matrix, MatrixError, SparseMatrix, SparseMatrixData};
+use std::{collections::HashMap, env, fs};
 
 #[test]
 fn sparse_basic() {
@@ -63,15 +63,915 @@ fn matmul_sparse() {
 
     let res = sparse.matmul_sparse(&sparse2).unwrap();
 
-    assert_eq!(res.at(0, 0), 0.0);
-    assert_eq!(res.at(0, 1), 24.0);
+    assert_eq!(res.at(0, 0), 8.0);
+    assert_eq!(res.at(0, 1), 16.0);
     assert_eq!(res.at(0, 2), 0.0);
 
-    assert_eq!(res.at(1, 0), 8.0);
-    assert_eq!(res.at(1, 1), 72.0);
+    assert_eq!(res.at(1, 0), 32.0);
+    assert_eq!(res.at(1, 1), 48.0);
     assert_eq!(res.at(1, 2), 0.0);
 
     assert_eq!(res.at(2, 0), 0.0);
-    assert_eq!(res.at(2, 1), 0.0);
-    assert_eq!(res.at(2, 2), 48.0);
+    assert_eq!(res.at(2, 1), 48.0);
+    assert_eq!(res.at(2, 2), 0.0);
+}
+
+#[test]
+fn matmul_sparse_matches_dense() {
+    // [ 1 0 2
+    //   0 3 0 ]
+    let lhs = SparseMatrix::<i32>::new(smd![((0, 0), 1), ((0, 2), 2), ((1, 1), 3)], (2, 3));
+    // [ 0 4
+    //   5 0
+    //   6 7 ]
+    let rhs = SparseMatrix::<i32>::new(
+        smd![((0, 1), 4), ((1, 0), 5), ((2, 0), 6), ((2, 1), 7)],
+        (3, 2),
+    );
+
+    let res = lhs.matmul_sparse(&rhs).unwrap();
+
+    assert_eq!(res.shape(), (2, 2));
+    assert_eq!(Matrix::from_sparse(res).get_vec(), vec![12, 18, 15, 0]);
+
+    // Non-symmetric square matrices, checked against the dense product
+    let a = SparseMatrix::<i32>::new(
+        smd![((0, 1), 2), ((1, 2), -1), ((2, 0), 3), ((2, 2), 4)],
+        (3, 3),
+    );
+    let b = SparseMatrix::<i32>::new(smd![((0, 0), 1), ((1, 2), 5), ((2, 1), 6)], (3, 3));
+
+    let res = a.matmul_sparse(&b).unwrap();
+    let dense = Matrix::from_sparse(a)
+        .matmul(&Matrix::from_sparse(b))
+        .unwrap();
+
+    assert_eq!(Matrix::from_sparse(res), dense);
+    assert_eq!(dense.get_vec(), vec![0, 0, 10, 0, -6, 0, 3, 24, 0]);
+
+    assert!(rhs.matmul_sparse(&rhs).is_err());
+}
+
+#[test]
+fn sparse_sub_mul_div() {
+    let lhs = SparseMatrix::<f64>::new(smd![((0, 0), 8.0), ((0, 1), 3.0), ((1, 1), 6.0)], (3, 3));
+    let rhs = SparseMatrix::<f64>::new(smd![((0, 0), 2.0), ((1, 1), 6.0), ((2, 2), 4.0)], (3, 3));
+
+    let sub = lhs.sub(&rhs).unwrap();
+
+    assert_eq!(sub.at(0, 0), 6.0);
+    assert_eq!(sub.at(0, 1), 3.0);
+    assert_eq!(sub.at(2, 2), -4.0);
+    // 6 - 6 cancels out and should not be stored
+    assert_eq!(sub.get(1, 1), Some(0.0));
+    assert!(!sub.data.contains_key(&(1, 1)));
+
+    let mul = lhs.mul(&rhs).unwrap();
+
+    assert_eq!(mul.at(0, 0), 16.0);
+    assert_eq!(mul.at(1, 1), 36.0);
+    assert_eq!(mul.at(2, 2), 0.0);
+    assert!(!mul.data.contains_key(&(2, 2)));
+    // Only stored in lhs, so the product is zero
+    assert!(!mul.data.contains_key(&(0, 1)));
+
+    // 3.0 at (0, 1) would be divided by an implicit zero
+    assert_eq!(lhs.div(&rhs), Err(MatrixError::MatrixDivideByZeroError));
+
+    let lhs = SparseMatrix::<f64>::new(smd![((0, 0), 8.0), ((1, 1), 6.0)], (3, 3));
+    let div = lhs.div(&rhs).unwrap();
+
+    assert_eq!(div.at(0, 0), 4.0);
+    assert_eq!(div.at(1, 1), 1.0);
+    assert_eq!(div.at(2, 2), 0.0);
+    assert!(!div.data.contains_key(&(2, 2)));
+}
+
+#[test]
+fn sparse_div_by_stored_zero() {
+    let lhs = SparseMatrix::<f64>::new(smd![((0, 0), 8.0)], (2, 2));
+    let rhs = SparseMatrix::<f64>::new(smd![((0, 0), 0.0)], (2, 2));
+
+    assert_eq!(lhs.div(&rhs), Err(MatrixError::MatrixDivideByZeroError));
+}
+
+#[test]
+fn sparse_min() {
+    let positive = SparseMatrix::<f64>::new(smd![((0, 0), 3.0), ((1, 1), 7.0)], (2, 2));
+    assert_eq!(positive.min(), 0.0);
+
+    let negative = SparseMatrix::<f64>::new(smd![((0, 0), -3.0), ((1, 1), -7.0)], (2, 2));
+    assert_eq!(negative.min(), -7.0);
+
+    let full = SparseMatrix::<f64>::new(
+        smd![((0, 0), 3.0), ((0, 1), 2.0), ((1, 0), 5.0), ((1, 1), 7.0)],
+        (2, 2),
+    );
+    assert_eq!(full.min(), 2.0);
+
+    let empty = SparseMatrix::<f64>::init(3, 3);
+    assert_eq!(empty.min(), 0.0);
+}
+
+#[test]
+fn sparse_trace() {
+    assert_eq!(SparseMatrix::<i32>::identity(5).trace(), 5);
+
+    let sparse =
+        SparseMatrix::<f64>::new(smd![((0, 0), 2.0), ((1, 2), 9.0), ((2, 2), 3.5)], (3, 3));
+    assert_eq!(sparse.trace(), 5.5);
+}
+
+#[test]
+fn sparse_from_file_runtime_path() {
+    let path = env::temp_dir().join(format!("linalg_rs_sparse_{}.txt", std::process::id()));
+
+    fs::write(&path, "3 3\n0 0 1\n2 1 5\n").unwrap();
+    let read: SparseMatrix<i32> = SparseMatrix::from_file(path.clone()).unwrap();
+
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(read.shape(), (3, 3));
+    assert_eq!(read.at(0, 0), 1);
+    assert_eq!(read.at(2, 1), 5);
+    assert_eq!(read.at(1, 1), 0);
+
+    assert!(matches!(
+        SparseMatrix::<i32>::from_file(path),
+        Err(MatrixError::MatrixFileReadError(_))
+    ));
+}
+
+#[test]
+fn sparse_to_slices_round_trip() {
+    let rows = vec![3, 0, 2, 0, 1];
+    let cols = vec![1, 4, 2, 0, 3];
+    let vals = vec![1.5, -2.0, 3.0, 4.0, 0.5];
+
+    let sparse = SparseMatrix::from_slices(&rows, &cols, &vals, (4, 5)).unwrap();
+
+    let exported = sparse.to_slices();
+
+    assert_eq!(exported, sparse.to_slices());
+    assert_eq!(exported.0, vec![0, 0, 1, 2, 3]);
+    assert_eq!(exported.1, vec![0, 4, 3, 2, 1]);
+
+    let mut expected: Vec<_> = rows
+        .iter()
+        .zip(cols.iter())
+        .map(|(&i, &j)| (i, j))
+        .zip(vals.iter().copied())
+        .collect();
+    expected.sort_by_key(|&(idx, _)| idx);
+
+    let round_trip: Vec<_> = exported
+        .0
+        .into_iter()
+        .zip(exported.1)
+        .zip(exported.2)
+        .collect();
+
+    assert_eq!(round_trip, expected);
+
+    let (rows, cols, vals) = sparse.to_slices();
+    let rebuilt = SparseMatrix::from_slices(&rows, &cols, &vals, sparse.shape()).unwrap();
+
+    assert_eq!(rebuilt.data, sparse.data);
+}
+
+#[test]
+fn sparse_entries_sorted() {
+    let sparse = SparseMatrix::<i32>::randomize_range(1, 9, 0.5, (20, 20));
+
+    let entries = sparse.entries_sorted();
+
+    assert_eq!(entries, sparse.entries_sorted());
+    assert_eq!(entries.len(), sparse.data.len());
+    assert!(entries.windows(2).all(|w| w[0].0 < w[1].0));
+    assert!(entries.iter().all(|&(idx, val)| sparse.data[&idx] == val));
+}
+
+#[test]
+fn matmul_sparse_drops_cancelled_entries() {
+    // [ 1 1
+    //   0 2 ]
+    let lhs = SparseMatrix::<i32>::new(smd![((0, 0), 1), ((0, 1), 1), ((1, 1), 2)], (2, 2));
+    // [ 1  3
+    //  -1  0 ]
+    let rhs = SparseMatrix::<i32>::new(smd![((0, 0), 1), ((0, 1), 3), ((1, 0), -1)], (2, 2));
+
+    let res = lhs.matmul_sparse(&rhs).unwrap();
+
+    // 1 * 1 + 1 * -1 cancels out
+    assert!(!res.data.contains_key(&(0, 0)));
+    assert_eq!(res.get(0, 0), Some(0));
+
+    assert_eq!(res.data.len(), 2);
+    assert_eq!(res.get(0, 1), Some(3));
+    assert_eq!(res.get(1, 0), Some(-2));
+}
+
+#[test]
+fn mixed_dense_sparse_matmul() {
+    let dense: Matrix<f64> = Matrix::randomize((3, 3));
+    let eye = SparseMatrix::<f64>::eye(3);
+
+    assert_eq!(dense.matmul_sparse(&eye).unwrap(), dense);
+    assert_eq!(eye.matmul_dense(&dense).unwrap(), dense);
+    assert_eq!(
+        dense.matmul_sparse(&eye).unwrap(),
+        dense.matmul(&Matrix::from_sparse(eye.clone())).unwrap()
+    );
+
+    let dense: Matrix<i32> = Matrix::randomize_range(-5, 5, (3, 4));
+    let sparse = SparseMatrix::<i32>::new(smd![((0, 2), 2), ((1, 0), -3), ((2, 1), 4)], (3, 3));
+    let sparse_as_dense = Matrix::from_sparse(sparse.clone());
+
+    assert_eq!(
+        sparse.matmul_dense(&dense).unwrap(),
+        sparse_as_dense.matmul(&dense).unwrap()
+    );
+
+    let dense: Matrix<i32> = Matrix::randomize_range(-5, 5, (2, 3));
+
+    assert_eq!(
+        dense.matmul_sparse(&sparse).unwrap(),
+        dense.matmul(&sparse_as_dense).unwrap()
+    );
+
+    assert_eq!(
+        dense.matmul_sparse(&SparseMatrix::eye(2)),
+        Err(MatrixError::MatrixMultiplicationDimensionMismatchError)
+    );
+    assert_eq!(
+        sparse.matmul_dense(&dense),
+        Err(MatrixError::MatrixMultiplicationDimensionMismatchError)
+    );
+}
+
+#[test]
+fn sparse_bounds_checks() {
+    let mut sparse = SparseMatrix::<i32>::new(smd![((1, 2), 7)], (2, 3));
+
+    assert_eq!(sparse.get(2, 0), None);
+    assert_eq!(sparse.get(0, 3), None);
+    assert_eq!(sparse.get(usize::MAX / 4, 0), None);
+    assert_eq!(sparse.get(1, 2), Some(7));
+    assert_eq!(sparse.get(0, 1), Some(0));
+
+    // Would wrap into (1, 0) if checked as a flattened index
+    sparse.set(5, (0, 3));
+    sparse.insert(2, 0, 5);
+
+    assert_eq!(sparse.data.len(), 1);
+    assert_eq!(sparse.at(1, 0), 0);
+
+    sparse.insert(1, 0, 4);
+
+    assert_eq!(sparse.at(1, 0), 4);
+}
+
+#[test]
+#[should_panic]
+fn sparse_at_out_of_bounds() {
+    SparseMatrix::<i32>::eye(2).at(0, 2);
+}
+
+#[test]
+fn sparse_sub_matrix() {
+    // [ 1 0 0 0
+    //   0 2 3 0
+    //   0 0 4 0
+    //   5 0 0 6 ]
+    let sparse = SparseMatrix::<i32>::new(
+        smd![
+            ((0, 0), 1),
+            ((1, 1), 2),
+            ((1, 2), 3),
+            ((2, 2), 4),
+            ((3, 0), 5),
+            ((3, 3), 6)
+        ],
+        (4, 4),
+    );
+
+    let sub = sparse.get_sub_matrix((1, 1), (2, 2)).unwrap();
+
+    assert_eq!(sub.shape(), (2, 2));
+    assert_eq!(sub.data, smd![((0, 0), 2), ((0, 1), 3), ((1, 1), 4)]);
+
+    let corner = sparse.get_sub_matrix((2, 2), (2, 2)).unwrap();
+
+    assert_eq!(corner.data, smd![((0, 0), 4), ((1, 1), 6)]);
+
+    assert_eq!(
+        sparse.get_sub_matrix((3, 3), (2, 1)),
+        Err(MatrixError::MatrixIndexOutOfBoundsError)
+    );
+    assert_eq!(
+        sparse.get_sub_matrix((0, 2), (1, 3)),
+        Err(MatrixError::MatrixIndexOutOfBoundsError)
+    );
+
+    // The window end overflows usize instead of running past the shape
+    assert_eq!(
+        sparse.get_sub_matrix((3, 3), (usize::MAX, 1)),
+        Err(MatrixError::MatrixIndexOutOfBoundsError)
+    );
+    assert_eq!(
+        sparse.get_sub_matrix((0, 1), (1, usize::MAX)),
+        Err(MatrixError::MatrixIndexOutOfBoundsError)
+    );
+}
+
+#[test]
+fn sparse_seeded_randomize() {
+    let a = SparseMatrix::<f64>::randomize_range_seeded(1.0, 5.0, 0.8, (10, 10), 99);
+    let b = SparseMatrix::<f64>::randomize_range_seeded(1.0, 5.0, 0.8, (10, 10), 99);
+    let c = SparseMatrix::<f64>::randomize_range_seeded(1.0, 5.0, 0.8, (10, 10), 100);
+
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+    assert_eq!(a.data.len(), 20);
+}
+
+#[test]
+fn sparse_diagonal() {
+    let sparse = SparseMatrix::<i32>::from_diagonal(&[0, 5, 0, 3]);
+
+    assert_eq!(sparse.shape(), (4, 4));
+    assert_eq!(sparse.data.len(), 2);
+    assert_eq!(sparse.at(1, 1), 5);
+    assert_eq!(sparse.at(0, 0), 0);
+    assert_eq!(sparse.diagonal(), vec![0, 5, 0, 3]);
+
+    let wide = SparseMatrix::<i32>::new(smd![((0, 0), 1), ((0, 2), 9), ((1, 1), -2)], (2, 4));
+
+    assert_eq!(wide.diagonal(), vec![1, -2]);
+}
+
+#[test]
+fn dense_sparse_element_wise() {
+    let ones: Matrix<i32> = Matrix::ones((3, 3));
+    let eye = SparseMatrix::<i32>::eye(3);
+
+    assert_eq!(
+        ones.add_sparse(&eye).unwrap().get_vec(),
+        vec![2, 1, 1, 1, 2, 1, 1, 1, 2]
+    );
+    assert_eq!(
+        ones.sub_sparse(&eye).unwrap().get_vec(),
+        vec![0, 1, 1, 1, 0, 1, 1, 1, 0]
+    );
+    assert_eq!(ones.mul_sparse(&eye).unwrap(), Matrix::eye(3));
+
+    let eye = SparseMatrix::<i32>::eye(2);
+
+    assert_eq!(
+        ones.add_sparse(&eye),
+        Err(MatrixError::MatrixDimensionMismatchError)
+    );
+    assert!(ones.sub_sparse(&eye).is_err());
+    assert!(ones.mul_sparse(&eye).is_err());
+}
+
+#[test]
+fn sparse_median_implicit_zeros() {
+    let mut indexes: SparseMatrixData<f64> = HashMap::new();
+
+    indexes.insert((0, 0), -5.0);
+    indexes.insert((1, 2), -2.0);
+    indexes.insert((3, 3), -7.0);
+
+    let sparse = SparseMatrix::new(indexes, (4, 4));
+
+    assert_eq!(sparse.median(), 0.0);
+    assert_eq!(
+        sparse.median(),
+        Matrix::from_sparse(sparse.clone()).median()
+    );
+
+    let mut indexes: SparseMatrixData<f64> = HashMap::new();
+
+    indexes.insert((0, 0), -5.0);
+    indexes.insert((0, 1), -2.0);
+    indexes.insert((0, 2), 3.0);
+    indexes.insert((1, 0), 4.0);
+    indexes.insert((1, 1), 1.0);
+    indexes.insert((1, 2), -1.0);
+    indexes.insert((2, 0), 6.0);
+    indexes.insert((2, 1), 2.0);
+
+    let sparse = SparseMatrix::new(indexes, (3, 3));
+
+    assert_eq!(sparse.median(), 1.0);
+    assert_eq!(
+        sparse.median(),
+        Matrix::from_sparse(sparse.clone()).median()
+    );
+
+    let mut indexes: SparseMatrixData<f64> = HashMap::new();
+
+    indexes.insert((0, 0), -3.0);
+    indexes.insert((0, 1), -2.0);
+    indexes.insert((1, 0), -1.0);
+
+    let sparse = SparseMatrix::new(indexes, (2, 3));
+
+    assert_eq!(sparse.median(), -0.5);
+    assert_eq!(
+        sparse.median(),
+        Matrix::from_sparse(sparse.clone()).median()
+    );
+}
+
+#[test]
+fn sparse_matrix_market_round_trip() {
+    let rows = vec![3, 0, 2, 0, 1];
+    let cols = vec![1, 4, 2, 0, 3];
+    let vals = vec![1.5, -2.0, 3.0, 4.0, 0.1];
+
+    let sparse = SparseMatrix::from_slices(&rows, &cols, &vals, (4, 5)).unwrap();
+
+    let path = env::temp_dir().join(format!("linalg_rs_mtx_{}.mtx", std::process::id()));
+
+    sparse.to_matrix_market(&path).unwrap();
+    let read: SparseMatrix<f64> = SparseMatrix::from_matrix_market(&path).unwrap();
+
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(read.shape(), (4, 5));
+    assert_eq!(read.entries_sorted(), sparse.entries_sorted());
+}
+
+#[test]
+fn sparse_matrix_market_known_file() {
+    let content = "%%MatrixMarket matrix coordinate real general\n\
+                   % a comment\n\
+                   3 4 3\n\
+                   1 1 2.5\n\
+                   3 2 -1\n\
+                   2 4 7\n";
+
+    let path = env::temp_dir().join(format!("linalg_rs_known_{}.mtx", std::process::id()));
+
+    fs::write(&path, content).unwrap();
+    let read: SparseMatrix<f64> = SparseMatrix::from_matrix_market(&path).unwrap();
+
+    assert_eq!(read.shape(), (3, 4));
+    assert_eq!(read.at(0, 0), 2.5);
+    assert_eq!(read.at(2, 1), -1.0);
+    assert_eq!(read.at(1, 3), 7.0);
+    assert_eq!(read.at(1, 1), 0.0);
+
+    fs::write(
+        &path,
+        "%%MatrixMarket matrix array real general\n2 2\n1\n2\n3\n4\n",
+    )
+    .unwrap();
+    assert_eq!(
+        SparseMatrix::<f64>::from_matrix_market(&path).unwrap_err(),
+        MatrixError::MatrixParseError
+    );
+
+    fs::write(
+        &path,
+        "%%MatrixMarket matrix coordinate real general\n2 2 1\n3 1 1.0\n",
+    )
+    .unwrap();
+    assert!(SparseMatrix::<f64>::from_matrix_market(&path).is_err());
+
+    fs::write(
+        &path,
+        "%%MatrixMarket matrix coordinate real general\n1 1 18446744073709551615\n",
+    )
+    .unwrap();
+    assert_eq!(
+        SparseMatrix::<f64>::from_matrix_market(&path).unwrap_err(),
+        MatrixError::MatrixParseError
+    );
+
+    fs::write(
+        &path,
+        "%%MatrixMarket matrix coordinate real general\n2 2 2\n1 1 1.0\n1 1 2.0\n",
+    )
+    .unwrap();
+    assert_eq!(
+        SparseMatrix::<f64>::from_matrix_market(&path).unwrap_err(),
+        MatrixError::MatrixParseError
+    );
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn sparse_nnz_matches_dense() {
+    let sparse =
+        SparseMatrix::<i32>::from_slices(&[0, 2, 1], &[1, 2, 0], &[4, -2, 7], (3, 4)).unwrap();
+    let dense = Matrix::from_sparse(sparse.clone());
+
+    assert_eq!(sparse.nnz(), 3);
+    assert_eq!(sparse.nnz(), dense.nnz());
+    assert_eq!(sparse.density(), dense.density());
+    assert_eq!(sparse.density(), 0.25);
+}
+
+#[test]
+fn sparse_from_dense_threshold() {
+    let dense: Matrix<f64> = Matrix::new(
+        vec![3.5, 1e-12, 0.0, -1e-12, -2.0, 4e-13, 0.0, 0.7, 1e-12],
+        (3, 3),
+    )
+    .unwrap();
+
+    let sparse = SparseMatrix::from_dense_threshold(dense.clone(), 1e-10);
+
+    assert_eq!(sparse.shape(), (3, 3));
+    assert_eq!(sparse.nnz(), 3);
+    assert_eq!(sparse.at(0, 0), 3.5);
+    assert_eq!(sparse.at(1, 1), -2.0);
+    assert_eq!(sparse.at(2, 1), 0.7);
+    assert_eq!(sparse.at(0, 1), 0.0);
+    assert_eq!(sparse.at(1, 0), 0.0);
+
+    assert_eq!(SparseMatrix::from_dense(dense).nnz(), 7);
+}
+
+#[test]
+fn sparse_add_val_dense() {
+    let sparse =
+        SparseMatrix::<i32>::from_slices(&[0, 1, 2], &[2, 0, 1], &[4, -3, 9], (3, 3)).unwrap();
+
+    let dense = sparse.add_val_dense(5);
+
+    assert_eq!(dense.shape(), (3, 3));
+    for i in 0..3 {
+        for j in 0..3 {
+            assert_eq!(dense.at(i, j), sparse.at(i, j) + 5);
+        }
+    }
+    assert_eq!(dense.at(1, 1), 5);
+    assert_eq!(dense.at(2, 1), 14);
+
+    // add_val only touches the stored entries
+    let stored_only = sparse.add_val(5);
+    assert_eq!(stored_only.at(1, 1), 0);
+    assert_eq!(stored_only.at(2, 1), 14);
+
+    let negated = sparse.add_val_dense(-4);
+    assert_eq!(negated.at(0, 2), 0);
+    assert_eq!(negated.at(0, 0), -4);
+}
+
+#[test]
+fn sparse_sub_drops_cancelled_entries() {
+    let sparse =
+        SparseMatrix::<i32>::from_slices(&[0, 1, 2], &[2, 0, 1], &[4, -3, 9], (3, 3)).unwrap();
+
+    let result = sparse.sub(&sparse).unwrap();
+
+    assert!(result.data.is_empty());
+    for i in 0..3 {
+        for j in 0..3 {
+            assert_eq!(result.get(i, j), Some(0));
+        }
+    }
+
+    let mut in_place = sparse.clone();
+    in_place.sub_self(&sparse).unwrap();
+
+    assert!(in_place.data.is_empty());
+    assert_eq!(in_place.get(2, 1), Some(0));
+
+    // Partial cancellation keeps the remaining entries, and
+    // entries only present in the rhs are negated
+    let other = SparseMatrix::<i32>::from_slices(&[0, 1], &[2, 1], &[4, 7], (3, 3)).unwrap();
+
+    let mut partial = sparse.clone();
+    partial.sub_self(&other).unwrap();
+
+    assert_eq!(partial.nnz(), 3);
+    assert_eq!(partial.get(0, 2), Some(0));
+    assert_eq!(partial.get(1, 1), Some(-7));
+    assert_eq!(partial, sparse.sub(&other).unwrap());
+
+    let neg = other.mul_val(-1);
+    let mut added = other.clone();
+    added.add_self(&neg).unwrap();
+    assert!(added.data.is_empty());
+}
+
+#[test]
+fn sparse_mul_div_partial_overlap() {
+    let a = SparseMatrix::<i32>::from_slices(&[0, 1, 2, 2], &[0, 2, 1, 2], &[4, -6, 8, 3], (3, 3))
+        .unwrap();
+    let b = SparseMatrix::<i32>::from_slices(&[0, 1, 2, 0], &[0, 2, 1, 1], &[2, 3, -2, 5], (3, 3))
+        .unwrap();
+
+    let product = a.mul(&b).unwrap();
+
+    let hadamard = Matrix::from_sparse(a.clone())
+        .hadamard(&Matrix::from_sparse(b.clone()))
+        .unwrap();
+
+    assert_eq!(product.nnz(), 3);
+    assert_eq!(Matrix::from_sparse(product.clone()), hadamard);
+    assert_eq!(product.get(2, 2), Some(0));
+    assert_eq!(product.get(0, 1), Some(0));
+
+    let mut in_place = a.clone();
+    in_place.mul_self(&b).unwrap();
+    assert_eq!(in_place, product);
+
+    // (2, 2) in a has no divisor in b
+    assert_eq!(a.div(&b), Err(MatrixError::MatrixDivideByZeroError));
+
+    let mut unchanged = a.clone();
+    assert_eq!(
+        unchanged.div_self(&b),
+        Err(MatrixError::MatrixDivideByZeroError)
+    );
+    assert_eq!(unchanged, a);
+
+    assert_eq!(
+        unchanged.add_self(&SparseMatrix::eye(2)),
+        Err(MatrixError::MatrixDimensionMismatchError)
+    );
+    assert_eq!(unchanged, a);
+
+    // Every non-zero of c has a divisor, extra entries in b are ignored
+    let c = SparseMatrix::<i32>::from_slices(&[0, 1, 2], &[0, 2, 1], &[8, -9, 4], (3, 3)).unwrap();
+
+    let quotient = c.div(&b).unwrap();
+    assert_eq!(quotient.nnz(), 3);
+    assert_eq!(quotient.get(0, 0), Some(4));
+    assert_eq!(quotient.get(1, 2), Some(-3));
+    assert_eq!(quotient.get(2, 1), Some(-2));
+    assert_eq!(quotient.get(0, 1), Some(0));
+
+    let mut in_place = c.clone();
+    in_place.div_self(&b).unwrap();
+    assert_eq!(in_place, quotient);
+
+    // Integer division that truncates to zero is not stored by either path
+    let one = SparseMatrix::<i32>::new(smd![((0, 0), 1)], (2, 2));
+    let two = SparseMatrix::<i32>::new(smd![((0, 0), 2)], (2, 2));
+
+    let quotient = one.div(&two).unwrap();
+    assert_eq!(quotient.nnz(), 0);
+
+    let mut in_place = one.clone();
+    in_place.div_self(&two).unwrap();
+    assert_eq!(in_place.nnz(), 0);
+    assert_eq!(in_place, quotient);
+}
+
+#[test]
+fn sparse_sums() {
+    let sparse = SparseMatrix::<f64>::new(
+        smd![((0, 1), 2.5), ((0, 3), -1.0), ((2, 0), 4.0), ((2, 3), 0.5)],
+        (3, 4),
+    );
+    let dense = Matrix::from_sparse(sparse.clone());
+
+    assert_eq!(sparse.cumsum(), dense.cumsum());
+    assert_eq!(sparse.cumsum(), 6.0);
+
+    assert_eq!(
+        sparse.sum_axis(Dimension::Row),
+        dense.sum_axis(Dimension::Row).get_vec()
+    );
+    assert_eq!(
+        sparse.sum_axis(Dimension::Col),
+        dense.sum_axis(Dimension::Col).get_vec()
+    );
+    assert_eq!(sparse.sum_axis(Dimension::Col), vec![1.5, 0.0, 4.5]);
+
+    let empty = SparseMatrix::<i32>::init(2, 2);
+    assert_eq!(empty.cumsum(), 0);
+    assert_eq!(empty.sum_axis(Dimension::Row), vec![0, 0]);
+}
+
+#[test]
+fn sparse_pow_counts_walks() {
+    // Directed graph 0 -> 1 -> 2 -> 0 with an extra edge 0 -> 2 and a self loop on 3
+    let adjacency = SparseMatrix::<i64>::from_slices(
+        &[0, 1, 2, 0, 3],
+        &[1, 2, 0, 2, 3],
+        &[1, 1, 1, 1, 1],
+        (4, 4),
+    )
+    .unwrap();
+    let dense = Matrix::from_sparse(adjacency.clone());
+
+    for n in 0..7 {
+        let powered = adjacency.pow(n).unwrap();
+        assert_eq!(Matrix::from_sparse(powered), dense.exp(n).unwrap());
+    }
+
+    assert_eq!(adjacency.pow(1).unwrap(), adjacency);
+    assert_eq!(adjacency.pow(0).unwrap(), SparseMatrix::eye(4));
+
+    // Walks of length 3 from 0 back to itself: 0->1->2->0 only
+    assert_eq!(adjacency.pow(3).unwrap().at(0, 0), 1);
+    assert_eq!(adjacency.pow(5).unwrap().at(3, 3), 1);
+
+    let rect = SparseMatrix::<i64>::init(2, 3);
+    assert!(rect.pow(2).is_none());
+}
+
+#[test]
+fn sparse_degrees() {
+    // Undirected star on 5 nodes centered at 0, plus the edge 3 - 4
+    let edges = [(0, 1), (0, 2), (0, 3), (0, 4), (3, 4)];
+
+    let mut data: SparseMatrixData<i32> = HashMap::new();
+    for &(a, b) in edges.iter() {
+        data.insert((a, b), 1);
+        data.insert((b, a), 1);
+    }
+    let adjacency = SparseMatrix::new(data, (5, 5));
+
+    assert_eq!(adjacency.row_nnz(), vec![4, 1, 1, 2, 2]);
+    assert_eq!(adjacency.col_nnz(), adjacency.row_nnz());
+    assert_eq!(adjacency.row_nnz().iter().sum::<usize>(), adjacency.nnz());
+
+    // Directed edges split into out- and in-degrees
+    let directed =
+        SparseMatrix::<i32>::from_slices(&[0, 0, 1], &[1, 2, 2], &[1, 1, 1], (3, 4)).unwrap();
+
+    assert_eq!(directed.row_nnz(), vec![2, 1, 0]);
+    assert_eq!(directed.col_nnz(), vec![0, 1, 2, 0]);
+}
+
+#[test]
+fn sparse_json_round_trip() {
+    let sparse =
+        SparseMatrix::<f64>::new(smd![((0, 3), 0.1), ((2, 1), -7.25), ((1, 0), 3.0)], (3, 4));
+
+    let json = sparse.to_json().unwrap();
+    assert_eq!(
+        json,
+        r#"{"nrows":3,"ncols":4,"entries":[[0,3,0.1],[1,0,3.0],[2,1,-7.25]]}"#
+    );
+    assert_eq!(SparseMatrix::<f64>::from_json(&json).unwrap(), sparse);
+
+    let zeros =
+        SparseMatrix::<i32>::from_json(r#"{"nrows":2,"ncols":2,"entries":[[0,1,0],[1,1,4]]}"#)
+            .unwrap();
+    assert_eq!(zeros.nnz(), 1);
+    assert_eq!(zeros.at(1, 1), 4);
+
+    for bad in [
+        r#"{"nrows":2,"ncols":2,"entries":[[0,2,1]]}"#,
+        r#"{"nrows":2,"ncols":2,"entries":[[1,1,1],[1,1,2]]}"#,
+        r#"{"nrows":2,"ncols":2,"entries":[[1,1]]}"#,
+        r#"{"nrows":2,"ncols":2,"data":[]}"#,
+    ] {
+        assert_eq!(
+            SparseMatrix::<i32>::from_json(bad),
+            Err(MatrixError::MatrixParseError)
+        );
+    }
+}
+
+#[test]
+fn sparse_prune_and_clear() {
+    let mut sparse = SparseMatrix::<f64>::new(
+        smd![
+            ((0, 0), 2.0),
+            ((0, 2), 1e-14),
+            ((1, 1), -3e-11),
+            ((2, 0), 0.0),
+            ((3, 3), -0.5)
+        ],
+        (4, 4),
+    );
+
+    let sparsity_before = sparse.sparsity();
+    assert_eq!(sparse.data.len(), 5);
+
+    // Exact pruning only drops the stored zero
+    sparse.prune(0.0);
+    assert_eq!(sparse.data.len(), 4);
+
+    sparse.prune(1e-10);
+    assert_eq!(sparse.data.len(), 2);
+    assert!(sparse.sparsity() > sparsity_before);
+    assert_eq!(sparse.sparsity(), 14.0 / 16.0);
+    assert_eq!(sparse.get(1, 1), Some(0.0));
+    assert_eq!(sparse.get(3, 3), Some(-0.5));
+
+    sparse.clear();
+    assert!(sparse.data.is_empty());
+    assert_eq!(sparse.shape(), (4, 4));
+    assert_eq!(sparse.sparsity(), 1.0);
+}
+
+#[test]
+fn sparse_from_str_validation() {
+    let sparse: SparseMatrix<f64> = "3 4\n0 1 2.5\n2 3 -1.0\n".parse().unwrap();
+
+    assert_eq!(sparse.shape(), (3, 4));
+    assert_eq!(sparse.nnz(), 2);
+    assert_eq!(sparse.at(2, 3), -1.0);
+
+    let header_only: SparseMatrix<i32> = "2 2".parse().unwrap();
+    assert_eq!(header_only.nnz(), 0);
+
+    let out_of_range = "3 4\n0 1 2\n3 0 1"
+        .parse::<SparseMatrix<i32>>()
+        .unwrap_err()
+        .to_string();
+    assert!(out_of_range.contains("line 3"));
+    assert!(out_of_range.contains("out of bounds"));
+    assert!("3 4\n0 4 1".parse::<SparseMatrix<i32>>().is_err());
+
+    let bad_index = "3 4\n0 1.5 2"
+        .parse::<SparseMatrix<i32>>()
+        .unwrap_err()
+        .to_string();
+    assert!(bad_index.contains("line 2"));
+    assert!("3 4\n-1 0 2".parse::<SparseMatrix<i32>>().is_err());
+
+    assert!("3 4\n0 1 abc".parse::<SparseMatrix<i32>>().is_err());
+    assert!("3 4\n0 1".parse::<SparseMatrix<i32>>().is_err());
+    assert!("3\n0 1 2".parse::<SparseMatrix<i32>>().is_err());
+    assert!("".parse::<SparseMatrix<i32>>().is_err());
+
+    // Leading blank lines still count towards the line numbers
+    let shifted = "\n\n3 4\n0 1 2\n3 0 1\n\n"
+        .parse::<SparseMatrix<i32>>()
+        .unwrap_err()
+        .to_string();
+    assert!(shifted.contains("line 5"));
+
+    let bad_header = "\n3\n0 1 2"
+        .parse::<SparseMatrix<i32>>()
+        .unwrap_err()
+        .to_string();
+    assert!(bad_header.contains("Line 2"));
+
+    // Same rules as from_json, duplicates are rejected and zeros dropped
+    let duplicate = "3 4\n0 1 2\n0 1 5"
+        .parse::<SparseMatrix<i32>>()
+        .unwrap_err()
+        .to_string();
+    assert!(duplicate.contains("Duplicate index (0, 1) at line 3"));
+    assert!("3 4\n0 1 0\n0 1 5".parse::<SparseMatrix<i32>>().is_err());
+
+    let zeros: SparseMatrix<i32> = "3 4\n0 1 0\n2 2 7".parse().unwrap();
+    assert_eq!(zeros.nnz(), 1);
+    assert_eq!(zeros.data.get(&(0, 1)), None);
+}
+
+#[test]
+fn sparse_dense_into() {
+    let dense = Matrix::new(vec![0.0, 1.5, 0.0, -2.0, 0.0, 0.0], (2, 3)).unwrap();
+
+    let sparse: SparseMatrix<f64> = dense.clone().into();
+    assert_eq!(sparse.shape(), (2, 3));
+    assert_eq!(sparse.nnz(), 2);
+    assert_eq!(sparse.at(0, 1), 1.5);
+    assert_eq!(sparse.at(1, 0), -2.0);
+
+    let back: Matrix<f64> = sparse.clone().into();
+    assert_eq!(back, dense);
+    assert_eq!(back.shape(), (2, 3));
+    assert_eq!(back.at(1, 2), 0.0);
+
+    // Same results as the explicit constructors
+    assert_eq!(sparse, SparseMatrix::from_dense(dense.clone()));
+    assert_eq!(back, Matrix::from_sparse(sparse));
+
+    fn nnz_of<M: Into<SparseMatrix<'static, i32>>>(m: M) -> usize {
+        m.into().nnz()
+    }
+    assert_eq!(nnz_of(Matrix::<i32>::eye(5)), 5);
+}
+
+#[test]
+fn sparse_display_coordinate_form() {
+    let big = SparseMatrix::<i32>::new(
+        smd![((9999, 0), 5), ((0, 9999), -3), ((42, 17), 8)],
+        (10000, 10000),
+    );
+
+    let printed = format!("{}", big);
+
+    assert!(printed.len() < 200);
+    assert!(printed.starts_with("SparseMatrix 10000x10000, nnz = 3\n"));
+
+    let entries: Vec<&str> = printed.lines().filter(|l| l.starts_with('(')).collect();
+    assert_eq!(
+        entries,
+        vec!["(0, 9999): -3", "(42, 17): 8", "(9999, 0): 5"]
+    );
+
+    // Small matrices are still printed densely
+    let small = SparseMatrix::<i32>::eye(2);
+    assert!(format!("{}", small).starts_with("1 0 \n0 1 \n"));
+
+    let tall = SparseMatrix::<i32>::new(smd![((20, 0), 1)], (21, 1));
+    assert!(format!("{}", tall).contains("(20, 0): 1"));
 }